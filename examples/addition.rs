@@ -1,75 +1,73 @@
-use noa_parser::bytes::matchers::match_number;
+use noa_parser::bytes::primitives::number::Number;
 use noa_parser::bytes::token::Token;
+use noa_parser::debug_tree::{DebugTree, TreeBuilder};
 use noa_parser::errors::ParseResult;
-use noa_parser::matcher::{Match, MatchSize};
-use noa_parser::recognizer::{Recognizable, recognize};
 use noa_parser::scanner::Scanner;
-use noa_parser::visitor::Visitor;
-
-/// The token number which recognizes numbers.
-struct TokenNumber;
-
-/// Implement the `Match` trait for the token number.
-impl Match<u8> for TokenNumber {
-    fn matcher(&self, data: &[u8]) -> (bool, usize) {
-        match_number(data)
-    }
-}
-
-/// Implement the `MatchSize` trait for the token number.
-impl MatchSize for TokenNumber {
-    fn size(&self) -> usize {
-        0
-    }
-}
-
-/// Define how to accept the token number.
-struct Number(usize);
-
-/// Implement the `Visitor` trait for the token number.
-impl Visitor<'_, u8> for Number {
-    fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
-        let raw_data = recognize(TokenNumber, scanner)?;
-        let str_data = std::str::from_utf8(raw_data)?;
-        let result = str_data.parse::<usize>()?;
-        Ok(Number(result))
-    }
-}
+use noa_parser::seq;
+use noa_parser::visitor::{Spanned, Visitor};
 
 /// Define the addition expression.
 #[derive(Debug)]
 #[allow(dead_code)]
 struct Addition {
-    rhs: usize,
-    lhs: usize,
-    result: usize,
+    rhs: Spanned<Number<usize>>,
+    lhs: Spanned<Number<usize>>,
+    result: Spanned<Number<usize>>,
 }
 
 /// Implement the `Visitor` trait for the addition expression.
 impl<'a> Visitor<'a, u8> for Addition {
     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
-        // Parse the first number
-        let lhs = Number::accept(scanner)?.0;
-        // Expect a whitespace and a plus token then whitespace
-        Token::Whitespace.recognize(scanner)?;
-        Token::Plus.recognize(scanner)?;
-        Token::Whitespace.recognize(scanner)?;
-        // Parse the second number
-        let rhs = Number::accept(scanner)?.0;
-        // Expect a whitespace and an equal token then whitespace
-        Token::Whitespace.recognize(scanner)?;
-        Token::Equal.recognize(scanner)?;
-        Token::Whitespace.recognize(scanner)?;
-        // Parse the result number
-        let result = Number::accept(scanner)?.0;
-        // Return the addition
+        seq!(scanner =>
+            lhs: Spanned<Number<usize>>, _ = Token::Whitespace, _ = Token::Plus, _ = Token::Whitespace,
+            rhs: Spanned<Number<usize>>, _ = Token::Whitespace, _ = Token::Equal, _ = Token::Whitespace,
+            result: Spanned<Number<usize>>,
+        );
         Ok(Addition { lhs, rhs, result })
     }
 }
 
+/// Dump the addition with its operands.
+impl DebugTree for Addition {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        out.node("Addition", |out| {
+            self.lhs.debug_tree(out);
+            self.rhs.debug_tree(out);
+            self.result.debug_tree(out);
+        });
+    }
+}
+
 fn main() {
     let data = b"1 + 2 = 3";
     let mut scanner = Scanner::new(data);
-    let result = Addition::accept(&mut scanner);
-    println!("{:?}", result);
+    let result = Spanned::<Addition>::accept(&mut scanner);
+    match result {
+        Ok(addition) => print!("{}", addition.to_debug_tree()),
+        Err(err) => println!("{err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Addition;
+    use noa_parser::debug_tree::DebugTree;
+    use noa_parser::scanner::Scanner;
+    use noa_parser::visitor::Spanned;
+    use noa_parser::visitor::Visitor;
+
+    #[test]
+    fn test_debug_tree() {
+        let mut scanner = Scanner::new(b"1 + 22 = 23");
+        let addition = Spanned::<Addition>::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(
+            addition.to_debug_tree(),
+            "\
+Addition [0..11]
+  Number(1) [0..1]
+  Number(22) [4..6]
+  Number(23) [9..11]
+"
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use noa_parser::chars::matchers::{match_char, match_number};
+use noa_parser::errors::ParseResult;
+use noa_parser::matcher::{Match, MatchSize};
+use noa_parser::recognizer::{Recognizable, recognize};
+use noa_parser::scanner::Scanner;
+use noa_parser::visitor::Visitor;
+
+/// A single char token.
+struct Token(char);
+
+const PLUS: Token = Token('+');
+const EQUAL: Token = Token('=');
+const WHITESPACE: Token = Token(' ');
+
+/// Implement the `Match` trait for the char token.
+impl Match<char> for Token {
+    fn matcher(&self, data: &[char]) -> (bool, usize) {
+        match_char(self.0, data)
+    }
+}
+
+/// Implement the `MatchSize` trait for the char token.
+impl MatchSize for Token {
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// The token number which recognizes numbers.
+struct TokenNumber;
+
+/// Implement the `Match` trait for the token number.
+impl Match<char> for TokenNumber {
+    fn matcher(&self, data: &[char]) -> (bool, usize) {
+        match_number(data)
+    }
+}
+
+/// Implement the `MatchSize` trait for the token number.
+impl MatchSize for TokenNumber {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+/// Define how to accept the token number.
+struct Number(usize);
+
+/// Implement the `Visitor` trait for the token number.
+impl Visitor<'_, char> for Number {
+    fn accept(scanner: &mut Scanner<char>) -> ParseResult<Self> {
+        let raw_data = recognize(TokenNumber, scanner)?;
+        let str_data = raw_data.iter().collect::<String>();
+        let result = str_data.parse::<usize>()?;
+        Ok(Number(result))
+    }
+}
+
+/// Define the addition expression.
+#[derive(Debug, PartialEq)]
+struct Addition {
+    rhs: usize,
+    lhs: usize,
+    result: usize,
+}
+
+/// Implement the `Visitor` trait for the addition expression.
+impl<'a> Visitor<'a, char> for Addition {
+    fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+        // Parse the first number
+        let lhs = Number::accept(scanner)?.0;
+        // Expect a whitespace and a plus token then whitespace
+        WHITESPACE.recognize(scanner)?;
+        PLUS.recognize(scanner)?;
+        WHITESPACE.recognize(scanner)?;
+        // Parse the second number
+        let rhs = Number::accept(scanner)?.0;
+        // Expect a whitespace and an equal token then whitespace
+        WHITESPACE.recognize(scanner)?;
+        EQUAL.recognize(scanner)?;
+        WHITESPACE.recognize(scanner)?;
+        // Parse the result number
+        let result = Number::accept(scanner)?.0;
+        // Return the addition
+        Ok(Addition { lhs, rhs, result })
+    }
+}
+
+fn main() {
+    let data = "1 + 2 = 3".chars().collect::<Vec<_>>();
+    let mut scanner = Scanner::new(&data);
+    let result = Addition::accept(&mut scanner);
+    println!("{:?}", result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addition() {
+        let data = "12 + 30 = 42".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        let result = Addition::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(
+            result,
+            Addition {
+                lhs: 12,
+                rhs: 30,
+                result: 42
+            }
+        );
+        assert!(scanner.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use noa_parser::dispatch::Dispatch;
+use noa_parser::errors::{ParseError, ParseResult};
+use noa_parser::scanner::Scanner;
+use noa_parser::visitor::Visitor;
+
+const PING: u8 = 0x01;
+const DATA: u8 = 0x02;
+const CLOSE: u8 = 0x03;
+
+/// Take `n` bytes from the scanner.
+fn take<'a>(scanner: &mut Scanner<'a, u8>, n: usize) -> ParseResult<&'a [u8]> {
+    let start = scanner.current_position();
+    let data = scanner
+        .data()
+        .get(start..start + n)
+        .ok_or(ParseError::UnexpectedEndOfInput)?;
+    scanner.bump_by(n);
+    Ok(data)
+}
+
+/// A frame, a tag byte followed by the payload of the message.
+#[derive(Debug, PartialEq)]
+enum Frame<'a> {
+    /// `0x01`
+    Ping,
+    /// `0x02`, a length byte then the data.
+    Data(&'a [u8]),
+    /// `0x03`, a big endian status code.
+    Close(u16),
+}
+
+impl<'a> Visitor<'a, u8> for Frame<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Dispatch::new(scanner)
+            .consume_discriminant(true)
+            .on(PING, |_| Ok(Frame::Ping))
+            .on(DATA, |scanner| {
+                let size = take(scanner, 1)?[0] as usize;
+                Ok(Frame::Data(take(scanner, size)?))
+            })
+            .on(CLOSE, |scanner| {
+                let code = take(scanner, 2)?;
+                Ok(Frame::Close(u16::from_be_bytes([code[0], code[1]])))
+            })
+            .finish()
+    }
+}
+
+/// Parse every frame of the data.
+fn frames(data: &[u8]) -> ParseResult<Vec<Frame<'_>>> {
+    let mut scanner = Scanner::new(data);
+    let mut frames = vec![];
+    while !scanner.is_empty() {
+        frames.push(Frame::accept(&mut scanner)?);
+    }
+    Ok(frames)
+}
+
+fn main() {
+    let data = [PING, DATA, 3, b'a', b'b', b'c', CLOSE, 0x03, 0xe8];
+    println!("{:?}", frames(&data));
+    println!("{:?}", frames(&[PING, 0x2a]));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CLOSE, DATA, Frame, PING, frames};
+    use noa_parser::errors::ParseError;
+
+    #[test]
+    fn test_frames() {
+        let data = [PING, DATA, 3, b'a', b'b', b'c', CLOSE, 0x03, 0xe8, PING];
+        assert_eq!(
+            frames(&data).expect("failed to parse"),
+            [
+                Frame::Ping,
+                Frame::Data(b"abc"),
+                Frame::Close(1000),
+                Frame::Ping
+            ]
+        );
+        assert!(matches!(
+            frames(&[DATA, 3, b'a']),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_tag() {
+        let err = frames(&[PING, 0x2a]).expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::UnknownDiscriminant { offset: 1, ref found, ref known }
+                if found == "42" && known == &["1", "2", "3"]
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Unknown discriminant 42 at offset 1, expected one of 1, 2, 3"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use noa_parser::bytes::primitives::number::Number;
+use noa_parser::errors::ParseResult;
+use noa_parser::scanner::Scanner;
+use noa_parser::visitor::Visitor;
+
+noa_parser::grammar! {
+    /// A whole arithmetic expression.
+    Program = Spaces Expression !.;
+    Expression = Term (AdditiveOperator Term)*;
+    Term = Factor (MultiplicativeOperator Factor)*;
+    Factor = Number<usize> Spaces / "(" Spaces Expression ")" Spaces / "-" Spaces Factor;
+    #[silent]
+    AdditiveOperator = ("+" / "-") Spaces;
+    #[silent]
+    MultiplicativeOperator = ("*" / "/") Spaces;
+    #[silent]
+    Spaces = (" " / "\t" / "\n")*;
+}
+
+fn check(data: &str) -> ParseResult<&str> {
+    let mut scanner = Scanner::new(data.as_bytes());
+    let program = Program::accept(&mut scanner)?;
+    Ok(std::str::from_utf8(program.data)?)
+}
+
+fn main() {
+    for data in ["1 + 2 * (3 - -4)", "1 + * 2", "(1 + 2", "1 2"] {
+        match check(data) {
+            Ok(expression) => println!("{expression:?} is valid"),
+            Err(err) => println!("{data:?}: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check;
+    use noa_parser::errors::ParseError;
+
+    #[test]
+    fn test_valid() {
+        assert_eq!(check("42").expect("failed to parse"), "42");
+        assert_eq!(
+            check(" 1 + 2 * (3 - -4) / 5 ").expect("failed to parse"),
+            " 1 + 2 * (3 - -4) / 5 "
+        );
+        assert!(check("((1))").is_ok());
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = check("1 + * 2").expect_err("should fail");
+        assert_eq!(err.to_string(), "Expected one of Term at offset 4");
+
+        let err = check("(1 + 2").expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::Expected { offset: 6, ref expected }
+                if expected.contains(&"\")\"") && expected.contains(&"AdditiveOperator")
+        ));
+
+        let err = check("1 2").expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::Expected { offset: 2, ref expected } if expected.contains(&"end of input")
+        ));
+
+        let err = check("").expect_err("should fail");
+        assert_eq!(err.to_string(), "Expected one of Expression at offset 0");
+    }
+}
@@ -0,0 +1,368 @@
+use noa_parser::bytes::components::groups::GroupKind;
+use noa_parser::bytes::matchers::{OperatorKind, match_keyword, match_operator};
+use noa_parser::bytes::primitives::identifier::Identifier;
+use noa_parser::bytes::primitives::number::Number;
+use noa_parser::bytes::token::Token;
+use noa_parser::errors::{ParseError, ParseResult};
+use noa_parser::matcher::{Match, MatchSize};
+use noa_parser::peek::{Until, peek};
+use noa_parser::recognizer::{Recognizable, recognize};
+use noa_parser::scanner::Scanner;
+use noa_parser::separated_list::SeparatedList;
+use noa_parser::visitor::Visitor;
+
+// ------------------------------------------------------------
+// Keywords
+// ------------------------------------------------------------
+
+/// A case-insensitive SQL keyword.
+#[derive(Debug, Clone, Copy)]
+struct Keyword(&'static str);
+
+const SELECT: Keyword = Keyword("SELECT");
+const FROM: Keyword = Keyword("FROM");
+const WHERE: Keyword = Keyword("WHERE");
+const AND: Keyword = Keyword("AND");
+const OR: Keyword = Keyword("OR");
+
+impl Match<u8> for Keyword {
+    fn matcher(&self, data: &[u8]) -> (bool, usize) {
+        match_keyword(self.0.as_bytes(), data)
+    }
+}
+
+impl MatchSize for Keyword {
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Recognize a keyword, returning false if absent.
+fn keyword(keyword: Keyword, scanner: &mut Scanner<u8>) -> ParseResult<bool> {
+    if scanner.is_empty() {
+        return Ok(false);
+    }
+    Ok(keyword.recognize(scanner)?.is_some())
+}
+
+/// Skip spaces, tabs and line breaks.
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    let count = scanner
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count();
+    scanner.bump_by(count);
+}
+
+/// Fail with a positioned error at the cursor.
+fn syntax_error<V>(scanner: &Scanner<u8>, reason: &'static str) -> ParseResult<V> {
+    Err(ParseError::Syntax {
+        offset: scanner.current_position(),
+        reason,
+    })
+}
+
+// ------------------------------------------------------------
+// Column list
+// ------------------------------------------------------------
+
+/// A column name preceded by optional whitespaces.
+struct Column<'a>(&'a str);
+
+impl<'a> Visitor<'a, u8> for Column<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        skip_whitespaces(scanner);
+        let Identifier(name) = Identifier::accept(scanner)?;
+        Ok(Column(name))
+    }
+}
+
+/// A comma preceded by optional whitespaces.
+struct SeparatorComma;
+
+impl<'a> Visitor<'a, u8> for SeparatorComma {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        skip_whitespaces(scanner);
+        recognize(Token::Comma, scanner)?;
+        Ok(SeparatorComma)
+    }
+}
+
+/// Parse the columns up to the `FROM` keyword.
+fn columns<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Vec<&'a str>> {
+    let Some(peeked) = peek(Until::new(FROM), scanner)? else {
+        return missing_from(scanner);
+    };
+    let list = peeked.data();
+    let list = &list[..list.len()
+        - list
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count()];
+
+    let columns = Scanner::new(list)
+        .visit::<SeparatedList<u8, Column, SeparatorComma>>()
+        .or_else(|_| syntax_error(scanner, "invalid column list"))?;
+    if columns.data.is_empty() {
+        return syntax_error(scanner, "expected columns");
+    }
+    scanner.bump_by(peeked.end_slice);
+    keyword(FROM, scanner)?;
+    Ok(columns.into_iter().map(|column| column.0).collect())
+}
+
+/// Walk the columns to report the missing `FROM` right after the last one.
+fn missing_from<V>(scanner: &mut Scanner<u8>) -> ParseResult<V> {
+    loop {
+        Column::accept(scanner).or_else(|_| syntax_error(scanner, "invalid column list"))?;
+        let cursor = scanner.current_position();
+        skip_whitespaces(scanner);
+        if scanner.first() != Some(&b',') {
+            scanner.rewind_to(cursor)?;
+            return syntax_error(scanner, "expected FROM");
+        }
+        scanner.bump_by(1);
+    }
+}
+
+// ------------------------------------------------------------
+// Conditions
+// ------------------------------------------------------------
+
+/// The right hand side of a comparison.
+#[derive(Debug, PartialEq)]
+enum Value<'a> {
+    Number(u64),
+    String(&'a str),
+    Column(&'a str),
+}
+
+impl<'a> Visitor<'a, u8> for Value<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        match scanner.first() {
+            Some(b'\'') => {
+                let Some(peeked) = peek(GroupKind::Quotes, scanner)? else {
+                    return syntax_error(scanner, "unterminated string");
+                };
+                let value = std::str::from_utf8(peeked.peeked_slice())?;
+                scanner.bump_by(peeked.end_slice);
+                Ok(Value::String(value))
+            }
+            Some(byte) if byte.is_ascii_digit() => Ok(Value::Number(Number::accept(scanner)?.0)),
+            Some(_) => Ok(Value::Column(Identifier::accept(scanner)?.0)),
+            None => Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+}
+
+/// A boolean expression tree over comparisons.
+#[derive(Debug, PartialEq)]
+enum Condition<'a> {
+    Comparison {
+        column: &'a str,
+        op: OperatorKind,
+        value: Value<'a>,
+    },
+    And(Box<Condition<'a>>, Box<Condition<'a>>),
+    Or(Box<Condition<'a>>, Box<Condition<'a>>),
+}
+
+fn comparison_operator(scanner: &mut Scanner<u8>) -> ParseResult<OperatorKind> {
+    let (found, size, kind) = match_operator(scanner.remaining());
+    match kind {
+        OperatorKind::Equal
+        | OperatorKind::NotEq
+        | OperatorKind::Less
+        | OperatorKind::LessEq
+        | OperatorKind::Greater
+        | OperatorKind::GreaterEq
+            if found =>
+        {
+            scanner.bump_by(size);
+            Ok(kind)
+        }
+        _ => syntax_error(scanner, "expected a comparison operator"),
+    }
+}
+
+/// `comparison | "(" or ")"`
+fn primary<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Condition<'a>> {
+    skip_whitespaces(scanner);
+    if scanner.first() == Some(&b'(') {
+        scanner.bump_by(1);
+        let condition = or(scanner)?;
+        skip_whitespaces(scanner);
+        if scanner.is_empty() || Token::CloseParen.recognize(scanner)?.is_none() {
+            return syntax_error(scanner, "expected `)`");
+        }
+        return Ok(condition);
+    }
+    let Identifier(column) = Identifier::accept(scanner)?;
+    skip_whitespaces(scanner);
+    let op = comparison_operator(scanner)?;
+    skip_whitespaces(scanner);
+    let value = Value::accept(scanner)?;
+    Ok(Condition::Comparison { column, op, value })
+}
+
+/// `primary ("AND" primary)*`
+fn and<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Condition<'a>> {
+    let mut lhs = primary(scanner)?;
+    loop {
+        skip_whitespaces(scanner);
+        if !keyword(AND, scanner)? {
+            return Ok(lhs);
+        }
+        let rhs = primary(scanner)?;
+        lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+    }
+}
+
+/// `and ("OR" and)*`
+fn or<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Condition<'a>> {
+    let mut lhs = and(scanner)?;
+    loop {
+        skip_whitespaces(scanner);
+        if !keyword(OR, scanner)? {
+            return Ok(lhs);
+        }
+        let rhs = and(scanner)?;
+        lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+    }
+}
+
+// ------------------------------------------------------------
+// Select
+// ------------------------------------------------------------
+
+/// `SELECT columns FROM table [WHERE condition];`
+#[derive(Debug, PartialEq)]
+struct Select<'a> {
+    columns: Vec<&'a str>,
+    table: &'a str,
+    condition: Option<Condition<'a>>,
+}
+
+impl<'a> Visitor<'a, u8> for Select<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        skip_whitespaces(scanner);
+        if !keyword(SELECT, scanner)? {
+            return syntax_error(scanner, "expected SELECT");
+        }
+        let columns = columns(scanner)?;
+        skip_whitespaces(scanner);
+        let Identifier(table) = Identifier::accept(scanner)?;
+        skip_whitespaces(scanner);
+        let condition = if keyword(WHERE, scanner)? {
+            Some(or(scanner)?)
+        } else {
+            None
+        };
+        skip_whitespaces(scanner);
+        if scanner.is_empty() || Token::Semicolon.recognize(scanner)?.is_none() {
+            return syntax_error(scanner, "expected `;`");
+        }
+        Ok(Select {
+            columns,
+            table,
+            condition,
+        })
+    }
+}
+
+fn main() {
+    let data = b"SELECT col1, col2 FROM table WHERE col1 = 'x' AND col2 > 42;";
+    let mut scanner = Scanner::new(data);
+    let result = Select::accept(&mut scanner);
+    println!("{:?}", result);
+    // Ok(Select { columns: ["col1", "col2"], table: "table", condition: Some(And(...)) })
+
+    let data = b"SELECT col1, col2 WHERE col1 = 'x';";
+    let mut scanner = Scanner::new(data);
+    let result = Select::accept(&mut scanner);
+    println!("{:?}", result); // Err(Syntax { offset: 17, reason: "expected FROM" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(data: &[u8]) -> ParseResult<Select<'_>> {
+        Select::accept(&mut Scanner::new(data))
+    }
+
+    fn comparison<'a>(column: &'a str, op: OperatorKind, value: Value<'a>) -> Box<Condition<'a>> {
+        Box::new(Condition::Comparison { column, op, value })
+    }
+
+    #[test]
+    fn test_select() {
+        let select = parse(b"SELECT col1, col2 FROM table WHERE col1 = 'x' AND col2 > 42;")
+            .expect("failed to parse");
+        assert_eq!(
+            select,
+            Select {
+                columns: vec!["col1", "col2"],
+                table: "table",
+                condition: Some(Condition::And(
+                    comparison("col1", OperatorKind::Equal, Value::String("x")),
+                    comparison("col2", OperatorKind::Greater, Value::Number(42)),
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn test_without_where() {
+        let select = parse(b"select id from users ;").expect("failed to parse");
+        assert_eq!(select.columns, vec!["id"]);
+        assert_eq!(select.table, "users");
+        assert_eq!(select.condition, None);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let select = parse(b"SELECT a FROM t WHERE a = 1 OR b <= c AND (c != 'it' OR d >= 2);");
+        let Condition::Or(lhs, rhs) = select.expect("failed to parse").condition.unwrap() else {
+            panic!("expected OR at the root");
+        };
+        assert_eq!(lhs, comparison("a", OperatorKind::Equal, Value::Number(1)));
+        let Condition::And(lhs, rhs) = *rhs else {
+            panic!("expected AND");
+        };
+        assert_eq!(
+            lhs,
+            comparison("b", OperatorKind::LessEq, Value::Column("c"))
+        );
+        assert!(matches!(*rhs, Condition::Or(_, _)));
+    }
+
+    fn assert_error(data: &[u8], expected_offset: usize, expected_reason: &str) {
+        match parse(data) {
+            Err(ParseError::Syntax { offset, reason }) => {
+                assert_eq!((offset, reason), (expected_offset, expected_reason));
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_error(b"SELECT col1, col2 WHERE col1 = 'x';", 17, "expected FROM");
+        assert_error(b"SELECT col1, col2", 17, "expected FROM");
+        assert_error(
+            b"SELECT col1 FROM t WHERE col1 = 'x;",
+            32,
+            "unterminated string",
+        );
+        assert_error(b"SELECT col1 col2 FROM t;", 6, "invalid column list");
+        assert_error(
+            b"SELECT a FROM t WHERE a + 1;",
+            24,
+            "expected a comparison operator",
+        );
+        assert_error(b"SELECT a FROM t", 15, "expected `;`");
+        assert_error(b"UPDATE t;", 0, "expected SELECT");
+    }
+}
@@ -0,0 +1,20 @@
+use noa_parser::Match;
+use noa_parser::matcher::Match as _;
+
+/// Handle turbofish operator.
+#[derive(Match)]
+#[pattern("::<>")]
+#[chars]
+struct Turbofish;
+
+fn main() {
+    let data = [':', ':', '<', '>', 'b'];
+    let scanner = noa_parser::scanner::Scanner::new(&data);
+    let result = Turbofish.matcher(&scanner);
+    println!("{:?}", result); // ( true, 4 ) because the turbofish operator is 4 char
+
+    let data = ['a', ':', ':', '<', '>', 'b'];
+    let scanner = noa_parser::scanner::Scanner::new(&data);
+    let result = Turbofish.matcher(&scanner);
+    println!("{:?}", result); // ( false, 0 ) because doesn't match the turbofish operator
+}
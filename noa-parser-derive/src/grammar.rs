@@ -0,0 +1,380 @@
+//! Implementation of `grammar!`.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use std::collections::HashSet;
+use std::fmt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Ident, LitByteStr, LitChar, LitStr, Path, Token, Visibility, parenthesized};
+
+/// A grammar, a list of rules.
+pub struct Grammar {
+    rules: Vec<Rule>,
+}
+
+/// A rule, `Name = expression;`.
+struct Rule {
+    attributes: Vec<Attribute>,
+    silent: bool,
+    visibility: Visibility,
+    name: Ident,
+    expression: Expression,
+}
+
+/// A parsing expression.
+enum Expression {
+    /// `"literal"` or `b"literal"`
+    Literal(Vec<u8>),
+    /// `'a'..='z'`
+    Range(u8, u8),
+    /// `.`
+    Any,
+    /// A reference to a rule or to a `Visitor` type.
+    Reference(Path),
+    /// `a b`
+    Sequence(Vec<Expression>),
+    /// `a / b`
+    Choice(Vec<Expression>),
+    /// `a*`
+    ZeroOrMore(Box<Expression>),
+    /// `a+`
+    OneOrMore(Box<Expression>),
+    /// `a?`
+    Optional(Box<Expression>),
+    /// `&a`
+    And(Box<Expression>),
+    /// `!a`
+    Not(Box<Expression>),
+}
+
+impl Parse for Grammar {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut rules = vec![];
+        while !input.is_empty() {
+            let mut attributes = input.call(Attribute::parse_outer)?;
+            let count = attributes.len();
+            attributes.retain(|attribute| !attribute.path().is_ident("silent"));
+            let silent = attributes.len() < count;
+            let visibility = input.parse()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let expression = parse_choice(input)?;
+            input.parse::<Token![;]>()?;
+            rules.push(Rule {
+                attributes,
+                silent,
+                visibility,
+                name,
+                expression,
+            });
+        }
+        Ok(Grammar { rules })
+    }
+}
+
+fn parse_choice(input: ParseStream) -> syn::Result<Expression> {
+    let mut alternatives = vec![parse_sequence(input)?];
+    while input.peek(Token![/]) {
+        input.parse::<Token![/]>()?;
+        alternatives.push(parse_sequence(input)?);
+    }
+    Ok(match alternatives.len() {
+        1 => alternatives.remove(0),
+        _ => Expression::Choice(alternatives),
+    })
+}
+
+fn parse_sequence(input: ParseStream) -> syn::Result<Expression> {
+    let mut elements = vec![];
+    while !input.is_empty() && !input.peek(Token![/]) && !input.peek(Token![;]) {
+        elements.push(parse_prefix(input)?);
+    }
+    Ok(match elements.len() {
+        0 => return Err(input.error("expected an expression")),
+        1 => elements.remove(0),
+        _ => Expression::Sequence(elements),
+    })
+}
+
+fn parse_prefix(input: ParseStream) -> syn::Result<Expression> {
+    if input.peek(Token![&]) {
+        input.parse::<Token![&]>()?;
+        return Ok(Expression::And(Box::new(parse_suffix(input)?)));
+    }
+    if input.peek(Token![!]) {
+        input.parse::<Token![!]>()?;
+        return Ok(Expression::Not(Box::new(parse_suffix(input)?)));
+    }
+    parse_suffix(input)
+}
+
+fn parse_suffix(input: ParseStream) -> syn::Result<Expression> {
+    let expression = parse_primary(input)?;
+    if input.peek(Token![*]) {
+        input.parse::<Token![*]>()?;
+        return Ok(Expression::ZeroOrMore(Box::new(expression)));
+    }
+    if input.peek(Token![+]) {
+        input.parse::<Token![+]>()?;
+        return Ok(Expression::OneOrMore(Box::new(expression)));
+    }
+    if input.peek(Token![?]) {
+        input.parse::<Token![?]>()?;
+        return Ok(Expression::Optional(Box::new(expression)));
+    }
+    Ok(expression)
+}
+
+fn parse_primary(input: ParseStream) -> syn::Result<Expression> {
+    if input.peek(syn::token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        let expression = parse_choice(&content)?;
+        if !content.is_empty() {
+            return Err(content.error("expected `/` or `)`"));
+        }
+        return Ok(expression);
+    }
+    if input.peek(LitStr) {
+        let literal = input.parse::<LitStr>()?;
+        return non_empty(literal.value().into_bytes(), &literal);
+    }
+    if input.peek(LitByteStr) {
+        let literal = input.parse::<LitByteStr>()?;
+        return non_empty(literal.value(), &literal);
+    }
+    if input.peek(LitChar) {
+        let start = ascii(&input.parse::<LitChar>()?)?;
+        input.parse::<Token![..=]>()?;
+        let end = ascii(&input.parse::<LitChar>()?)?;
+        return Ok(Expression::Range(start, end));
+    }
+    if input.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+        return Ok(Expression::Any);
+    }
+    Ok(Expression::Reference(input.parse()?))
+}
+
+fn non_empty(bytes: Vec<u8>, literal: &impl ToTokens) -> syn::Result<Expression> {
+    if bytes.is_empty() {
+        return Err(syn::Error::new_spanned(
+            literal,
+            "the literal must not be empty",
+        ));
+    }
+    Ok(Expression::Literal(bytes))
+}
+
+fn ascii(literal: &LitChar) -> syn::Result<u8> {
+    let char = literal.value();
+    if !char.is_ascii() {
+        return Err(syn::Error::new_spanned(
+            literal,
+            "the range bounds must be ASCII",
+        ));
+    }
+    Ok(char as u8)
+}
+
+/// Display the expression as written in the grammar, used in errors.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Literal(bytes) => write!(f, "{:?}", String::from_utf8_lossy(bytes)),
+            Expression::Range(start, end) => {
+                write!(f, "{:?}..={:?}", *start as char, *end as char)
+            }
+            Expression::Any => write!(f, "."),
+            Expression::Reference(path) => write!(f, "{}", path_name(path)),
+            Expression::Sequence(elements) => {
+                let elements = elements.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                write!(f, "({})", elements.join(" "))
+            }
+            Expression::Choice(alternatives) => {
+                let alternatives = alternatives
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>();
+                write!(f, "({})", alternatives.join(" / "))
+            }
+            Expression::ZeroOrMore(expression) => write!(f, "{expression}*"),
+            Expression::OneOrMore(expression) => write!(f, "{expression}+"),
+            Expression::Optional(expression) => write!(f, "{expression}?"),
+            Expression::And(expression) => write!(f, "&{expression}"),
+            Expression::Not(expression) => write!(f, "!{expression}"),
+        }
+    }
+}
+
+fn path_name(path: &Path) -> String {
+    path.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Generate the code matching an expression.
+///
+/// The code is a `bool` expression using the `scanner` and `context`
+/// variables, which leaves the scanner untouched when it evaluates to false.
+fn expand_expression(expression: &Expression, rules: &HashSet<String>) -> TokenStream {
+    match expression {
+        Expression::Literal(bytes) => {
+            let pattern = LitByteStr::new(bytes, proc_macro2::Span::call_site());
+            let name = expression.to_string();
+            quote!(context.literal(scanner, #pattern, #name))
+        }
+        Expression::Range(start, end) => {
+            let name = expression.to_string();
+            quote!(context.range(scanner, #start, #end, #name))
+        }
+        Expression::Any => quote!(context.any(scanner)),
+        Expression::Reference(path) => match path.get_ident() {
+            Some(ident) if rules.contains(&ident.to_string()) => {
+                quote!(#ident::parse_rule(scanner, context))
+            }
+            _ => {
+                let name = path_name(path);
+                quote!(context.visitor::<#path>(scanner, #name))
+            }
+        },
+        Expression::Sequence(elements) => {
+            let elements = elements
+                .iter()
+                .map(|element| expand_expression(element, rules));
+            quote!({
+                let start = scanner.current_position();
+                #(#elements)&&* || {
+                    context.rewind(scanner, start);
+                    false
+                }
+            })
+        }
+        Expression::Choice(alternatives) => {
+            let alternatives = alternatives
+                .iter()
+                .map(|alternative| expand_expression(alternative, rules));
+            quote!((#(#alternatives)||*))
+        }
+        Expression::ZeroOrMore(inner) => repeat(&expand_expression(inner, rules)),
+        Expression::OneOrMore(inner) => {
+            let inner = expand_expression(inner, rules);
+            let rest = repeat(&inner);
+            quote!((#inner && #rest))
+        }
+        Expression::Optional(inner) => {
+            let inner = expand_expression(inner, rules);
+            quote!({
+                let _ = #inner;
+                true
+            })
+        }
+        Expression::And(inner) => {
+            let inner = expand_expression(inner, rules);
+            quote!({
+                let start = scanner.current_position();
+                let matched = #inner;
+                context.rewind(scanner, start);
+                matched
+            })
+        }
+        Expression::Not(inner) => {
+            let name = match **inner {
+                Expression::Any => "end of input".to_string(),
+                _ => expression.to_string(),
+            };
+            let inner = expand_expression(inner, rules);
+            quote!({
+                let start = scanner.current_position();
+                if #inner {
+                    context.rewind(scanner, start);
+                    context.fail(start, #name);
+                    false
+                } else {
+                    true
+                }
+            })
+        }
+    }
+}
+
+/// Repeat a matching code while it consumes data.
+fn repeat(inner: &TokenStream) -> TokenStream {
+    quote!({
+        loop {
+            let start = scanner.current_position();
+            // stop on an element matching nothing, which would loop forever
+            if !#inner || scanner.current_position() == start {
+                break;
+            }
+        }
+        true
+    })
+}
+
+pub fn expand(grammar: Grammar) -> syn::Result<TokenStream> {
+    let mut rules = HashSet::new();
+    for rule in &grammar.rules {
+        if !rules.insert(rule.name.to_string()) {
+            return Err(syn::Error::new_spanned(&rule.name, "duplicate rule"));
+        }
+    }
+
+    let rules = grammar.rules.iter().map(|rule| {
+        let Rule {
+            attributes,
+            silent,
+            visibility,
+            name,
+            expression,
+        } = rule;
+        let label = name.to_string();
+        let body = expand_expression(expression, &rules);
+        quote! {
+            #(#attributes)*
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            #visibility struct #name<'a> {
+                /// Offset of the recognized data.
+                pub offset: usize,
+                /// The recognized data.
+                pub data: &'a [u8],
+            }
+
+            impl<'a> #name<'a> {
+                fn parse_body(
+                    scanner: &mut ::noa_parser::scanner::Scanner<'a, u8>,
+                    context: &mut ::noa_parser::grammar::RuleContext,
+                ) -> bool {
+                    #body
+                }
+
+                fn parse_rule(
+                    scanner: &mut ::noa_parser::scanner::Scanner<'a, u8>,
+                    context: &mut ::noa_parser::grammar::RuleContext,
+                ) -> bool {
+                    let mark = context.enter(scanner, #silent);
+                    let matched = Self::parse_body(scanner, context);
+                    context.leave(scanner, mark, matched, #label)
+                }
+            }
+
+            impl<'a> ::noa_parser::visitor::Visitor<'a, u8> for #name<'a> {
+                fn accept(
+                    scanner: &mut ::noa_parser::scanner::Scanner<'a, u8>,
+                ) -> ::noa_parser::errors::ParseResult<Self> {
+                    let offset = scanner.current_position();
+                    let mut context = ::noa_parser::grammar::RuleContext::new(offset);
+                    // the rule itself is the entry point, its elements are reported
+                    // a stopped parse may still match, through optional elements
+                    if !Self::parse_body(scanner, &mut context) || context.is_stopped() {
+                        context.rewind(scanner, offset);
+                        return Err(context.into_error());
+                    }
+                    Ok(#name {
+                        offset,
+                        data: &scanner.data()[offset..scanner.current_position()],
+                    })
+                }
+            }
+        }
+    });
+    Ok(quote!(#(#rules)*))
+}
@@ -0,0 +1,78 @@
+//! Derive macros for the noa-parser framework.
+//!
+//! The macros are re-exported by `noa-parser` when its `derive` feature is
+//! enabled.
+
+mod grammar;
+mod match_derive;
+mod visitor_derive;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// Derive `Match<u8>` and `MatchSize` for a literal pattern.
+///
+/// # Attributes
+///
+/// * `#[pattern("...")]` or `#[pattern(b"...")]` - The pattern to match, required.
+/// * `#[no_case]` - Ignore the ASCII case of the data.
+/// * `#[keyword]` - The pattern must not be followed by an identifier
+///   character.
+/// * `#[chars]` - Also derive `Match<char>`, the pattern must be ASCII.
+#[proc_macro_derive(Match, attributes(pattern, no_case, keyword, chars))]
+pub fn derive_match(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match_derive::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Visitor<'a, u8>` for a struct whose fields are visitors, accepted
+/// in their declaration order.
+///
+/// The data lifetime is the first lifetime of the struct, if any. The scanner
+/// is rewound to its position before the struct if a field fails.
+///
+/// # Attributes
+///
+/// * `#[separator(expression)]` - A recognizable object required between two
+///   fields, `Token::Whitespace` for example, discarded.
+#[proc_macro_derive(Visitor, attributes(separator))]
+pub fn derive_visitor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    visitor_derive::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Define `Visitor` types from PEG rules.
+///
+/// Each rule `Name = expression;` defines a `Name<'a>` struct holding the
+/// recognized data and its offset, implementing `Visitor<'a, u8>`.
+///
+/// # Expressions
+///
+/// * `"literal"` or `b"literal"` - Match the bytes exactly.
+/// * `'a'..='z'` - Match a byte of an ASCII range.
+/// * `.` - Match any byte.
+/// * `Name` - Match another rule of the grammar, or any `Visitor<'a, u8>`
+///   type, `Number<usize>` for example.
+/// * `a b` - Match `a` then `b`.
+/// * `a / b` - Match `a`, or `b` if `a` fails.
+/// * `a*`, `a+`, `a?` - Match `a` zero or more times, one or more times,
+///   or optionally.
+/// * `&a`, `!a` - Succeed if `a` matches, or doesn't match, without
+///   consuming anything.
+/// * `(a)` - Group an expression.
+///
+/// # Attributes
+///
+/// * `#[silent]` - Report the rule under its own name only, ignoring the
+///   failures of its elements, for whitespaces or tokens for example.
+#[proc_macro]
+pub fn grammar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as grammar::Grammar);
+    grammar::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,185 @@
+//! Implementation of `#[derive(Match)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Lit, LitByteStr};
+
+/// The options read from the attributes.
+struct Options {
+    pattern: LitByteStr,
+    no_case: bool,
+    keyword: bool,
+    chars: bool,
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut pattern = None;
+    let mut no_case = false;
+    let mut keyword = false;
+    let mut chars = false;
+
+    for attribute in &input.attrs {
+        let path = attribute.path();
+        if path.is_ident("pattern") {
+            if pattern.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    "duplicate `pattern` attribute",
+                ));
+            }
+            let bytes = match attribute.parse_args::<Lit>()? {
+                Lit::Str(literal) => LitByteStr::new(literal.value().as_bytes(), literal.span()),
+                Lit::ByteStr(literal) => literal,
+                literal => {
+                    return Err(syn::Error::new_spanned(
+                        literal,
+                        "expected a string or byte string pattern",
+                    ));
+                }
+            };
+            if bytes.value().is_empty() {
+                return Err(syn::Error::new_spanned(
+                    bytes,
+                    "the pattern must not be empty",
+                ));
+            }
+            pattern = Some(bytes);
+        } else if path.is_ident("no_case") {
+            attribute.meta.require_path_only()?;
+            no_case = true;
+        } else if path.is_ident("keyword") {
+            attribute.meta.require_path_only()?;
+            keyword = true;
+        } else if path.is_ident("chars") {
+            attribute.meta.require_path_only()?;
+            chars = true;
+        }
+    }
+
+    let Some(pattern) = pattern else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[pattern(\"...\")]` attribute",
+        ));
+    };
+    if chars && !pattern.value().is_ascii() {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            "`#[chars]` requires an ASCII pattern",
+        ));
+    }
+
+    Ok(Options {
+        pattern,
+        no_case,
+        keyword,
+        chars,
+    })
+}
+
+/// Generate the body of a matcher over a slice of `symbol` elements.
+fn matcher_body(options: &Options, symbol: TokenStream) -> TokenStream {
+    let pattern = &options.pattern;
+    let compare = if options.no_case {
+        quote!(expected.eq_ignore_ascii_case(found))
+    } else {
+        quote!(expected == found)
+    };
+    let boundary = if options.keyword {
+        quote! {
+            if let Some(next) = data.get(PATTERN.len()) {
+                if next.is_ascii_alphanumeric() || *next == (b'_' as #symbol) {
+                    return (false, 0);
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        const PATTERN: &[u8] = #pattern;
+        if data.len() < PATTERN.len() {
+            return (false, 0);
+        }
+        let matched = PATTERN
+            .iter()
+            .zip(data)
+            .all(|(expected, found)| {
+                let expected = &(*expected as #symbol);
+                #compare
+            });
+        if !matched {
+            return (false, 0);
+        }
+        #boundary
+        (true, PATTERN.len())
+    }
+}
+
+/// Generate the body of the streaming matcher over bytes, reporting a
+/// prefix of the pattern as incomplete, the pattern itself too for a keyword
+/// as an identifier character may follow it.
+fn streaming_body(options: &Options) -> TokenStream {
+    let pattern = &options.pattern;
+    let compare = if options.no_case {
+        quote!(expected.eq_ignore_ascii_case(found))
+    } else {
+        quote!(expected == found)
+    };
+    let boundary = usize::from(options.keyword);
+
+    quote! {
+        const PATTERN: &[u8] = #pattern;
+        if data.len() < PATTERN.len() + #boundary
+            && PATTERN.iter().zip(data).all(|(expected, found)| #compare)
+        {
+            return ::noa_parser::matcher::MatchResult::Incomplete(
+                ::noa_parser::matcher::Needed::Size(PATTERN.len() + #boundary - data.len()),
+            );
+        }
+        ::noa_parser::matcher::Match::<u8>::matcher(self, data).into()
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let options = parse_options(&input)?;
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let size = options.pattern.value().len();
+
+    let bytes_body = matcher_body(&options, quote!(u8));
+    let streaming_body = streaming_body(&options);
+    let chars_impl = if options.chars {
+        let chars_body = matcher_body(&options, quote!(char));
+        quote! {
+            impl #impl_generics ::noa_parser::matcher::Match<char> for #name #type_generics #where_clause {
+                fn matcher(&self, data: &[char]) -> (bool, usize) {
+                    #chars_body
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::noa_parser::matcher::Match<u8> for #name #type_generics #where_clause {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                #bytes_body
+            }
+
+            fn matcher2(&self, data: &[u8]) -> ::noa_parser::matcher::MatchResult {
+                #streaming_body
+            }
+        }
+
+        impl #impl_generics ::noa_parser::matcher::MatchSize for #name #type_generics #where_clause {
+            fn size(&self) -> usize {
+                #size
+            }
+        }
+
+        #chars_impl
+    })
+}
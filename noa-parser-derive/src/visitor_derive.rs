@@ -0,0 +1,92 @@
+//! Implementation of `#[derive(Visitor)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Expr, Fields, GenericParam, Lifetime, LifetimeParam};
+
+/// Read the separator recognized between two fields, if any.
+fn parse_separator(input: &DeriveInput) -> syn::Result<Option<Expr>> {
+    let mut separator = None;
+    for attribute in &input.attrs {
+        if attribute.path().is_ident("separator") {
+            if separator.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    "duplicate `separator` attribute",
+                ));
+            }
+            separator = Some(attribute.parse_args::<Expr>()?);
+        }
+    }
+    Ok(separator)
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let separator = parse_separator(&input)?;
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`Visitor` can only be derived for structs",
+        ));
+    };
+
+    // the data lifetime is the one of the struct, or a new one
+    let mut generics = input.generics.clone();
+    let lifetime = match generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'a", Span::call_site());
+            generics.params.insert(
+                0,
+                GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+
+    let bindings = (0..data.fields.len())
+        .map(|index| format_ident!("field{index}"))
+        .collect::<Vec<_>>();
+    let steps = data
+        .fields
+        .iter()
+        .zip(&bindings)
+        .enumerate()
+        .map(|(index, (field, binding))| {
+            let ty = &field.ty;
+            let separator = match (&separator, index) {
+                (Some(separator), 1..) => {
+                    quote!(::noa_parser::recognizer::recognize(#separator, scanner)?;)
+                }
+                _ => quote!(),
+            };
+            quote! {
+                #separator
+                let #binding = <#ty as ::noa_parser::visitor::Visitor<#lifetime, u8>>::accept(scanner)?;
+            }
+        });
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(#name { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#name(#(#bindings),*)),
+        Fields::Unit => quote!(#name),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::noa_parser::visitor::Visitor<#lifetime, u8> for #name #type_generics #where_clause {
+            fn accept(
+                scanner: &mut ::noa_parser::scanner::Scanner<#lifetime, u8>,
+            ) -> ::noa_parser::errors::ParseResult<Self> {
+                scanner.rollback_on_err(|scanner| {
+                    #(#steps)*
+                    Ok(#construct)
+                })
+            }
+        }
+    })
+}
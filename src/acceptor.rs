@@ -72,7 +72,7 @@ impl<'a, T, V> Acceptor<'a, '_, T, V> {
                 self.data = Some(transformer(found));
             }
             Err(ParseError::UnexpectedToken) => {
-                self.scanner.jump_to(cursor);
+                self.scanner.rewind_to(cursor)?;
             }
             Err(err) => {
                 return Err(err);
@@ -197,6 +197,9 @@ where
     }
 }
 
+/// A boxed group matcher as returned by [GroupKind].
+type GroupMatcher<'a> = Box<dyn Fn(&'a [u8]) -> ParseResult<PeekResult<Token, Token>> + 'a>;
+
 /// Types of groups
 ///
 /// This enum is used to specify the type of a group in a matcher.
@@ -210,8 +213,7 @@ pub enum GroupKind {
 }
 
 impl GroupKind {
-    fn matcher<'a>(&self) -> Box<dyn Fn(&'a [u8]) -> ParseResult<PeekResult<Token, Token>> + 'a>
-where {
+    fn matcher<'a>(&self) -> GroupMatcher<'a> {
         match self {
             GroupKind::Parenthesis => Box::new(match_group(Token::OpenParen, Token::CloseParen)),
             GroupKind::Quotes => {
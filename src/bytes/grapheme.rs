@@ -0,0 +1,44 @@
+//! Extended grapheme cluster boundaries, see UAX #29.
+//!
+//! The boundaries are those of the `unicode-segmentation` crate, computed
+//! over the valid UTF-8 data at the start of a byte slice.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The number of bytes decoded first to look for a boundary, doubled as long
+/// as the cluster may go on past them.
+const WINDOW: usize = 64;
+
+/// Compute the length of the grapheme cluster at the start of a byte slice.
+///
+/// A cluster ends at an invalid UTF-8 sequence, which is only reported if
+/// it starts the slice.
+///
+/// # Returns
+///
+/// The length in bytes of the cluster, 0 if the slice is empty, or the
+/// offset of the invalid UTF-8 sequence.
+pub(crate) fn next_boundary(data: &[u8]) -> Result<usize, usize> {
+    let mut window = WINDOW;
+    loop {
+        let end = window.min(data.len());
+        let (valid, complete) = match std::str::from_utf8(&data[..end]) {
+            Ok(_) => (end, end == data.len()),
+            // the text ends at an invalid sequence
+            Err(err) if err.error_len().is_some() => (err.valid_up_to(), true),
+            // the window, or the data, ends in the middle of a char
+            Err(err) => (err.valid_up_to(), end == data.len()),
+        };
+        // SAFETY: the bytes up to `valid` were checked to be UTF-8 above
+        let text = unsafe { std::str::from_utf8_unchecked(&data[..valid]) };
+        let size = text.graphemes(true).next().map_or(0, str::len);
+        // a boundary before the end of the text doesn't depend on what follows
+        if size < text.len() || complete {
+            return match size {
+                0 if !data.is_empty() => Err(0),
+                _ => Ok(size),
+            };
+        }
+        window *= 2;
+    }
+}
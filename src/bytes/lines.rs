@@ -0,0 +1,166 @@
+//! Parse line oriented data, one entry per line.
+//!
+//! Every line gets its own scanner, so that a bad line doesn't stop the
+//! parse of the following ones. The results are tagged with the 1-based
+//! number of their line and the offset of the line in the data.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+
+/// The result of the parse of a line.
+#[derive(Debug)]
+pub struct ParsedLine<V> {
+    /// The 1-based number of the line.
+    pub line: usize,
+    /// The offset of the first byte of the line in the data.
+    pub offset: usize,
+    /// The entry of the line, the offsets of the errors being relative to the
+    /// line.
+    pub result: ParseResult<V>,
+}
+
+/// A lazy iterator over the entries of the lines of some data.
+///
+/// Lines end with `\n` or `\r\n`, the last one may be unterminated. A line
+/// must be consumed entirely by the visitor.
+pub struct Lines<'a, V, F = fn(&[u8]) -> bool> {
+    scanner: Scanner<'a, u8>,
+    line: usize,
+    skip: F,
+    _marker: PhantomData<V>,
+}
+
+impl<'a, V> Lines<'a, V> {
+    /// Create a new iterator over the lines of the data, none being skipped.
+    pub fn new(data: &'a [u8]) -> Self {
+        Lines {
+            scanner: Scanner::new(data),
+            line: 0,
+            skip: |_| false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, V, F> Lines<'a, V, F> {
+    /// Skip the lines for which `skip` returns true, blank or comment lines
+    /// for example. The line numbers still count them.
+    ///
+    /// # Arguments
+    ///
+    /// * `skip` - Called on the lines, without their terminator.
+    pub fn skip_if<G: FnMut(&[u8]) -> bool>(self, skip: G) -> Lines<'a, V, G> {
+        Lines {
+            scanner: self.scanner,
+            line: self.line,
+            skip,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, V: Visitor<'a, u8>, F: FnMut(&[u8]) -> bool> Iterator for Lines<'a, V, F> {
+    type Item = ParsedLine<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let offset = self.scanner.current_position();
+            let data = self.scanner.consume_line()?;
+            self.line += 1;
+            if (self.skip)(data) {
+                continue;
+            }
+            return Some(ParsedLine {
+                line: self.line,
+                offset,
+                result: parse_line(data),
+            });
+        }
+    }
+}
+
+/// Parse a line, which must be consumed entirely.
+fn parse_line<'a, V: Visitor<'a, u8>>(data: &'a [u8]) -> ParseResult<V> {
+    let mut scanner = Scanner::new(data);
+    let value = V::accept(&mut scanner)?;
+    if !scanner.is_empty() {
+        return Err(ParseError::Syntax {
+            offset: scanner.current_position(),
+            reason: "unexpected data after the entry",
+        });
+    }
+    Ok(value)
+}
+
+/// Parse the entries of the lines of some data, none being skipped.
+///
+/// Use [Lines] to skip some lines or to get the offsets of the lines.
+///
+/// # Arguments
+///
+/// * `data` - The data to parse.
+///
+/// # Returns
+///
+/// The 1-based line numbers along with the entry of the line.
+pub fn parse_lines<'a, V: Visitor<'a, u8>>(data: &'a [u8]) -> Vec<(usize, ParseResult<V>)> {
+    Lines::new(data)
+        .map(|parsed| (parsed.line, parsed.result))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::lines::{Lines, parse_lines};
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+
+    const FIXTURE: &[u8] = b"1\r\n# comment\nx\n\n22 3\n333";
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = parse_lines::<Number<usize>>(FIXTURE);
+        let lines = lines
+            .iter()
+            .map(|(line, result)| (*line, result.as_ref().map(|number| number.0).ok()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                (1, Some(1)),
+                (2, None),
+                (3, None),
+                (4, None),
+                (5, None),
+                (6, Some(333))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines_skip() {
+        let lines = Lines::<Number<usize>>::new(FIXTURE)
+            .skip_if(|line| line.is_empty() || line.starts_with(b"#"))
+            .collect::<Vec<_>>();
+        let tags = lines
+            .iter()
+            .map(|parsed| (parsed.line, parsed.offset))
+            .collect::<Vec<_>>();
+        assert_eq!(tags, [(1, 0), (3, 13), (5, 16), (6, 21)]);
+
+        assert!(matches!(lines[0].result, Ok(Number(1))));
+        assert!(matches!(lines[1].result, Err(ParseError::UnexpectedToken)));
+        let err = lines[2].result.as_ref().expect_err("should fail");
+        assert_eq!(err.offset(), Some(2));
+        assert_eq!(
+            format!("line {}: {err}", lines[2].line),
+            "line 5: unexpected data after the entry at offset 2"
+        );
+        assert!(matches!(lines[3].result, Ok(Number(333))));
+
+        assert_eq!(Lines::<Number<usize>>::new(b"").count(), 0);
+        assert_eq!(Lines::<Number<usize>>::new(b"1\n").count(), 1);
+    }
+}
@@ -1,47 +1,735 @@
 //! Byte slice matchers.
+
+use crate::matcher::{MatchResult, Needed};
+use crate::symbol;
+
 /// Attempt to match a single character against a byte slice.
 ///
 /// # Arguments
 ///
-/// * `pattern` - The character to match against.
+/// * `pattern` - The character to match against.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_char(pattern: char, data: &[u8]) -> (bool, usize) {
+    symbol::match_symbol(pattern as u8, data)
+}
+
+/// Attempt to match a byte of a set of ranges against a byte slice, a class
+/// of characters such as `[a-z0-9]` for example.
+///
+/// # Arguments
+///
+/// * `ranges` - The inclusive ranges of the bytes to match.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_ranges(ranges: &[(u8, u8)], data: &[u8]) -> (bool, usize) {
+    match data.first() {
+        Some(byte)
+            if ranges
+                .iter()
+                .any(|(low, high)| (low..=high).contains(&byte)) =>
+        {
+            (true, 1)
+        }
+        _ => (false, 0),
+    }
+}
+
+/// Attempt to match a byte slice against a byte slice.
+///
+/// # Arguments
+///
+/// * `pattern` - The byte slice to match against.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_pattern(pattern: &[u8], data: &[u8]) -> (bool, usize) {
+    symbol::match_sequence(pattern.iter().copied(), data)
+}
+
+/// Attempt to match a byte slice against data which may be followed by more
+/// data, ignoring case like [match_pattern].
+///
+/// # Arguments
+///
+/// * `pattern` - The byte slice to match against.
+/// * `data` - The data available so far.
+///
+/// # Returns
+///
+/// [MatchResult::Incomplete] if the data is a strict prefix of the pattern,
+/// the result of [match_pattern] otherwise.
+pub fn match_pattern_streaming(pattern: &[u8], data: &[u8]) -> MatchResult {
+    if data.len() < pattern.len() && pattern[..data.len()].eq_ignore_ascii_case(data) {
+        return MatchResult::Incomplete(Needed::Size(pattern.len() - data.len()));
+    }
+    match_pattern(pattern, data).into()
+}
+
+/// Attempt to match a keyword against a byte slice, ignoring case.
+///
+/// Unlike [match_pattern], the keyword must not be followed by an identifier
+/// character, so that `select` doesn't match `selection`.
+///
+/// # Arguments
+///
+/// * `keyword` - The keyword to match against.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_keyword(keyword: &[u8], data: &[u8]) -> (bool, usize) {
+    let (found, size) = match_pattern(keyword, data);
+    if !found {
+        return (false, 0);
+    }
+    match data.get(size) {
+        Some(byte) if byte.is_ascii_alphanumeric() || *byte == b'_' => (false, 0),
+        _ => (true, size),
+    }
+}
+
+/// Attempt to match a keyword against data which may be followed by more
+/// data, ignoring case like [match_keyword].
+///
+/// # Arguments
+///
+/// * `keyword` - The keyword to match against.
+/// * `data` - The data available so far.
+///
+/// # Returns
+///
+/// [MatchResult::Incomplete] if the data is a prefix of the keyword, the
+/// keyword itself included as an identifier character may follow it, the
+/// result of [match_keyword] otherwise.
+pub fn match_keyword_streaming(keyword: &[u8], data: &[u8]) -> MatchResult {
+    if data.len() <= keyword.len() && keyword[..data.len()].eq_ignore_ascii_case(data) {
+        return MatchResult::Incomplete(Needed::Size(keyword.len() + 1 - data.len()));
+    }
+    match_keyword(keyword, data).into()
+}
+
+/// Attempt to match a literal, case included, against data which may be
+/// followed by more data.
+///
+/// # Arguments
+///
+/// * `literal` - The bytes to match exactly.
+/// * `data` - The data available so far.
+///
+/// # Returns
+///
+/// [MatchResult::Incomplete] if the data is a strict prefix of the literal,
+/// whether the data starts with the literal otherwise.
+pub fn match_literal_streaming(literal: &[u8], data: &[u8]) -> MatchResult {
+    if data.len() < literal.len() && literal.starts_with(data) {
+        return MatchResult::Incomplete(Needed::Size(literal.len() - data.len()));
+    }
+    match data.starts_with(literal) {
+        true => MatchResult::Matched(literal.len()),
+        false => MatchResult::NoMatch,
+    }
+}
+
+/// The boolean spellings and their value.
+const BOOLEAN_WORDS: [(&[u8], bool); 8] = [
+    (b"true", true),
+    (b"false", false),
+    (b"yes", true),
+    (b"no", false),
+    (b"on", true),
+    (b"off", false),
+    (b"1", true),
+    (b"0", false),
+];
+
+/// Attempt to match a boolean word against a byte slice, ignoring case.
+///
+/// The accepted spellings are `true/false`, `yes/no`, `on/off` and `1/0`,
+/// they must not be followed by an identifier character, so that `truthy`
+/// doesn't match `true`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded,
+/// the number of bytes consumed and the value of the word if the match
+/// succeeded. The value is meaningless when the match fails.
+pub fn match_boolean_words(data: &[u8]) -> (bool, usize, bool) {
+    for (word, value) in BOOLEAN_WORDS {
+        let (found, size) = match_keyword(word, data);
+        if found {
+            return (true, size, value);
+        }
+    }
+    (false, 0, false)
+}
+
+/// Attempt to match a number against a byte slice.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_number(data: &[u8]) -> (bool, usize) {
+    symbol::match_digits(data)
+}
+
+/// Attempt to match a number without leading zeros against a byte slice, as
+/// JSON requires.
+///
+/// The number is either a single `0` or a non-zero digit followed by digits,
+/// so `007` only matches its first `0`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_number_strict(data: &[u8]) -> (bool, usize) {
+    match data.first() {
+        Some(b'0') => (true, 1),
+        _ => match_number(data),
+    }
+}
+
+/// Attempt to match a JSON number against a byte slice, `-0.5e+10` for
+/// example.
+///
+/// The number has an optional `-`, an integer part without leading zeros as
+/// [match_number_strict] matches it, then an optional fraction and an
+/// optional exponent. A `.` or an exponent not followed by digits is left
+/// out, so `1.` only matches `1`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_json_number(data: &[u8]) -> (bool, usize) {
+    let sign = usize::from(data.first() == Some(&b'-'));
+    let mut pos = match match_number_strict(&data[sign..]) {
+        (true, size) => sign + size,
+        _ => return (false, 0),
+    };
+
+    if data.get(pos) == Some(&b'.') {
+        match count_digits(&data[pos + 1..]) {
+            0 => return (true, pos),
+            digits => pos += 1 + digits,
+        }
+    }
+
+    if matches!(data.get(pos), Some(b'e' | b'E')) {
+        let exponent = match data.get(pos + 1) {
+            Some(b'-' | b'+') => pos + 2,
+            _ => pos + 1,
+        };
+        match count_digits(data.get(exponent..).unwrap_or_default()) {
+            0 => {}
+            digits => pos = exponent + digits,
+        }
+    }
+
+    (true, pos)
+}
+
+/// Attempt to match a number against data which may be followed by more data.
+///
+/// # Arguments
+///
+/// * `data` - The data available so far.
+///
+/// # Returns
+///
+/// [MatchResult::Incomplete] if the digits run up to the end of the data, as
+/// more digits may follow, the result of [match_number] otherwise.
+pub fn match_number_streaming(data: &[u8]) -> MatchResult {
+    match match_number(data) {
+        (_, size) if size == data.len() => MatchResult::Incomplete(Needed::Unknown),
+        result => result.into(),
+    }
+}
+
+/// Attempt to match a run of ASCII whitespaces against a byte slice.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_whitespace(data: &[u8]) -> (bool, usize) {
+    symbol::match_whitespaces(data)
+}
+
+/// Decode the UTF-8 char at the start of a byte slice.
+///
+/// # Returns
+///
+/// The decoded char and its length in bytes, `None` if the slice doesn't
+/// start with a valid UTF-8 sequence.
+pub(crate) fn decode_utf8(data: &[u8]) -> Option<(char, usize)> {
+    let width = match data.first()? {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return None,
+    };
+    let decoded = std::str::from_utf8(data.get(..width)?).ok()?;
+    decoded.chars().next().map(|char| (char, width))
+}
+
+/// Attempt to match a run of Unicode whitespaces against a byte slice
+/// holding UTF-8 data.
+///
+/// Every `White_Space` code point is accepted, NBSP (U+00A0) or the
+/// ideographic space (U+3000) for example. Invalid UTF-8 stops the run.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_unicode_whitespace(data: &[u8]) -> (bool, usize) {
+    let mut pos = 0;
+    while let Some((char, width)) = decode_utf8(&data[pos..]) {
+        if !char.is_whitespace() {
+            break;
+        }
+        pos += width;
+    }
+    (pos > 0, pos)
+}
+
+/// Attempt to match one extended grapheme cluster against a byte slice
+/// holding UTF-8 data.
+///
+/// A base char and its combining marks, an emoji ZWJ sequence or a CRLF are
+/// single clusters. See [crate::bytes::scanner] for a positioned error on
+/// invalid UTF-8.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded. Invalid UTF-8 doesn't
+/// match.
+#[cfg(feature = "grapheme")]
+pub fn match_grapheme(data: &[u8]) -> (bool, usize) {
+    match crate::bytes::grapheme::next_boundary(data) {
+        Ok(size) => (size > 0, size),
+        Err(_) => (false, 0),
+    }
+}
+
+/// Attempt to match a string enclosed in double quotes against a byte slice.
+///
+/// A `\` escapes the following byte, so `"a\"b"` is a single string.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded, quotes included.
+pub fn match_quoted_string(data: &[u8]) -> (bool, usize) {
+    symbol::match_quoted_string(data)
+}
+
+/// Attempt to match a string enclosed in a quote byte against a byte slice.
+///
+/// The `escape` byte escapes the following byte, so that with `'` and `\`
+/// `'a\'b'` is a single string, and with `` ` `` and `%` `` `a%`b` `` is.
+/// The quote and the escape bytes are expected to differ.
+///
+/// # Arguments
+///
+/// * `quote` - The byte opening and closing the string.
+/// * `escape` - The byte escaping the next one.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded, quotes included.
+pub fn match_quoted(quote: u8, escape: u8, data: &[u8]) -> (bool, usize) {
+    symbol::match_quoted(quote, escape, data)
+}
+
+/// Attempt to match a string enclosed in double quotes against data which
+/// may be followed by more data.
+///
+/// # Arguments
+///
+/// * `data` - The data available so far.
+///
+/// # Returns
+///
+/// [MatchResult::Incomplete] if the data is empty or the string is not
+/// terminated yet, the result of [match_quoted_string] otherwise.
+pub fn match_quoted_string_streaming(data: &[u8]) -> MatchResult {
+    match match_quoted_string(data) {
+        (false, _) if data.is_empty() || data[0] == b'"' => {
+            MatchResult::Incomplete(Needed::Unknown)
+        }
+        result => result.into(),
+    }
+}
+
+/// Attempt to match a string against a byte slice.
+/// Stop matching when a punctuation character is encountered.
+///  * U+0021 ..= U+002F ! " # $ % & ' ( ) * + , - . /, or
+///  * U+003A ..= U+0040 : ; < = > ? @, or
+///  * U+005B ..= U+0060 [ \ ] ^ _ `, or
+///  * U+007B ..= U+007E { | } ~
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_string(data: &[u8]) -> (bool, usize) {
+    if data.is_empty() {
+        return (false, 0);
+    }
+
+    let mut pos = 0;
+    let mut found = false;
+
+    loop {
+        if pos == data.len() {
+            break;
+        }
+        if !data[pos].is_ascii_punctuation() && !data[pos].is_ascii_whitespace() {
+            pos += 1;
+            found = true;
+            continue;
+        }
+        break;
+    }
+
+    (found, pos)
+}
+
+/// Count the leading ASCII digits of a byte slice.
+fn count_digits(data: &[u8]) -> usize {
+    data.iter().take_while(|byte| byte.is_ascii_digit()).count()
+}
+
+/// Attempt to match a decimal number written with locale specific
+/// separators against a byte slice, `1,234.56` or `1.234,56` for example.
+///
+/// The integer part is either a plain run of digits or groups of three
+/// digits separated by `group`, the first group having one to three digits.
+/// The optional fractional part follows the `decimal` separator.
+///
+/// # Arguments
+///
+/// * `group` - The grouping separator.
+/// * `decimal` - The decimal separator.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_decimal_locale(group: u8, decimal: u8, data: &[u8]) -> (bool, usize) {
+    let mut pos = count_digits(data);
+    if pos == 0 {
+        return (false, 0);
+    }
+
+    if pos <= 3 {
+        // each group must be exactly three digits long
+        while data.get(pos) == Some(&group) && count_digits(&data[pos + 1..]) == 3 {
+            pos += 4;
+        }
+    }
+
+    if data.get(pos) == Some(&decimal) {
+        let fraction = count_digits(&data[pos + 1..]);
+        if fraction > 0 {
+            pos += fraction + 1;
+        }
+    }
+
+    (true, pos)
+}
+
+/// Attempt to match an identifier against a byte slice.
+///
+/// An identifier starts with an ASCII letter or `_`, followed by ASCII
+/// letters, digits or `_`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_identifier(data: &[u8]) -> (bool, usize) {
+    symbol::match_identifier(data)
+}
+
+/// Attempt to match a Unicode identifier against a byte slice holding UTF-8
+/// data.
+///
+/// An identifier starts with `_` or an XID_Start code point, followed by
+/// XID_Continue code points, `café` or `変数` for example. Invalid UTF-8
+/// ends the identifier.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+#[cfg(feature = "unicode")]
+pub fn match_unicode_identifier(data: &[u8]) -> (bool, usize) {
+    match decode_utf8(data) {
+        Some((char, _)) if symbol::is_unicode_identifier_start(char) => {}
+        _ => return (false, 0),
+    }
+
+    let mut pos = 0;
+    while pos < data.len() {
+        // ASCII fast path, avoiding the UTF-8 decoding
+        if data[pos].is_ascii() {
+            if !data[pos].is_ascii_alphanumeric() && data[pos] != b'_' {
+                break;
+            }
+            pos += 1;
+            continue;
+        }
+        match decode_utf8(&data[pos..]) {
+            Some((char, width)) if symbol::is_unicode_identifier_continue(char) => pos += width,
+            _ => break,
+        }
+    }
+
+    (true, pos)
+}
+
+/// Attempt to match an HTML tag name against a byte slice.
+///
+/// A tag name starts with an ASCII letter, followed by ASCII letters, digits
+/// or `-`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_tag_name(data: &[u8]) -> (bool, usize) {
+    match data.first() {
+        Some(byte) if byte.is_ascii_alphabetic() => {}
+        _ => return (false, 0),
+    }
+
+    let pos = data
+        .iter()
+        .position(|byte| !byte.is_ascii_alphanumeric() && *byte != b'-')
+        .unwrap_or(data.len());
+
+    (true, pos)
+}
+
+/// Attempt to match an XML name against a byte slice, qualified names such
+/// as `ns:element` included.
+///
+/// A name starts with an ASCII letter, `_` or `:`, followed by ASCII letters,
+/// digits, `_`, `:`, `-` or `.`.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_xml_name(data: &[u8]) -> (bool, usize) {
+    let is_start = |byte: &u8| byte.is_ascii_alphabetic() || matches!(byte, b'_' | b':');
+    match data.first() {
+        Some(byte) if is_start(byte) => {}
+        _ => return (false, 0),
+    }
+
+    let pos = data
+        .iter()
+        .position(|byte| !is_start(byte) && !byte.is_ascii_digit() && !matches!(byte, b'-' | b'.'))
+        .unwrap_or(data.len());
+
+    (true, pos)
+}
+
+/// Attempt to match an INI section header against a byte slice, `[server]`
+/// for example.
+///
+/// The name runs up to the first `]`, on the same line. Its whitespaces are
+/// matched as part of it.
+///
+/// # Arguments
+///
 /// * `data` - The byte slice to match against.
 ///
 /// # Returns
 ///
 /// A tuple containing a boolean indicating whether the match succeeded and
-/// the number of bytes consumed if the match succeeded.
-pub fn match_char(pattern: char, data: &[u8]) -> (bool, usize) {
-    (pattern as u8 == data[0], 1)
+/// the number of bytes consumed if the match succeeded, brackets included.
+pub fn match_ini_section(data: &[u8]) -> (bool, usize) {
+    if data.first() != Some(&b'[') {
+        return (false, 0);
+    }
+    match data.iter().position(|byte| matches!(byte, b']' | b'\n')) {
+        Some(end) if data[end] == b']' => (true, end + 1),
+        _ => (false, 0),
+    }
 }
 
-/// Attempt to match a byte slice against a byte slice.
+/// Attempt to match the key of an INI `key = value` pair against a byte
+/// slice.
+///
+/// The key runs up to the `=`, on the same line, its trailing whitespaces
+/// included. A line starting with `[`, `;` or `#`, a section header or a
+/// comment, isn't a key.
 ///
 /// # Arguments
 ///
-/// * `pattern` - The byte slice to match against.
 /// * `data` - The byte slice to match against.
 ///
 /// # Returns
 ///
 /// A tuple containing a boolean indicating whether the match succeeded and
-/// the number of bytes consumed if the match succeeded.
-pub fn match_pattern(pattern: &[u8], data: &[u8]) -> (bool, usize) {
-    if pattern.is_empty() {
+/// the number of bytes consumed if the match succeeded, the `=` excluded.
+pub fn match_ini_key(data: &[u8]) -> (bool, usize) {
+    if matches!(data.first(), None | Some(b'[' | b';' | b'#' | b'=')) {
         return (false, 0);
     }
+    match data.iter().position(|byte| matches!(byte, b'=' | b'\n')) {
+        Some(end) if data[end] == b'=' => (true, end),
+        _ => (false, 0),
+    }
+}
 
-    if pattern.len() > data.len() {
-        return (false, 0);
+/// Attempt to match the prefix of a Markdown heading against a byte slice,
+/// `### ` for example.
+///
+/// The prefix is made of one to six `#` followed by a space or a tab.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded,
+/// the number of bytes consumed, the space included, and the level of the
+/// heading, the number of `#`, if the match succeeded. The level is `0` when
+/// the match fails.
+pub fn match_heading(data: &[u8]) -> (bool, usize, u8) {
+    let level = data.iter().take_while(|byte| **byte == b'#').count();
+    match data.get(level) {
+        Some(b' ' | b'\t') if (1..=6).contains(&level) => (true, level + 1, level as u8),
+        _ => (false, 0, 0),
     }
+}
 
-    if pattern.eq_ignore_ascii_case(&data[..pattern.len()]) {
-        return (true, pattern.len());
+/// Attempt to match a shell glob token against a byte slice.
+///
+/// The token is made of literal bytes, `*`, `?` and `[...]` classes, and ends
+/// at a whitespace or a `/`. A `]` right after `[` or `[!` belongs to the
+/// class, so `[]a]` matches `]` or `a`. An unterminated class doesn't match.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_glob_token(data: &[u8]) -> (bool, usize) {
+    let mut pos = 0;
+    while let Some(byte) = data.get(pos) {
+        match byte {
+            b'/' => break,
+            byte if byte.is_ascii_whitespace() => break,
+            b'[' => {
+                let mut end = pos + 1;
+                if data.get(end) == Some(&b'!') {
+                    end += 1;
+                }
+                // the first byte of the class may be a `]`
+                end += 1;
+                match data
+                    .get(end..)
+                    .and_then(|rest| rest.iter().position(|byte| *byte == b']'))
+                {
+                    Some(close) => pos = end + close + 1,
+                    None => return (false, 0),
+                }
+            }
+            _ => pos += 1,
+        }
     }
 
-    (false, 0)
+    (pos > 0, pos)
 }
 
-/// Attempt to match a number against a byte slice.
+/// Attempt to match a MAC address against a byte slice.
+///
+/// The address is made of six pairs of hexadecimal digits separated by `:`
+/// or `-`, the same separator being used throughout, `AA:BB:CC:DD:EE:FF`
+/// for example.
 ///
 /// # Arguments
 ///
@@ -50,36 +738,101 @@ pub fn match_pattern(pattern: &[u8], data: &[u8]) -> (bool, usize) {
 /// # Returns
 ///
 /// A tuple containing a boolean indicating whether the match succeeded and
-/// the number of bytes consumed if the match succeeded.
-pub fn match_number(data: &[u8]) -> (bool, usize) {
-    if data.is_empty() {
+/// the number of bytes consumed if the match succeeded, always 17.
+pub fn match_mac(data: &[u8]) -> (bool, usize) {
+    let Some(address) = data.get(..17) else {
+        return (false, 0);
+    };
+    let separator = address[2];
+    if separator != b':' && separator != b'-' {
         return (false, 0);
     }
 
-    let mut pos = 0;
-    let mut found = false;
-
-    loop {
-        if pos == data.len() {
-            break;
+    let valid = address.chunks(3).all(|group| match group {
+        [high, low, rest @ ..] => {
+            high.is_ascii_hexdigit()
+                && low.is_ascii_hexdigit()
+                && rest.iter().all(|byte| *byte == separator)
         }
-        if data[pos].is_ascii_digit() {
-            pos += 1;
-            found = true;
-            continue;
+        _ => false,
+    });
+    // a seventh group would make a longer address
+    let followed =
+        matches!(data.get(17), Some(byte) if *byte == separator || byte.is_ascii_hexdigit());
+    match valid && !followed {
+        true => (true, 17),
+        false => (false, 0),
+    }
+}
+
+/// Attempt to match an SQL identifier against a byte slice, either bare,
+/// `users`, or delimited by double quotes or backticks, `"my col"`.
+///
+/// A bare identifier is matched as [match_identifier] does. In a delimited
+/// one a doubled delimiter stands for the delimiter itself, `"a""b"` for
+/// example. A delimited identifier must not be empty.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded, delimiters included.
+pub fn match_sql_identifier(data: &[u8]) -> (bool, usize) {
+    let delimiter = match data.first() {
+        Some(byte @ (b'"' | b'`')) => *byte,
+        _ => return match_identifier(data),
+    };
+
+    let mut pos = 1;
+    while pos < data.len() {
+        match data.get(pos + 1) {
+            _ if data[pos] != delimiter => pos += 1,
+            Some(next) if *next == delimiter => pos += 2,
+            _ if pos == 1 => return (false, 0),
+            _ => return (true, pos + 1),
         }
-        break;
     }
 
-    (found, pos)
+    // unterminated identifier
+    (false, 0)
 }
 
-/// Attempt to match a string against a byte slice.
-/// Stop matching when a punctuation character is encountered.
-///  * U+0021 ..= U+002F ! " # $ % & ' ( ) * + , - . /, or
-///  * U+003A ..= U+0040 : ; < = > ? @, or
-///  * U+005B ..= U+0060 [ \ ] ^ _ `, or
-///  * U+007B ..= U+007E { | } ~
+/// Attempt to match a reference to an environment variable against a byte
+/// slice, either `$NAME` or `${NAME}`.
+///
+/// The name is an identifier as [match_identifier] matches it, an
+/// unterminated `${NAME` doesn't match.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded, the `$` and braces
+/// included.
+pub fn match_env_var_ref(data: &[u8]) -> (bool, usize) {
+    match data {
+        [b'$', b'{', rest @ ..] => match match_identifier(rest) {
+            (true, size) if rest.get(size) == Some(&b'}') => (true, size + 3),
+            _ => (false, 0),
+        },
+        [b'$', rest @ ..] => match match_identifier(rest) {
+            (true, size) => (true, size + 1),
+            _ => (false, 0),
+        },
+        _ => (false, 0),
+    }
+}
+
+/// Attempt to match a fraction against a byte slice, `3/4` for example.
+///
+/// The numerator and the denominator are runs of digits around a `/`,
+/// without spaces.
 ///
 /// # Arguments
 ///
@@ -89,32 +842,250 @@ pub fn match_number(data: &[u8]) -> (bool, usize) {
 ///
 /// A tuple containing a boolean indicating whether the match succeeded and
 /// the number of bytes consumed if the match succeeded.
-pub fn match_string(data: &[u8]) -> (bool, usize) {
-    if data.is_empty() {
+pub fn match_fraction(data: &[u8]) -> (bool, usize) {
+    let numerator = count_digits(data);
+    if numerator == 0 || data.get(numerator) != Some(&b'/') {
         return (false, 0);
     }
+    match count_digits(&data[numerator + 1..]) {
+        0 => (false, 0),
+        denominator => (true, numerator + 1 + denominator),
+    }
+}
 
-    let mut pos = 0;
-    let mut found = false;
+/// Read a number written with exactly `len` digits at the start of the data.
+fn fixed_number(data: &[u8], len: usize) -> Option<u32> {
+    let digits = data.get(..len)?;
+    digits.iter().try_fold(0, |number, byte| {
+        byte.is_ascii_digit()
+            .then(|| number * 10 + u32::from(byte - b'0'))
+    })
+}
 
-    loop {
-        if pos == data.len() {
-            break;
+/// Match a TOML local date, `YYYY-MM-DD`, the day existing in its month.
+fn toml_date(data: &[u8]) -> Option<usize> {
+    let year = fixed_number(data, 4)?;
+    let month = fixed_number(data.get(5..)?, 2)?;
+    let day = fixed_number(data.get(8..)?, 2)?;
+    if data[4] != b'-' || data[7] != b'-' {
+        return None;
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    (1..=days).contains(&day).then_some(10)
+}
+
+/// Match a TOML local time, `HH:MM:SS` followed by optional fractional
+/// seconds, a leap second being allowed.
+fn toml_time(data: &[u8]) -> Option<usize> {
+    let hour = fixed_number(data, 2)?;
+    let minute = fixed_number(data.get(3..)?, 2)?;
+    let second = fixed_number(data.get(6..)?, 2)?;
+    if data[2] != b':' || data[5] != b':' || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    match data.get(8) {
+        Some(b'.') => match count_digits(&data[9..]) {
+            0 => Some(8),
+            digits => Some(9 + digits),
+        },
+        _ => Some(8),
+    }
+}
+
+/// Match a TOML time offset, `Z` or `+HH:MM`.
+fn toml_offset(data: &[u8]) -> Option<usize> {
+    match data.first()? {
+        b'Z' | b'z' => Some(1),
+        b'+' | b'-' => {
+            let hour = fixed_number(data.get(1..)?, 2)?;
+            let minute = fixed_number(data.get(4..)?, 2)?;
+            (data[3] == b':' && hour <= 23 && minute <= 59).then_some(6)
         }
-        if !data[pos].is_ascii_punctuation() && !data[pos].is_ascii_whitespace() {
+        _ => None,
+    }
+}
+
+/// Attempt to match a TOML date or time against a byte slice.
+///
+/// The forms of the TOML specification are matched, the fields being
+/// checked against their range, February 29 only existing in leap years:
+///
+/// * an offset date-time, `1979-05-27T07:32:00Z` or
+///   `1979-05-27T00:32:00.999-07:00`,
+/// * a local date-time, `1979-05-27T07:32:00`, the `T` being possibly
+///   replaced by a space,
+/// * a local date, `1979-05-27`,
+/// * a local time, `07:32:00.999`.
+///
+/// A date followed by an invalid time or offset only matches its valid
+/// part.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_toml_datetime(data: &[u8]) -> (bool, usize) {
+    let Some(date) = toml_date(data) else {
+        return match toml_time(data) {
+            Some(time) => (true, time),
+            None => (false, 0),
+        };
+    };
+
+    if !matches!(data.get(date), Some(b'T' | b't' | b' ')) {
+        return (true, date);
+    }
+    match toml_time(&data[date + 1..]) {
+        Some(time) => {
+            let end = date + 1 + time;
+            (true, end + toml_offset(&data[end..]).unwrap_or(0))
+        }
+        None => (true, date),
+    }
+}
+
+/// Attempt to match a CSV field against a byte slice.
+///
+/// The field is either enclosed in double quotes, a doubled quote `""`
+/// standing for an escaped quote, or an unquoted run of bytes up to the next
+/// `,` or line break. An empty field doesn't match.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded, quotes included.
+pub fn match_csv_field(data: &[u8]) -> (bool, usize) {
+    if data.first() == Some(&b'"') {
+        let mut pos = 1;
+        while pos < data.len() {
+            if data[pos] == b'"' {
+                // escaped quote
+                if data.get(pos + 1) == Some(&b'"') {
+                    pos += 2;
+                    continue;
+                }
+                return (true, pos + 1);
+            }
             pos += 1;
-            found = true;
-            continue;
         }
-        break;
+        // unterminated quoted field
+        return (false, 0);
     }
 
-    (found, pos)
+    let pos = data
+        .iter()
+        .position(|byte| matches!(byte, b',' | b'\n' | b'\r'))
+        .unwrap_or(data.len());
+
+    (pos > 0, pos)
+}
+
+/// Arithmetic and comparison operators recognized by [match_operator].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OperatorKind {
+    /// The `==` operator
+    EqEq,
+    /// The `!=` operator
+    NotEq,
+    /// The `<=` operator
+    LessEq,
+    /// The `>=` operator
+    GreaterEq,
+    /// The `**` operator
+    Power,
+    /// The `=` operator
+    Equal,
+    /// The `<` operator
+    Less,
+    /// The `>` operator
+    Greater,
+    /// The `+` operator
+    Plus,
+    /// The `-` operator
+    Minus,
+    /// The `*` operator
+    Star,
+    /// The `/` operator
+    Slash,
+    /// The `%` operator
+    Percent,
+}
+
+/// Operators sorted longest first, so that the longest match wins.
+const OPERATORS: [(&[u8], OperatorKind); 13] = [
+    (b"==", OperatorKind::EqEq),
+    (b"!=", OperatorKind::NotEq),
+    (b"<=", OperatorKind::LessEq),
+    (b">=", OperatorKind::GreaterEq),
+    (b"**", OperatorKind::Power),
+    (b"=", OperatorKind::Equal),
+    (b"<", OperatorKind::Less),
+    (b">", OperatorKind::Greater),
+    (b"+", OperatorKind::Plus),
+    (b"-", OperatorKind::Minus),
+    (b"*", OperatorKind::Star),
+    (b"/", OperatorKind::Slash),
+    (b"%", OperatorKind::Percent),
+];
+
+/// Attempt to match the longest operator against a byte slice.
+///
+/// # Arguments
+///
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded,
+/// the number of bytes consumed and the kind of the operator if the match
+/// succeeded. The kind is meaningless when the match fails.
+pub fn match_operator(data: &[u8]) -> (bool, usize, OperatorKind) {
+    for (pattern, kind) in OPERATORS {
+        if data.starts_with(pattern) {
+            return (true, pattern.len(), kind);
+        }
+    }
+    (false, 0, OperatorKind::Equal)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::bytes::matchers::{match_char, match_number, match_pattern, match_string};
+    use crate::bytes::matchers::{
+        OperatorKind, match_boolean_words, match_char, match_csv_field, match_decimal_locale,
+        match_env_var_ref, match_fraction, match_glob_token, match_heading, match_identifier,
+        match_ini_key, match_ini_section, match_json_number, match_keyword,
+        match_keyword_streaming, match_literal_streaming, match_mac, match_number,
+        match_number_streaming, match_number_strict, match_operator, match_pattern,
+        match_pattern_streaming, match_quoted, match_quoted_string, match_quoted_string_streaming,
+        match_ranges, match_sql_identifier, match_string, match_tag_name, match_toml_datetime,
+        match_unicode_whitespace, match_whitespace, match_xml_name,
+    };
+    use crate::matcher::{MatchResult, Needed};
+
+    #[test]
+    fn test_match_ranges() {
+        let class = [(b'a', b'z'), (b'0', b'9')];
+        assert_eq!(match_ranges(&class, b"5"), (true, 1));
+        assert_eq!(match_ranges(&class, b"z9"), (true, 1));
+        assert_eq!(match_ranges(&class, b"#"), (false, 0));
+        assert_eq!(match_ranges(&class, b""), (false, 0));
+        assert_eq!(match_ranges(&[], b"a"), (false, 0));
+    }
 
     #[test]
     fn test_match_char() {
@@ -138,6 +1109,87 @@ mod tests {
         assert_eq!(consumed, 0);
     }
 
+    #[test]
+    fn test_streaming_matchers() {
+        let incomplete = |size| MatchResult::Incomplete(Needed::Size(size));
+        assert_eq!(match_pattern_streaming(b"select", b"SEL"), incomplete(3));
+        assert_eq!(match_pattern_streaming(b"select", b""), incomplete(6));
+        assert_eq!(
+            match_pattern_streaming(b"select", b"selx"),
+            MatchResult::NoMatch
+        );
+        assert_eq!(
+            match_pattern_streaming(b"select", b"select *"),
+            MatchResult::Matched(6)
+        );
+
+        let unknown = MatchResult::Incomplete(Needed::Unknown);
+        assert_eq!(match_number_streaming(b"123"), unknown);
+        assert_eq!(match_number_streaming(b""), unknown);
+        assert_eq!(match_number_streaming(b"123,"), MatchResult::Matched(3));
+        assert_eq!(match_number_streaming(b"a1"), MatchResult::NoMatch);
+
+        assert_eq!(match_quoted_string_streaming(br#""ab"#), unknown);
+        assert_eq!(match_quoted_string_streaming(br#""a\"#), unknown);
+        assert_eq!(match_quoted_string_streaming(b""), unknown);
+        assert_eq!(
+            match_quoted_string_streaming(br#""ab" "#),
+            MatchResult::Matched(4)
+        );
+        assert_eq!(match_quoted_string_streaming(b"ab"), MatchResult::NoMatch);
+
+        assert_eq!(match_keyword_streaming(b"select", b"SEL"), incomplete(4));
+        // `selection` may follow
+        assert_eq!(match_keyword_streaming(b"select", b"select"), incomplete(1));
+        assert_eq!(
+            match_keyword_streaming(b"select", b"select *"),
+            MatchResult::Matched(6)
+        );
+        assert_eq!(
+            match_keyword_streaming(b"select", b"selection"),
+            MatchResult::NoMatch
+        );
+
+        assert_eq!(match_literal_streaming(b"->", b"-"), incomplete(1));
+        assert_eq!(
+            match_literal_streaming(b"->", b"->x"),
+            MatchResult::Matched(2)
+        );
+        assert_eq!(match_literal_streaming(b"ab", b"AB"), MatchResult::NoMatch);
+    }
+
+    #[test]
+    fn test_match_keyword() {
+        assert_eq!(match_keyword(b"select", b"SELECT *"), (true, 6));
+        assert_eq!(match_keyword(b"select", b"select"), (true, 6));
+        assert_eq!(match_keyword(b"select", b"select(1)"), (true, 6));
+        assert_eq!(match_keyword(b"select", b"selection"), (false, 0));
+        assert_eq!(match_keyword(b"select", b"select_1"), (false, 0));
+        assert_eq!(match_keyword(b"select", b"sel"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_boolean_words() {
+        for (data, value) in [
+            (&b"true"[..], true),
+            (b"TRUE", true),
+            (b"yes", true),
+            (b"On", true),
+            (b"1", true),
+            (b"false", false),
+            (b"No", false),
+            (b"off", false),
+            (b"0", false),
+        ] {
+            assert_eq!(match_boolean_words(data), (true, data.len(), value));
+        }
+        assert_eq!(match_boolean_words(b"yes # comment"), (true, 3, true));
+        assert!(!match_boolean_words(b"truthy").0);
+        assert!(!match_boolean_words(b"10").0);
+        assert!(!match_boolean_words(b"online").0);
+        assert!(!match_boolean_words(b"").0);
+    }
+
     #[test]
     fn test_match_number() {
         let (result, consumed) = match_number(b"123abc");
@@ -149,10 +1201,317 @@ mod tests {
         assert_eq!(consumed, 0);
     }
 
+    #[test]
+    fn test_match_number_strict() {
+        assert_eq!(match_number_strict(b"0"), (true, 1));
+        assert_eq!(match_number_strict(b"10"), (true, 2));
+        assert_eq!(match_number_strict(b"007"), (true, 1));
+        assert_eq!(match_number_strict(b"0.5"), (true, 1));
+        assert_eq!(match_number_strict(b"x"), (false, 0));
+        assert_eq!(match_number_strict(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_whitespace() {
+        assert_eq!(match_whitespace(b" \t\r\na"), (true, 4));
+        assert_eq!(match_whitespace(b"a "), (false, 0));
+    }
+
+    #[test]
+    fn test_match_unicode_whitespace() {
+        assert_eq!(match_unicode_whitespace("\u{a0}a".as_bytes()), (true, 2));
+        assert_eq!(
+            match_unicode_whitespace("\u{3000}\u{3000}a".as_bytes()),
+            (true, 6)
+        );
+        assert_eq!(
+            match_unicode_whitespace(" \u{a0}\t\u{2003}\na".as_bytes()),
+            (true, 8)
+        );
+        assert_eq!(match_unicode_whitespace(b" \xff "), (true, 1));
+        assert_eq!(match_unicode_whitespace(b" \xc2"), (true, 1));
+        assert_eq!(match_unicode_whitespace(b"a "), (false, 0));
+        assert_eq!(match_whitespace("\u{a0}".as_bytes()), (false, 0));
+    }
+
+    #[cfg(feature = "grapheme")]
+    #[test]
+    fn test_match_grapheme() {
+        use crate::bytes::matchers::match_grapheme;
+
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        assert_eq!(match_grapheme(format!("{family}!").as_bytes()), (true, 18));
+        assert_eq!(match_grapheme("e\u{301}x".as_bytes()), (true, 3));
+        assert_eq!(match_grapheme(b"\r\nx"), (true, 2));
+        assert_eq!(match_grapheme(b"\n\r"), (true, 1));
+        assert_eq!(
+            match_grapheme("\u{1f1eb}\u{1f1f7}\u{1f1ea}".as_bytes()),
+            (true, 8)
+        );
+        assert_eq!(match_grapheme("\u{1f44d}\u{1f3fd}".as_bytes()), (true, 8));
+        assert_eq!(
+            match_grapheme("\u{1100}\u{1161}\u{11a8}a".as_bytes()),
+            (true, 9)
+        );
+        assert_eq!(match_grapheme("a\u{200d}\u{1f467}".as_bytes()), (true, 4));
+        assert_eq!(match_grapheme(b"ab"), (true, 1));
+        assert_eq!(match_grapheme("\u{915}\u{93f}x".as_bytes()), (true, 6));
+        assert_eq!(match_grapheme("\u{5e9}\u{5c1}".as_bytes()), (true, 4));
+        assert_eq!(match_grapheme(b"a\xff"), (true, 1));
+        // a cluster longer than the decoded window
+        let accents = format!("e{}x", "\u{301}".repeat(100));
+        assert_eq!(match_grapheme(accents.as_bytes()), (true, 201));
+        assert_eq!(match_grapheme(b""), (false, 0));
+        assert_eq!(match_grapheme(b"\xff"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_quoted_string() {
+        assert_eq!(match_quoted_string(br#""abc" d"#), (true, 5));
+        assert_eq!(match_quoted_string(br#""a\"b""#), (true, 6));
+        assert_eq!(match_quoted_string(br#""abc"#), (false, 0));
+        assert_eq!(match_quoted_string(b"abc"), (false, 0));
+    }
+
     #[test]
     fn test_match_string() {
         let (result, consumed) = match_string(b"abc123(");
         assert!(result);
         assert_eq!(consumed, 6);
     }
+
+    #[test]
+    fn test_match_decimal_locale() {
+        assert_eq!(match_decimal_locale(b'.', b',', b"1.234,56"), (true, 8));
+        assert_eq!(
+            match_decimal_locale(b',', b'.', b"1,234,567.8 EUR"),
+            (true, 11)
+        );
+        assert_eq!(match_decimal_locale(b'.', b',', b"1234,5"), (true, 6));
+        assert_eq!(match_decimal_locale(b'.', b',', b"12.34,5"), (true, 2));
+        assert_eq!(match_decimal_locale(b'.', b',', b"1.2345"), (true, 1));
+        assert_eq!(match_decimal_locale(b'.', b',', b"12,"), (true, 2));
+        assert_eq!(match_decimal_locale(b'.', b',', b",5"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_identifier() {
+        assert_eq!(match_identifier(b"_foo_42.bar"), (true, 7));
+        assert_eq!(match_identifier(b"a"), (true, 1));
+        assert_eq!(match_identifier(b"42a"), (false, 0));
+        assert_eq!(match_identifier(b""), (false, 0));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_match_unicode_identifier() {
+        use crate::bytes::matchers::match_unicode_identifier;
+
+        assert_eq!(match_unicode_identifier("café.au".as_bytes()), (true, 5));
+        assert_eq!(match_unicode_identifier("変数+1".as_bytes()), (true, 6));
+        assert_eq!(match_unicode_identifier("αβ_γ1,".as_bytes()), (true, 8));
+        assert_eq!(match_unicode_identifier("_x\u{301}y".as_bytes()), (true, 5));
+        assert_eq!(match_unicode_identifier("\u{301}x".as_bytes()), (false, 0));
+        assert_eq!(match_unicode_identifier(b"ab\xffc"), (true, 2));
+        assert_eq!(match_unicode_identifier(b"1x"), (false, 0));
+        assert_eq!(match_unicode_identifier(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_glob_token() {
+        assert_eq!(match_glob_token(b"*.rs "), (true, 4));
+        assert_eq!(match_glob_token(b"src/*.rs"), (true, 3));
+        assert_eq!(match_glob_token(b"file?.[ch]\t"), (true, 10));
+        assert_eq!(match_glob_token(b"[!a-z]x y"), (true, 7));
+        assert_eq!(match_glob_token(b"[]a]b"), (true, 5));
+        assert_eq!(match_glob_token(b"[ ]"), (true, 3));
+        assert_eq!(match_glob_token(b"a[bc"), (false, 0));
+        assert_eq!(match_glob_token(b" *.rs"), (false, 0));
+        assert_eq!(match_glob_token(b"/"), (false, 0));
+        assert_eq!(match_glob_token(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_mac() {
+        assert_eq!(match_mac(b"AA:BB:CC:DD:EE:FF"), (true, 17));
+        assert_eq!(match_mac(b"0a-1b-2c-3d-4e-5f up"), (true, 17));
+        assert_eq!(match_mac(b"AA:BB:CC"), (false, 0));
+        assert_eq!(match_mac(b"AA:BB-CC:DD:EE:FF"), (false, 0));
+        assert_eq!(match_mac(b"AA:BB:CC:DD:EE:FG"), (false, 0));
+        assert_eq!(match_mac(b"AABB:CC:DD:EE:FF:0"), (false, 0));
+        assert_eq!(match_mac(b"AA:BB:CC:DD:EE:FF:00"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_sql_identifier() {
+        assert_eq!(match_sql_identifier(b"users"), (true, 5));
+        assert_eq!(match_sql_identifier(b"users.id"), (true, 5));
+        assert_eq!(match_sql_identifier(b"\"my col\""), (true, 8));
+        assert_eq!(match_sql_identifier(b"`tbl` AS t"), (true, 5));
+        assert_eq!(match_sql_identifier(b"\"a\"\"b\".x"), (true, 6));
+        assert_eq!(match_sql_identifier(b"`a\"b`"), (true, 5));
+        assert_eq!(match_sql_identifier(b"\"\"\"\""), (true, 4));
+        assert_eq!(match_sql_identifier(b"\"\""), (false, 0));
+        assert_eq!(match_sql_identifier(b"\"abc"), (false, 0));
+        assert_eq!(match_sql_identifier(b"\"ab\"\""), (false, 0));
+        assert_eq!(match_sql_identifier(b"1col"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_env_var_ref() {
+        assert_eq!(match_env_var_ref(b"$HOME/bin"), (true, 5));
+        assert_eq!(match_env_var_ref(b"${HOME}/bin"), (true, 7));
+        assert_eq!(match_env_var_ref(b"${HOME/bin"), (false, 0));
+        assert_eq!(match_env_var_ref(b"${}"), (false, 0));
+        assert_eq!(match_env_var_ref(b"$1"), (false, 0));
+        assert_eq!(match_env_var_ref(b"HOME"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_quoted() {
+        assert_eq!(match_quoted(b'\'', b'\\', b"'a\\'b' c"), (true, 6));
+        assert_eq!(match_quoted(b'\'', b'\\', b"'a\\\\' c"), (true, 5));
+        assert_eq!(match_quoted(b'\'', b'\\', b"''"), (true, 2));
+        assert_eq!(match_quoted(b'\'', b'\\', b"'a\\'"), (false, 0));
+        assert_eq!(match_quoted(b'\'', b'\\', b"\"a\""), (false, 0));
+
+        assert_eq!(match_quoted(b'`', b'%', b"`a%`b`"), (true, 6));
+        assert_eq!(match_quoted(b'`', b'%', b"`a\\`b`"), (true, 4));
+        assert_eq!(match_quoted(b'`', b'%', b"`a%`"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_json_number() {
+        assert_eq!(match_json_number(b"-0.5e+10"), (true, 8));
+        assert_eq!(match_json_number(b"01"), (true, 1));
+        assert_eq!(match_json_number(b"1."), (true, 1));
+        assert_eq!(match_json_number(b"12.50]"), (true, 5));
+        assert_eq!(match_json_number(b"1E3,"), (true, 3));
+        assert_eq!(match_json_number(b"1e"), (true, 1));
+        assert_eq!(match_json_number(b"1.5e-"), (true, 3));
+        assert_eq!(match_json_number(b"-"), (false, 0));
+        assert_eq!(match_json_number(b"+1"), (false, 0));
+        assert_eq!(match_json_number(b".5"), (false, 0));
+        assert_eq!(match_json_number(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_fraction() {
+        assert_eq!(match_fraction(b"3/4"), (true, 3));
+        assert_eq!(match_fraction(b"12/345 + 1"), (true, 6));
+        assert_eq!(match_fraction(b"3/"), (false, 0));
+        assert_eq!(match_fraction(b"3 / 4"), (false, 0));
+        assert_eq!(match_fraction(b"/4"), (false, 0));
+        assert_eq!(match_fraction(b"34"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_tag_name() {
+        assert_eq!(match_tag_name(b"div class"), (true, 3));
+        assert_eq!(match_tag_name(b"my-element2>"), (true, 11));
+        assert_eq!(match_tag_name(b"h1"), (true, 2));
+        assert_eq!(match_tag_name(b"1bad"), (false, 0));
+        assert_eq!(match_tag_name(b"-x"), (false, 0));
+        assert_eq!(match_tag_name(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_xml_name() {
+        assert_eq!(match_xml_name(b"ns:el-1 "), (true, 7));
+        assert_eq!(match_xml_name(b"_a.b>"), (true, 4));
+        assert_eq!(match_xml_name(b":root"), (true, 5));
+        assert_eq!(match_xml_name(b"1bad"), (false, 0));
+        assert_eq!(match_xml_name(b"-x"), (false, 0));
+        assert_eq!(match_xml_name(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_ini_section() {
+        assert_eq!(match_ini_section(b"[server]"), (true, 8));
+        assert_eq!(match_ini_section(b"[a b] ; comment"), (true, 5));
+        assert_eq!(match_ini_section(b"[]"), (true, 2));
+        assert_eq!(match_ini_section(b"[server\n]"), (false, 0));
+        assert_eq!(match_ini_section(b"[server"), (false, 0));
+        assert_eq!(match_ini_section(b"server]"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_ini_key() {
+        assert_eq!(match_ini_key(b"host = localhost"), (true, 5));
+        assert_eq!(match_ini_key(b"port=80"), (true, 4));
+        assert_eq!(match_ini_key(b"= value"), (false, 0));
+        assert_eq!(match_ini_key(b"host\n=x"), (false, 0));
+        assert_eq!(match_ini_key(b"; a = b"), (false, 0));
+        assert_eq!(match_ini_key(b"[a=b]"), (false, 0));
+        assert_eq!(match_ini_key(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_heading() {
+        assert_eq!(match_heading(b"### title"), (true, 4, 3));
+        assert_eq!(match_heading(b"# title"), (true, 2, 1));
+        assert_eq!(match_heading(b"######\ttitle"), (true, 7, 6));
+        assert_eq!(match_heading(b"####### title"), (false, 0, 0));
+        assert_eq!(match_heading(b"#title"), (false, 0, 0));
+        assert_eq!(match_heading(b"##"), (false, 0, 0));
+        assert_eq!(match_heading(b" # title"), (false, 0, 0));
+    }
+
+    #[test]
+    fn test_match_toml_datetime() {
+        assert_eq!(match_toml_datetime(b"1979-05-27T07:32:00Z"), (true, 20));
+        assert_eq!(
+            match_toml_datetime(b"1979-05-27T00:32:00.999999-07:00\n"),
+            (true, 32)
+        );
+        assert_eq!(match_toml_datetime(b"1979-05-27 07:32:00"), (true, 19));
+        assert_eq!(match_toml_datetime(b"1979-05-27"), (true, 10));
+        assert_eq!(match_toml_datetime(b"1979-05-27 # comment"), (true, 10));
+        assert_eq!(match_toml_datetime(b"07:32:00"), (true, 8));
+        assert_eq!(match_toml_datetime(b"00:32:00.5,"), (true, 10));
+        assert_eq!(match_toml_datetime(b"23:59:60"), (true, 8));
+        assert_eq!(match_toml_datetime(b"2024-02-29"), (true, 10));
+
+        // out of range fields
+        assert_eq!(match_toml_datetime(b"2023-02-29"), (false, 0));
+        assert_eq!(match_toml_datetime(b"1979-13-01"), (false, 0));
+        assert_eq!(match_toml_datetime(b"1979-04-31"), (false, 0));
+        assert_eq!(match_toml_datetime(b"1979-00-10"), (false, 0));
+        assert_eq!(match_toml_datetime(b"24:00:00"), (false, 0));
+        assert_eq!(match_toml_datetime(b"07:60:00"), (false, 0));
+
+        // only the valid part of a date-time is matched
+        assert_eq!(match_toml_datetime(b"1979-05-27T25:00:00"), (true, 10));
+        assert_eq!(
+            match_toml_datetime(b"1979-05-27T07:32:00+24:00"),
+            (true, 19)
+        );
+        assert_eq!(match_toml_datetime(b"1979-05-27T07:32:00."), (true, 19));
+
+        assert_eq!(match_toml_datetime(b"07:32"), (false, 0));
+        assert_eq!(match_toml_datetime(b"1979-5-27"), (false, 0));
+        assert_eq!(match_toml_datetime(b""), (false, 0));
+    }
+
+    #[test]
+    fn test_match_csv_field() {
+        assert_eq!(match_csv_field(b"\"a,b\",c"), (true, 5));
+        assert_eq!(match_csv_field(b"ab,c"), (true, 2));
+        assert_eq!(match_csv_field(b"\"a\"\"b\"\n"), (true, 6));
+        assert_eq!(match_csv_field(b"ab\r\nc"), (true, 2));
+        assert_eq!(match_csv_field(b"\"unterminated"), (false, 0));
+        assert_eq!(match_csv_field(b",c"), (false, 0));
+    }
+
+    #[test]
+    fn test_match_operator() {
+        assert_eq!(match_operator(b"=="), (true, 2, OperatorKind::EqEq));
+        assert_eq!(match_operator(b"= 2"), (true, 1, OperatorKind::Equal));
+        assert_eq!(match_operator(b"<=3"), (true, 2, OperatorKind::LessEq));
+        assert_eq!(match_operator(b"<3"), (true, 1, OperatorKind::Less));
+        assert_eq!(match_operator(b"**2"), (true, 2, OperatorKind::Power));
+        assert_eq!(match_operator(b"*2"), (true, 1, OperatorKind::Star));
+        assert!(!match_operator(b"a").0);
+        assert!(!match_operator(b"").0);
+    }
 }
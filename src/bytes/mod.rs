@@ -1,6 +1,10 @@
 //! Specialized parsers for bytes.
 pub mod components;
+#[cfg(feature = "grapheme")]
+mod grapheme;
+pub mod lines;
 pub mod matchers;
 pub mod primitives;
 mod recognizer;
+mod scanner;
 pub mod token;
@@ -0,0 +1,36 @@
+//! Define the identifier token and its acceptor.
+
+use crate::bytes::matchers::match_identifier;
+use crate::errors::ParseResult;
+use crate::matcher::{Match, MatchSize};
+use crate::recognizer::recognize;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+pub struct TokenIdentifier;
+
+/// Implement the `Match` trait for the token identifier.
+impl Match<u8> for TokenIdentifier {
+    fn matcher(&self, data: &[u8]) -> (bool, usize) {
+        match_identifier(data)
+    }
+}
+
+/// Implement the `MatchSize` trait for the token identifier.
+impl MatchSize for TokenIdentifier {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+/// An identifier borrowed from the input.
+#[derive(Debug, PartialEq)]
+pub struct Identifier<'a>(pub &'a str);
+
+impl<'a> Visitor<'a, u8> for Identifier<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let raw_data = recognize(TokenIdentifier, scanner)?;
+        let str_data = std::str::from_utf8(raw_data)?;
+        Ok(Identifier(str_data))
+    }
+}
@@ -1,6 +1,8 @@
 //! Specialized primitive parsers for bytes.
 
 pub mod binary_operator;
+pub mod identifier;
 pub mod number;
+pub mod number_literal;
 pub mod string;
 pub mod whitespace;
@@ -1,8 +1,8 @@
 //! Define the number token and its acceptor.
 
-use crate::bytes::matchers::match_number;
+use crate::bytes::matchers::{match_number, match_number_streaming};
 use crate::errors::ParseResult;
-use crate::matcher::{Match, MatchSize};
+use crate::matcher::{Match, MatchResult, MatchSize};
 use crate::recognizer::recognize;
 use crate::scanner::Scanner;
 use crate::visitor::Visitor;
@@ -14,6 +14,10 @@ impl Match<u8> for TokenNumber {
     fn matcher(&self, data: &[u8]) -> (bool, usize) {
         match_number(data)
     }
+
+    fn matcher2(&self, data: &[u8]) -> MatchResult {
+        match_number_streaming(data)
+    }
 }
 
 /// Implement the `MatchSize` trait for the token number.
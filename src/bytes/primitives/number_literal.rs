@@ -0,0 +1,211 @@
+//! Define a numeric literal in any of the usual forms and its acceptor.
+//!
+//! The literal has an optional sign followed by either a `0x`, `0o` or `0b`
+//! prefixed integer, or a decimal number with an optional fraction and
+//! exponent: `-42`, `0xFF`, `0b1010`, `3.14e-2`. Underscores may separate
+//! the digits, `1_000` for example.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::ops::Range;
+
+/// The value of a numeric literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    /// An integer fitting an `i64`
+    Int(i64),
+    /// A positive integer too large for an `i64`
+    UInt(u64),
+    /// A number with a fraction or an exponent
+    Float(f64),
+}
+
+/// A numeric literal along with its radix and span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberLiteral {
+    /// The value of the literal, its sign applied
+    pub value: NumberValue,
+    /// 2, 8, 10 or 16, according to the prefix
+    pub radix: u32,
+    /// The span of the literal, its sign included
+    pub span: Range<usize>,
+}
+
+/// Count the digits of `radix` starting the data, along with the underscores
+/// following the first digit.
+fn count_digits(data: &[u8], radix: u32) -> usize {
+    let is_digit = |byte: &u8| (*byte as char).is_digit(radix);
+    match data.first() {
+        Some(byte) if is_digit(byte) => data
+            .iter()
+            .take_while(|byte| is_digit(byte) || **byte == b'_')
+            .count(),
+        _ => 0,
+    }
+}
+
+impl Visitor<'_, u8> for NumberLiteral {
+    /// Accept a numeric literal.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` if no literal stands at the cursor,
+    /// `ParseError::Syntax` if a radix prefix isn't followed by digits and
+    /// `ParseError::OutOfRange` if the value overflows. The scanner is left
+    /// untouched on error.
+    fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let data = scanner.remaining();
+
+        let (negative, sign) = match data.first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+        let (radix, digits) = match data.get(sign..sign + 2) {
+            Some(b"0x" | b"0X") => (16, sign + 2),
+            Some(b"0o" | b"0O") => (8, sign + 2),
+            Some(b"0b" | b"0B") => (2, sign + 2),
+            _ => (10, sign),
+        };
+
+        let mut end = digits + count_digits(&data[digits..], radix);
+        if end == digits {
+            if radix == 10 {
+                return Err(ParseError::UnexpectedToken);
+            }
+            return Err(ParseError::Syntax {
+                offset: start + digits,
+                reason: "missing digits after the radix prefix",
+            });
+        }
+
+        let mut float = false;
+        if radix == 10 {
+            if data.get(end) == Some(&b'.') {
+                let fraction = count_digits(&data[end + 1..], 10);
+                if fraction > 0 {
+                    end += 1 + fraction;
+                    float = true;
+                }
+            }
+            if matches!(data.get(end), Some(b'e' | b'E')) {
+                let exponent = match data.get(end + 1) {
+                    Some(b'-' | b'+') => end + 2,
+                    _ => end + 1,
+                };
+                let size = count_digits(data.get(exponent..).unwrap_or_default(), 10);
+                if size > 0 {
+                    end = exponent + size;
+                    float = true;
+                }
+            }
+        }
+
+        let span = start..start + end;
+        let out_of_range = || ParseError::OutOfRange { span: span.clone() };
+        let text = |range: Range<usize>| {
+            data[range]
+                .iter()
+                .filter(|byte| **byte != b'_')
+                .map(|byte| *byte as char)
+                .collect::<String>()
+        };
+
+        let value = if float {
+            let mut number = text(digits..end);
+            if negative {
+                number.insert(0, '-');
+            }
+            match number.parse::<f64>() {
+                Ok(value) if value.is_finite() => NumberValue::Float(value),
+                _ => return Err(out_of_range()),
+            }
+        } else {
+            let magnitude =
+                u64::from_str_radix(&text(digits..end), radix).map_err(|_| out_of_range())?;
+            match (negative, i64::try_from(magnitude)) {
+                (false, Ok(value)) => NumberValue::Int(value),
+                (false, Err(_)) => NumberValue::UInt(magnitude),
+                (true, _) => NumberValue::Int(
+                    0i64.checked_sub_unsigned(magnitude)
+                        .ok_or_else(out_of_range)?,
+                ),
+            }
+        };
+
+        scanner.bump_by(end);
+        Ok(NumberLiteral { value, radix, span })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number_literal::{NumberLiteral, NumberValue};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    fn parse(data: &[u8]) -> ParseResult<NumberLiteral> {
+        let mut scanner = Scanner::new(data);
+        NumberLiteral::accept(&mut scanner)
+    }
+
+    fn value(data: &[u8]) -> (NumberValue, u32) {
+        let literal = parse(data).expect("failed to parse");
+        assert_eq!(literal.span, 0..data.len());
+        (literal.value, literal.radix)
+    }
+
+    #[test]
+    fn test_number_literal() {
+        assert_eq!(value(b"-42"), (NumberValue::Int(-42), 10));
+        assert_eq!(value(b"0xFF"), (NumberValue::Int(255), 16));
+        assert_eq!(value(b"0b1010"), (NumberValue::Int(10), 2));
+        assert_eq!(value(b"-0o17"), (NumberValue::Int(-15), 8));
+        assert_eq!(value(b"3.14e-2"), (NumberValue::Float(0.0314), 10));
+        assert_eq!(value(b"1_000_000"), (NumberValue::Int(1_000_000), 10));
+        assert_eq!(value(b"2E3"), (NumberValue::Float(2000.0), 10));
+        assert_eq!(
+            value(b"18446744073709551615"),
+            (NumberValue::UInt(u64::MAX), 10)
+        );
+        assert_eq!(
+            value(b"-9223372036854775808"),
+            (NumberValue::Int(i64::MIN), 10)
+        );
+
+        // the dot and the exponent must be followed by digits
+        let mut scanner = Scanner::new(b"1.x 2e");
+        let literal = NumberLiteral::accept(&mut scanner).expect("failed to parse");
+        assert_eq!((literal.value, literal.span), (NumberValue::Int(1), 0..1));
+        scanner.bump_by(3);
+        let literal = NumberLiteral::accept(&mut scanner).expect("failed to parse");
+        assert_eq!((literal.value, literal.span), (NumberValue::Int(2), 4..5));
+    }
+
+    #[test]
+    fn test_number_literal_errors() {
+        let mut scanner = Scanner::new(b"= 99999999999999999999999999");
+        scanner.bump_by(2);
+        let err = NumberLiteral::accept(&mut scanner).expect_err("should overflow");
+        assert!(matches!(err, ParseError::OutOfRange { ref span } if *span == (2..28)));
+        assert_eq!(err.offset(), Some(2));
+        assert_eq!(scanner.current_position(), 2);
+
+        assert!(matches!(
+            parse(b"-9223372036854775809"),
+            Err(ParseError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            parse(b"1e999"),
+            Err(ParseError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            parse(b"0x"),
+            Err(ParseError::Syntax { offset: 2, .. })
+        ));
+        assert!(matches!(parse(b"-x"), Err(ParseError::UnexpectedToken)));
+    }
+}
@@ -1,8 +1,8 @@
 //! String primitives
 
-use crate::bytes::matchers::match_string;
+use crate::bytes::matchers::{match_quoted_string, match_quoted_string_streaming, match_string};
 use crate::errors::ParseResult;
-use crate::matcher::{Match, MatchSize};
+use crate::matcher::{Match, MatchResult, MatchSize};
 use crate::recognizer::recognize;
 use crate::scanner::Scanner;
 use crate::visitor::Visitor;
@@ -22,6 +22,26 @@ impl MatchSize for TokenString {
     }
 }
 
+/// A string enclosed in double quotes, quotes included, see
+/// [crate::bytes::matchers::match_quoted_string].
+pub struct TokenQuotedString;
+
+impl Match<u8> for TokenQuotedString {
+    fn matcher(&self, data: &[u8]) -> (bool, usize) {
+        match_quoted_string(data)
+    }
+
+    fn matcher2(&self, data: &[u8]) -> MatchResult {
+        match_quoted_string_streaming(data)
+    }
+}
+
+impl MatchSize for TokenQuotedString {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
 pub struct DataString<T>(pub T);
 
 /// Implement the `Visitor` trait for the token string.
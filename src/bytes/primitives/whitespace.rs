@@ -1,10 +1,18 @@
 //! Recognize whitespaces
 
+use crate::bytes::matchers::match_unicode_whitespace;
 use crate::bytes::token::Token;
 use crate::errors::{ParseError, ParseResult};
 use crate::recognizer::Recognizable;
 use crate::scanner::Scanner;
-use crate::visitor::Visitor;
+use crate::visitor::{ParseContext, Visitor};
+
+/// Skip the Unicode whitespaces, returning whether any was found.
+fn skip_unicode_whitespaces(scanner: &mut Scanner<u8>) -> bool {
+    let (found, size) = match_unicode_whitespace(scanner.remaining());
+    scanner.bump_by(size);
+    found
+}
 
 /// Recognize at least one whitespace
 pub struct Whitespaces;
@@ -24,6 +32,17 @@ impl<'a> Visitor<'a, u8> for Whitespaces {
         }
         Ok(Whitespaces)
     }
+
+    /// Accept Unicode whitespaces if the context asks for it.
+    fn accept_with(scanner: &mut Scanner<'a, u8>, context: &ParseContext) -> ParseResult<Self> {
+        if !context.unicode_whitespace {
+            return Self::accept(scanner);
+        }
+        if !skip_unicode_whitespaces(scanner) {
+            return Err(ParseError::UnexpectedToken);
+        }
+        Ok(Whitespaces)
+    }
 }
 
 impl<'a> Visitor<'a, u8> for OptionalWhitespaces {
@@ -34,4 +53,39 @@ impl<'a> Visitor<'a, u8> for OptionalWhitespaces {
         while Token::Whitespace.recognize(scanner)?.is_some() {}
         Ok(OptionalWhitespaces)
     }
+
+    /// Accept Unicode whitespaces if the context asks for it.
+    fn accept_with(scanner: &mut Scanner<'a, u8>, context: &ParseContext) -> ParseResult<Self> {
+        if !context.unicode_whitespace {
+            return Self::accept(scanner);
+        }
+        skip_unicode_whitespaces(scanner);
+        Ok(OptionalWhitespaces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::whitespace::{OptionalWhitespaces, Whitespaces};
+    use crate::scanner::Scanner;
+    use crate::visitor::ParseContext;
+
+    #[test]
+    fn test_unicode_whitespaces() {
+        let context = ParseContext {
+            unicode_whitespace: true,
+            ..Default::default()
+        };
+        let data = " \u{a0}\u{3000}a".as_bytes();
+        let mut scanner = Scanner::new(data);
+        assert!(scanner.visit_with::<Whitespaces>(&context).is_ok());
+        assert_eq!(scanner.remaining(), b"a");
+        assert!(scanner.visit_with::<Whitespaces>(&context).is_err());
+        assert!(scanner.visit_with::<OptionalWhitespaces>(&context).is_ok());
+        assert_eq!(scanner.remaining(), b"a");
+
+        let mut scanner = Scanner::new(data);
+        assert!(scanner.visit::<Whitespaces>().is_ok());
+        assert_eq!(scanner.current_position(), 1);
+    }
 }
@@ -0,0 +1,543 @@
+//! Byte specific scanner operations.
+
+use crate::bytes::token::Token;
+use crate::describe::{Describe, Expectation};
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::MatchSize;
+use crate::recognizer::Recognizable;
+use crate::scanner::Scanner;
+
+impl<'a> Scanner<'a, u8> {
+    /// Return the remaining data as a string slice without checking that it
+    /// is valid UTF-8.
+    ///
+    /// # Returns
+    ///
+    /// The remaining data as a string slice.
+    ///
+    /// # Safety
+    ///
+    /// The remaining data must be valid UTF-8, which is the case if the whole
+    /// data comes from a `&str` and the cursor stands on a char boundary.
+    /// Calling this method on invalid UTF-8 is undefined behavior, use
+    /// `std::str::from_utf8` on `remaining()` when in doubt.
+    pub unsafe fn as_str_unchecked(&self) -> &'a str {
+        let remaining = &self.data()[self.current_position()..];
+        // SAFETY: upheld by the caller
+        unsafe { std::str::from_utf8_unchecked(remaining) }
+    }
+
+    /// Consume a token if it stands at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The token to consume.
+    ///
+    /// # Returns
+    ///
+    /// true if the token has been consumed, false otherwise, the cursor is
+    /// then left untouched.
+    pub fn eat(&mut self, token: Token) -> bool {
+        if token.size() > self.remaining().len() {
+            return false;
+        }
+        matches!(token.recognize(self), Ok(Some(_)))
+    }
+
+    /// Consume the first of several tokens standing at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - The tokens to try, in order.
+    ///
+    /// # Returns
+    ///
+    /// The consumed token.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::ExpectedStructured` expecting one of the tokens if none of
+    /// them stands at the cursor, which is then left untouched.
+    pub fn expect_one_of(&mut self, tokens: &[Token]) -> ParseResult<Token> {
+        match tokens.iter().find(|token| self.eat(**token)) {
+            Some(token) => Ok(*token),
+            None => Err(ParseError::ExpectedStructured {
+                offset: self.current_position(),
+                expected: Expectation::OneOf(tokens.iter().map(Token::expected).collect()),
+            }),
+        }
+    }
+
+    /// Parse items separated by a synchronization token, collecting the
+    /// errors instead of stopping at the first one.
+    ///
+    /// An item failing is recorded and the data up to the next `sync` token
+    /// is skipped, the parse going on after it. An item followed by neither
+    /// `sync` nor the end of the data makes the next item fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Parses an item.
+    /// * `sync` - The token following each item, consumed.
+    ///
+    /// # Returns
+    ///
+    /// The parsed items and the errors, each in the order of the data. The
+    /// parse stops on `ParseError::Incomplete`, `ParseError::BudgetExhausted`
+    /// or `ParseError::BacktrackLimitExceeded`, which can't be recovered
+    /// from.
+    pub fn parse_with_recovery<V>(
+        &mut self,
+        item: impl Fn(&mut Scanner<'a, u8>) -> ParseResult<V>,
+        sync: Token,
+    ) -> (Vec<V>, Vec<ParseError>) {
+        let mut values = vec![];
+        let mut errors = vec![];
+        while !self.is_empty() {
+            let start = self.current_position();
+            let result = match self.rollback_on_err(&item) {
+                // nothing would ever be consumed again
+                Ok(_) if self.current_position() == start && self.peek_len(&sync).is_none() => {
+                    Err(ParseError::NoProgress { offset: start })
+                }
+                result => result,
+            };
+            match result {
+                Ok(value) => values.push(value),
+                Err(err) if err.is_unrecoverable() => {
+                    errors.push(err);
+                    break;
+                }
+                Err(err) => {
+                    errors.push(err);
+                    let skipped = self.count_until(&sync).unwrap_or(self.remaining().len());
+                    self.bump_by(skipped);
+                }
+            }
+            self.eat(sync);
+        }
+        (values, errors)
+    }
+
+    /// Look ahead for the nth token, without consuming anything.
+    ///
+    /// Whitespaces, tabs and line breaks are skipped, so the tokens returned
+    /// are the single byte punctuation tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the token among the next non-whitespace bytes,
+    ///   `0` being the first one.
+    ///
+    /// # Returns
+    ///
+    /// The nth token, `None` if the data ends before it or if the byte there
+    /// isn't a token.
+    pub fn peek_token_at(&self, n: usize) -> Option<Token> {
+        self.remaining()
+            .iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .nth(n)
+            .and_then(|byte| Token::from_byte(*byte))
+    }
+
+    /// Render the bytes around the cursor in hexadecimal, to debug a binary
+    /// parse.
+    ///
+    /// Each row holds up to 16 bytes as `offset: hex | ascii`, the non
+    /// printable bytes being shown as `.` in the ASCII column. The row of the
+    /// cursor starts with `>`, the other rows with a space.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The number of bytes to render before and after the
+    ///   cursor.
+    ///
+    /// # Returns
+    ///
+    /// The rows, each ending with a line break.
+    pub fn hexdump(&self, context: usize) -> String {
+        const WIDTH: usize = 16;
+
+        let data = self.data();
+        let position = self.current_position().min(data.len());
+        let start = position.saturating_sub(context);
+        let end = position.saturating_add(context).min(data.len());
+
+        let mut rows = (start..end).step_by(WIDTH).collect::<Vec<_>>();
+        // a cursor at the end of a full row gets its own empty row
+        if rows.last().is_none_or(|row| position >= row + WIDTH) {
+            rows.push(position);
+        }
+
+        let mut dump = String::new();
+        for row in rows {
+            let bytes = &data[row..end.min(row + WIDTH)];
+            let hex = bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let ascii = bytes
+                .iter()
+                .map(|byte| match byte {
+                    b' '..=b'~' => *byte as char,
+                    _ => '.',
+                })
+                .collect::<String>();
+            let marker = if (row..row + WIDTH).contains(&position) {
+                '>'
+            } else {
+                ' '
+            };
+            dump.push_str(&format!(
+                "{marker} {row:08x}: {hex:<width$} | {ascii}\n",
+                width = WIDTH * 3 - 1
+            ));
+        }
+        dump
+    }
+
+    /// Skip the padding up to the next multiple of `alignment`, the end of
+    /// the data at most.
+    ///
+    /// # Arguments
+    ///
+    /// * `alignment` - The boundary of the fields, `0` being taken as `1`.
+    ///
+    /// # Returns
+    ///
+    /// The number of padding bytes skipped.
+    pub fn align_to(&mut self, alignment: usize) -> usize {
+        let position = self.current_position();
+        let padding = position.next_multiple_of(alignment.max(1)) - position;
+        let padding = padding.min(self.remaining().len());
+        self.bump_by(padding);
+        padding
+    }
+
+    /// Count the occurrences of a byte in the remaining data, without
+    /// consuming anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte` - The byte to count.
+    ///
+    /// # Returns
+    ///
+    /// The number of occurrences of `byte`.
+    pub fn byte_count(&self, byte: u8) -> usize {
+        self.remaining()
+            .iter()
+            .filter(|&&found| found == byte)
+            .count()
+    }
+
+    /// Consume the line holding the cursor.
+    ///
+    /// The line ends with the next `\n`, which is consumed but not returned,
+    /// as is a `\r` preceding it, or with the end of the data.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the line, `None` if the scanner is empty.
+    pub fn consume_line(&mut self) -> Option<&'a [u8]> {
+        let start = self.current_position();
+        let remaining = self.data().get(start..).filter(|data| !data.is_empty())?;
+        let (line, size) = match remaining.iter().position(|byte| *byte == b'\n') {
+            Some(newline) => (&remaining[..newline], newline + 1),
+            None => (remaining, remaining.len()),
+        };
+        self.bump_by(size);
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    /// Count the indentation columns of the line holding the cursor.
+    ///
+    /// The leading spaces and tabs of the line are counted from the byte
+    /// following the previous `\n`, a tab moving to the next multiple of
+    /// `tab_width`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_width` - The number of columns between two tab stops.
+    ///
+    /// # Returns
+    ///
+    /// The indentation level of the current line, in columns.
+    pub fn line_indentation(&self, tab_width: usize) -> usize {
+        let data = self.data();
+        let position = self.current_position().min(data.len());
+        let start = data[..position]
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        let tab_width = tab_width.max(1);
+        let mut columns = 0;
+        for byte in &data[start..] {
+            match byte {
+                b' ' => columns += 1,
+                b'\t' => columns = (columns / tab_width + 1) * tab_width,
+                _ => break,
+            }
+        }
+        columns
+    }
+
+    /// Consume `n` extended grapheme clusters.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of clusters to consume.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes consumed.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if fewer than `n` clusters remain,
+    /// `ParseError::Syntax` at the offset of an invalid UTF-8 sequence. The
+    /// cursor is left untouched on error.
+    #[cfg(feature = "grapheme")]
+    pub fn take_graphemes(&mut self, n: usize) -> ParseResult<usize> {
+        let start = self.current_position();
+        let mut pos = start;
+        for _ in 0..n {
+            let size =
+                crate::bytes::grapheme::next_boundary(&self.data()[pos..]).map_err(|offset| {
+                    ParseError::Syntax {
+                        offset: pos + offset,
+                        reason: "invalid UTF-8",
+                    }
+                })?;
+            if size == 0 {
+                return Err(ParseError::UnexpectedEndOfInput);
+            }
+            pos += size;
+        }
+        self.jump_to(pos);
+        Ok(pos - start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::token::Token;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_as_str_unchecked() {
+        let data = "1 + 2 = 3";
+        let mut scanner = Scanner::new(data.as_bytes());
+        // SAFETY: the data comes from a `&str`
+        assert_eq!(unsafe { scanner.as_str_unchecked() }, "1 + 2 = 3");
+        scanner.bump_by(4);
+        // SAFETY: the cursor stands on a char boundary
+        assert_eq!(unsafe { scanner.as_str_unchecked() }, "2 = 3");
+    }
+
+    #[test]
+    fn test_eat() {
+        let data = b",a";
+        let mut scanner = Scanner::new(data);
+        assert!(scanner.eat(Token::Comma));
+        assert_eq!(scanner.current_position(), 1);
+        assert!(!scanner.eat(Token::Comma));
+        assert_eq!(scanner.current_position(), 1);
+        scanner.bump_by(1);
+        assert!(!scanner.eat(Token::Comma));
+    }
+
+    #[test]
+    fn test_expect_one_of() {
+        let signs = [Token::Plus, Token::Dash];
+        let mut scanner = Scanner::new(b"-+x");
+        assert_eq!(
+            scanner.expect_one_of(&signs).expect("failed to parse"),
+            Token::Dash
+        );
+        assert_eq!(
+            scanner.expect_one_of(&signs).expect("failed to parse"),
+            Token::Plus
+        );
+        assert_eq!(scanner.current_position(), 2);
+
+        let err = scanner.expect_one_of(&signs).expect_err("should fail");
+        assert_eq!(err.to_string(), "Expected one of \"+\", \"-\" at offset 2");
+        assert_eq!(scanner.current_position(), 2);
+    }
+
+    #[test]
+    fn test_parse_with_recovery() {
+        use crate::bytes::primitives::identifier::TokenIdentifier;
+        use crate::bytes::primitives::number::Number;
+        use crate::errors::{ParseError, ParseResult};
+        use crate::recognizer::recognize;
+        use crate::visitor::Visitor;
+
+        /// `name=number`
+        fn statement<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<(&'a [u8], usize)> {
+            let name = recognize(TokenIdentifier, scanner)?;
+            recognize(Token::Equal, scanner)?;
+            let Number(value) = Number::accept(scanner)?;
+            Ok((name, value))
+        }
+
+        let mut scanner = Scanner::new(b"x=1;y=+2;z=3");
+        let (values, errors) = scanner.parse_with_recovery(statement, Token::Semicolon);
+        assert_eq!(values, [(&b"x"[..], 1), (&b"z"[..], 3)]);
+        assert!(matches!(errors[..], [ParseError::UnexpectedToken]));
+        assert!(scanner.is_empty());
+
+        // a missing synchronization token fails the next item
+        let mut scanner = Scanner::new(b"x=1 y=2;z=3;");
+        let (values, errors) = scanner.parse_with_recovery(statement, Token::Semicolon);
+        assert_eq!(values, [(&b"x"[..], 1), (&b"z"[..], 3)]);
+        assert_eq!(errors.len(), 1);
+
+        // an item consuming nothing is reported once
+        let mut scanner = Scanner::new(b"ab;c");
+        let (values, errors) = scanner.parse_with_recovery(|_| Ok(()), Token::Semicolon);
+        assert!(values.is_empty());
+        assert!(matches!(
+            errors[..],
+            [
+                ParseError::NoProgress { offset: 0 },
+                ParseError::NoProgress { offset: 3 }
+            ]
+        ));
+
+        // empty items between the synchronization tokens are kept
+        let mut scanner = Scanner::new(b";;");
+        let (values, errors) = scanner.parse_with_recovery(|_| Ok(()), Token::Semicolon);
+        assert_eq!(values.len(), 2);
+        assert!(errors.is_empty());
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_peek_token_at() {
+        let mut scanner = Scanner::new(b"( )");
+        assert_eq!(scanner.peek_token_at(0), Some(Token::OpenParen));
+        assert_eq!(scanner.peek_token_at(1), Some(Token::CloseParen));
+        assert_eq!(scanner.peek_token_at(2), None);
+        assert_eq!(scanner.current_position(), 0);
+
+        scanner = Scanner::new(b"\t,\n a;");
+        assert_eq!(scanner.peek_token_at(0), Some(Token::Comma));
+        assert_eq!(scanner.peek_token_at(1), None);
+        assert_eq!(scanner.peek_token_at(2), Some(Token::Semicolon));
+        scanner.bump_by(2);
+        assert_eq!(scanner.peek_token_at(1), Some(Token::Semicolon));
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let data = b"\x7fELF\x02\x01\x01\x00 some header data";
+        let mut scanner = Scanner::new(data);
+        scanner.bump_by(18);
+        assert_eq!(
+            scanner.hexdump(10),
+            "> 00000008: 20 73 6f 6d 65 20 68 65 61 64 65 72 20 64 61 74 |  some header dat\n\
+             \x20 00000018: 61                                              | a\n"
+        );
+        assert_eq!(
+            Scanner::new(data).hexdump(4),
+            "> 00000000: 7f 45 4c 46                                     | .ELF\n"
+        );
+
+        // the cursor at the end of the data
+        scanner.bump_by(data.len() - 18);
+        assert_eq!(
+            scanner.hexdump(2),
+            "> 00000017: 74 61                                           | ta\n"
+        );
+        assert_eq!(
+            Scanner::new(b"").hexdump(2),
+            "> 00000000:                                                 | \n"
+        );
+    }
+
+    #[test]
+    fn test_align_to() {
+        let mut scanner = Scanner::new(&[0; 10]);
+        scanner.bump_by(3);
+        assert_eq!(scanner.align_to(4), 1);
+        assert_eq!(scanner.current_position(), 4);
+        assert_eq!(scanner.align_to(4), 0);
+        assert_eq!(scanner.align_to(0), 0);
+
+        // the padding stops at the end of the data
+        assert_eq!(scanner.align_to(8), 4);
+        assert_eq!(scanner.align_to(16), 2);
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_byte_count() {
+        let data = b"a,b,c";
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.byte_count(b','), 2);
+        assert_eq!(scanner.byte_count(b';'), 0);
+        scanner.bump_by(2);
+        assert_eq!(scanner.byte_count(b','), 1);
+        assert_eq!(scanner.current_position(), 2);
+    }
+
+    #[test]
+    fn test_consume_line() {
+        let data = b"a\r\nb";
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.consume_line(), Some(&b"a"[..]));
+        assert_eq!(scanner.current_position(), 3);
+        assert_eq!(scanner.consume_line(), Some(&b"b"[..]));
+        assert_eq!(scanner.consume_line(), None);
+
+        let mut scanner = Scanner::new(b"\n\r");
+        assert_eq!(scanner.consume_line(), Some(&b""[..]));
+        assert_eq!(scanner.consume_line(), Some(&b""[..]));
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_line_indentation() {
+        let data = b"a\n    b\n\t  c\n \td";
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.line_indentation(4), 0);
+        scanner.bump_by(2);
+        assert_eq!(scanner.line_indentation(4), 4);
+        scanner.bump_by(5);
+        assert_eq!(scanner.line_indentation(4), 4);
+        scanner.bump_by(1);
+        assert_eq!(scanner.line_indentation(4), 6);
+        assert_eq!(scanner.line_indentation(8), 10);
+        scanner.bump_by(5);
+        assert_eq!(scanner.line_indentation(4), 4);
+    }
+
+    #[cfg(feature = "grapheme")]
+    #[test]
+    fn test_take_graphemes() {
+        use crate::errors::ParseError;
+
+        let data = "e\u{301}\r\n\u{1f468}\u{200d}\u{1f469}x".as_bytes();
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.take_graphemes(2).expect("failed to parse"), 5);
+        assert_eq!(scanner.take_graphemes(1).expect("failed to parse"), 11);
+        assert!(matches!(
+            scanner.take_graphemes(2),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.remaining(), b"x");
+
+        let data = b"ab\xffc";
+        let mut scanner = Scanner::new(data);
+        assert!(matches!(
+            scanner.take_graphemes(3),
+            Err(ParseError::Syntax { offset: 2, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
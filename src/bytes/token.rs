@@ -1,7 +1,7 @@
 //! Classic tokens
 
-use crate::bytes::matchers::{match_char, match_pattern};
-use crate::matcher::{Match, MatchSize};
+use crate::bytes::matchers::{match_char, match_pattern, match_pattern_streaming};
+use crate::matcher::{Match, MatchResult, MatchSize, Needed};
 
 #[derive(Copy, Clone)]
 /// The token type
@@ -11,6 +11,10 @@ pub enum Token {
     OpenParen,
     /// The `)` character
     CloseParen,
+    /// The `[` character
+    OpenBracket,
+    /// The `]` character
+    CloseBracket,
     /// The `,` character
     Comma,
     /// The `;` character
@@ -75,11 +79,56 @@ pub enum Token {
     CrLn,
 }
 
+impl Token {
+    /// Return the single byte token matching `byte`, `#` being a [Token::Hash].
+    pub(crate) fn from_byte(byte: u8) -> Option<Token> {
+        let token = match byte {
+            b'(' => Token::OpenParen,
+            b')' => Token::CloseParen,
+            b'[' => Token::OpenBracket,
+            b']' => Token::CloseBracket,
+            b',' => Token::Comma,
+            b';' => Token::Semicolon,
+            b':' => Token::Colon,
+            b' ' => Token::Whitespace,
+            b'>' => Token::GreaterThan,
+            b'<' => Token::LessThan,
+            b'!' => Token::Exclamation,
+            b'\'' => Token::Quote,
+            b'"' => Token::DoubleQuote,
+            b'=' => Token::Equal,
+            b'+' => Token::Plus,
+            b'-' => Token::Dash,
+            b'/' => Token::Slash,
+            b'*' => Token::Star,
+            b'%' => Token::Percent,
+            b'&' => Token::Ampersand,
+            b'|' => Token::Pipe,
+            b'^' => Token::Caret,
+            b'~' => Token::Tilde,
+            b'.' => Token::Dot,
+            b'?' => Token::Question,
+            b'@' => Token::At,
+            b'#' => Token::Hash,
+            b'$' => Token::Dollar,
+            b'\\' => Token::Backslash,
+            b'_' => Token::Underscore,
+            b'\n' => Token::Ln,
+            b'\r' => Token::Cr,
+            b'\t' => Token::Tab,
+            _ => return None,
+        };
+        Some(token)
+    }
+}
+
 impl Match<u8> for Token {
     fn matcher(&self, data: &[u8]) -> (bool, usize) {
         match self {
             Token::OpenParen => match_char('(', data),
             Token::CloseParen => match_char(')', data),
+            Token::OpenBracket => match_char('[', data),
+            Token::CloseBracket => match_char(']', data),
             Token::Comma => match_char(',', data),
             Token::Semicolon => match_char(';', data),
             Token::Colon => match_char(':', data),
@@ -113,6 +162,14 @@ impl Match<u8> for Token {
             Token::CrLn => match_pattern(b"\r\n", data),
         }
     }
+
+    fn matcher2(&self, data: &[u8]) -> MatchResult {
+        match self {
+            Token::CrLn => match_pattern_streaming(b"\r\n", data),
+            _ if data.is_empty() => MatchResult::Incomplete(Needed::Size(1)),
+            _ => self.matcher(data).into(),
+        }
+    }
 }
 
 impl MatchSize for Token {
@@ -120,6 +177,8 @@ impl MatchSize for Token {
         match self {
             Token::OpenParen => 1,
             Token::CloseParen => 1,
+            Token::OpenBracket => 1,
+            Token::CloseBracket => 1,
             Token::Comma => 1,
             Token::Semicolon => 1,
             Token::Colon => 1,
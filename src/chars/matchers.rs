@@ -0,0 +1,265 @@
+//! Char slice matchers.
+//!
+//! These matchers share their semantics with the byte matchers of
+//! [crate::bytes::matchers], the consumed sizes being counted in chars.
+
+use crate::symbol;
+
+/// Attempt to match a single character against a char slice.
+///
+/// # Arguments
+///
+/// * `pattern` - The character to match against.
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_char(pattern: char, data: &[char]) -> (bool, usize) {
+    symbol::match_symbol(pattern, data)
+}
+
+/// Attempt to match a literal against a char slice, ignoring ASCII case.
+///
+/// # Arguments
+///
+/// * `pattern` - The literal to match against.
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_literal(pattern: &str, data: &[char]) -> (bool, usize) {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    symbol::match_sequence(pattern.into_iter(), data)
+}
+
+/// Attempt to match a number against a char slice.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_number(data: &[char]) -> (bool, usize) {
+    symbol::match_digits(data)
+}
+
+/// Attempt to match a run of ASCII whitespaces against a char slice.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_whitespace(data: &[char]) -> (bool, usize) {
+    symbol::match_whitespaces(data)
+}
+
+/// Attempt to match a run of Unicode whitespaces against a char slice.
+///
+/// Every `White_Space` code point is accepted, NBSP (U+00A0) or the
+/// ideographic space (U+3000) for example.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_unicode_whitespace(data: &[char]) -> (bool, usize) {
+    symbol::match_unicode_whitespaces(data)
+}
+
+/// Attempt to match an identifier against a char slice.
+///
+/// An identifier starts with an ASCII letter or `_`, followed by ASCII
+/// letters, digits or `_`.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_identifier(data: &[char]) -> (bool, usize) {
+    symbol::match_identifier(data)
+}
+
+/// Attempt to match a Unicode identifier against a char slice.
+///
+/// An identifier starts with `_` or an XID_Start code point, followed by
+/// XID_Continue code points, `café` or `変数` for example.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+#[cfg(feature = "unicode")]
+pub fn match_unicode_identifier(data: &[char]) -> (bool, usize) {
+    match data.first() {
+        Some(&symbol) if symbol::is_unicode_identifier_start(symbol) => {}
+        _ => return (false, 0),
+    }
+    symbol::match_while(data, |&symbol| {
+        symbol::is_unicode_identifier_continue(symbol)
+    })
+}
+
+/// Attempt to match a string enclosed in double quotes against a char slice.
+///
+/// A `\` escapes the following char, so `"a\"b"` is a single string.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded, quotes included.
+pub fn match_quoted_string(data: &[char]) -> (bool, usize) {
+    symbol::match_quoted_string(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::matchers as bytes;
+    use crate::chars::matchers::{
+        match_char, match_identifier, match_literal, match_number, match_quoted_string,
+        match_unicode_whitespace, match_whitespace,
+    };
+
+    type Matchers = (fn(&[u8]) -> (bool, usize), fn(&[char]) -> (bool, usize));
+    type Vectors = &'static [(&'static str, (bool, usize))];
+
+    /// Inputs shared by the byte and char matchers, with the expected result.
+    const VECTORS: &[(&str, Matchers, Vectors)] = &[
+        (
+            "number",
+            (bytes::match_number, match_number),
+            &[
+                ("123abc", (true, 3)),
+                ("abc123", (false, 0)),
+                ("", (false, 0)),
+            ],
+        ),
+        (
+            "whitespace",
+            (bytes::match_whitespace, match_whitespace),
+            &[(" \t\r\na", (true, 4)), ("a ", (false, 0))],
+        ),
+        (
+            "identifier",
+            (bytes::match_identifier, match_identifier),
+            &[
+                ("_foo_42.bar", (true, 7)),
+                ("a", (true, 1)),
+                ("42a", (false, 0)),
+                ("", (false, 0)),
+            ],
+        ),
+        (
+            "quoted string",
+            (bytes::match_quoted_string, match_quoted_string),
+            &[
+                (r#""abc" d"#, (true, 5)),
+                (r#""a\"b""#, (true, 6)),
+                (r#""abc"#, (false, 0)),
+                ("abc", (false, 0)),
+            ],
+        ),
+    ];
+
+    fn chars(data: &str) -> Vec<char> {
+        data.chars().collect()
+    }
+
+    #[test]
+    fn test_parity_with_bytes() {
+        for (name, (match_bytes, match_chars), vectors) in VECTORS {
+            for (data, expected) in *vectors {
+                assert_eq!(match_bytes(data.as_bytes()), *expected, "{name} {data:?}");
+                assert_eq!(match_chars(&chars(data)), *expected, "{name} {data:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_char() {
+        assert_eq!(
+            match_char('a', &chars("abc")),
+            bytes::match_char('a', b"abc")
+        );
+        assert_eq!(
+            match_char('b', &chars("abc")),
+            bytes::match_char('b', b"abc")
+        );
+        assert_eq!(match_char('é', &chars("éa")), (true, 1));
+        assert!(!match_char('a', &[]).0);
+    }
+
+    #[test]
+    fn test_match_literal() {
+        assert_eq!(match_literal("abc", &chars("abcdef")), (true, 3));
+        assert_eq!(match_literal("abc", &chars("ABCdef")), (true, 3));
+        assert_eq!(match_literal("abc", &chars("bbcdefg")), (false, 0));
+        assert_eq!(match_literal("abc", &chars("ab")), (false, 0));
+        assert_eq!(match_literal("", &chars("abc")), (false, 0));
+        assert_eq!(match_literal("été", &chars("été!")), (true, 3));
+    }
+
+    #[test]
+    fn test_match_unicode_whitespace() {
+        assert_eq!(match_unicode_whitespace(&chars("\u{a0}a")), (true, 1));
+        assert_eq!(
+            match_unicode_whitespace(&chars("\u{3000}\u{3000}a")),
+            (true, 2)
+        );
+        assert_eq!(
+            match_unicode_whitespace(&chars(" \u{a0}\t\u{2003}\na")),
+            (true, 5)
+        );
+        assert_eq!(match_unicode_whitespace(&chars("a ")), (false, 0));
+        assert_eq!(match_whitespace(&chars("\u{a0}")), (false, 0));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_match_unicode_identifier() {
+        use crate::chars::matchers::match_unicode_identifier;
+
+        assert_eq!(match_unicode_identifier(&chars("café.au")), (true, 4));
+        assert_eq!(match_unicode_identifier(&chars("変数+1")), (true, 2));
+        assert_eq!(match_unicode_identifier(&chars("_x\u{301}y")), (true, 4));
+        assert_eq!(match_unicode_identifier(&chars("\u{301}x")), (false, 0));
+        assert_eq!(match_unicode_identifier(&chars("1x")), (false, 0));
+        assert_eq!(match_unicode_identifier(&['f', 'o', 'ø', '2']), (true, 4));
+        assert_eq!(match_unicode_identifier(&chars("-x")), (false, 0));
+        assert_eq!(match_unicode_identifier(&[]), (false, 0));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        // sizes are counted in chars, not bytes
+        assert_eq!(match_quoted_string(&chars(r#""héhé" x"#)), (true, 6));
+        assert_eq!(match_identifier(&chars("aé")), (true, 1));
+        assert_eq!(match_number(&chars("٣")), (false, 0));
+    }
+}
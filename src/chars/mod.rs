@@ -0,0 +1,4 @@
+//! Specialized parsers for chars.
+pub mod matchers;
+pub mod recognizer;
+pub mod scanner;
@@ -0,0 +1,117 @@
+//! Recognizers specific to chars.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::{Match, MatchSize, Needed};
+use crate::recognizer::{Recognizable, match_at};
+use crate::scanner::Scanner;
+use std::ops::RangeInclusive;
+
+/// Recognize an object for the given scanner.
+/// Return a slice of the recognized object.
+impl<'a, M: Match<char> + MatchSize> Recognizable<'a, char, &'a [char]> for M {
+    fn recognize(self, scanner: &mut Scanner<'a, char>) -> ParseResult<Option<&'a [char]>> {
+        let Some(size) = match_at(&self, scanner)? else {
+            return Ok(None);
+        };
+        let current_position = scanner.current_position();
+        scanner.bump_by(size);
+        Ok(Some(
+            &scanner.data()[current_position..current_position + size],
+        ))
+    }
+}
+
+/// A recognizable object matching a char of a range, see [char_range].
+///
+/// `RangeInclusive<char>` doesn't implement [Recognizable] itself, its
+/// combinators such as `map` would be ambiguous with the ones of
+/// `Iterator`. A range is recognized through [RecognizeCharRange] instead.
+#[derive(Debug, Clone)]
+pub struct CharRange(RangeInclusive<char>);
+
+/// Build a recognizable object matching a single char of `range`, the char
+/// itself being recognized.
+///
+/// # Arguments
+/// * `range` - The chars to match.
+pub fn char_range(range: RangeInclusive<char>) -> CharRange {
+    CharRange(range)
+}
+
+/// A range of chars matches a single char.
+impl MatchSize for CharRange {
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// Recognize a char of the range, returning the char itself.
+impl<'a> Recognizable<'a, char, char> for CharRange {
+    fn recognize(self, scanner: &mut Scanner<'a, char>) -> ParseResult<Option<char>> {
+        scanner.burn_fuel()?;
+        match scanner.remaining().first() {
+            Some(symbol) if self.0.contains(symbol) => {
+                let symbol = *symbol;
+                scanner.bump_by(1);
+                Ok(Some(symbol))
+            }
+            Some(_) => Ok(None),
+            None if scanner.is_streaming() => Err(ParseError::Incomplete {
+                needed: Needed::Size(1),
+            }),
+            None => Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+}
+
+/// Recognize a char of a range, `('a'..='z').recognize(scanner)` for
+/// example.
+pub trait RecognizeCharRange {
+    /// Recognize a char of the range, see [CharRange].
+    fn recognize(self, scanner: &mut Scanner<'_, char>) -> ParseResult<Option<char>>;
+}
+
+impl RecognizeCharRange for RangeInclusive<char> {
+    fn recognize(self, scanner: &mut Scanner<'_, char>) -> ParseResult<Option<char>> {
+        char_range(self).recognize(scanner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::recognizer::{RecognizeCharRange, char_range};
+    use crate::errors::ParseError;
+    use crate::recognizer::{Recognizable, recognize};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_char_range() {
+        let data = ['x', '1'];
+        let mut scanner = Scanner::new(&data);
+        assert_eq!(
+            ('a'..='z')
+                .recognize(&mut scanner)
+                .expect("failed to parse"),
+            Some('x')
+        );
+        assert_eq!(
+            char_range('a'..='z')
+                .recognize(&mut scanner)
+                .expect("failed to parse"),
+            None
+        );
+        assert_eq!(scanner.current_position(), 1);
+        assert_eq!(
+            recognize(char_range('0'..='9'), &mut scanner).expect("failed to parse"),
+            '1'
+        );
+        assert!(matches!(
+            recognize(char_range('0'..='9'), &mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+
+        // the range itself is left to Iterator, Recognizable being in scope
+        let upper: String = ('a'..='c').map(|c| c.to_ascii_uppercase()).collect();
+        assert_eq!(upper, "ABC");
+    }
+}
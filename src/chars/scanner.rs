@@ -0,0 +1,344 @@
+//! A scanner of chars borrowing a string slice.
+//!
+//! Unlike `Scanner<char>`, which requires collecting the text into a
+//! `Vec<char>` first, a [CharScanner] decodes the UTF-8 text on the fly.
+//! Its positions are byte offsets into the original string.
+//!
+//! The char matchers and visitors, those running over a `Scanner<char>`,
+//! run over it through [CharScanner::recognize] and [CharScanner::visit].
+
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::{Match, MatchSize};
+use crate::recognizer::match_at;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// A scanner of chars over a string slice.
+#[derive(Debug, Clone)]
+pub struct CharScanner<'a> {
+    data: &'a str,
+    /// Byte offset of the cursor, always on a char boundary.
+    position: usize,
+    /// The chars decoded ahead of the cursor, reused between recognitions.
+    buffer: Vec<char>,
+}
+
+impl<'a> CharScanner<'a> {
+    pub fn new(data: &'a str) -> CharScanner<'a> {
+        CharScanner {
+            data,
+            position: 0,
+            buffer: vec![],
+        }
+    }
+
+    /// Return the char at the cursor, if any.
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    /// Move the cursor forward by `n` chars.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of chars to move the cursor forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` chars remain.
+    pub fn bump_by(&mut self, n: usize) {
+        let size = self
+            .remaining()
+            .char_indices()
+            .map(|(offset, char)| offset + char.len_utf8())
+            .nth(n.wrapping_sub(1))
+            .unwrap_or(0);
+        assert!(n == 0 || size > 0, "moved past the end of the data");
+        self.position += size;
+    }
+
+    /// Move the cursor to the specified byte offset.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The byte offset to move the cursor to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is not a char boundary of the data.
+    pub fn jump_to(&mut self, position: usize) {
+        assert!(self.data.is_char_boundary(position), "not a char boundary");
+        self.position = position;
+    }
+
+    /// Return the byte offset of the cursor.
+    pub fn current_position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the text that remains to be scanned.
+    pub fn remaining(&self) -> &'a str {
+        &self.data[self.position..]
+    }
+
+    /// Return the original text given to the scanner.
+    pub fn data(&self) -> &'a str {
+        self.data
+    }
+
+    /// Return true if there are no more chars to scan, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// Recognize a char matcher at the cursor and consume it.
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to recognize, as run over a char slice.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(&str))` the recognized text,
+    /// * `Ok(None)` if the matcher was not recognized,
+    /// * `Err(ParseError::UnexpectedEndOfInput)` if the scanner is empty.
+    pub fn recognize<M: Match<char> + MatchSize>(
+        &mut self,
+        matcher: M,
+    ) -> ParseResult<Option<&'a str>> {
+        let start = self.position;
+        let found = self.run(matcher.size(), |scanner| {
+            let size = match_at(&matcher, scanner)?;
+            if let Some(size) = size {
+                scanner.bump_by(size);
+            }
+            Ok(size.is_some())
+        })?;
+        Ok(found.then(|| &self.data[start..self.position]))
+    }
+
+    /// Run a char visitor at the cursor.
+    ///
+    /// The visitor can't borrow the data, its errors are positioned at byte
+    /// offsets of the text.
+    pub fn visit<V: for<'b> Visitor<'b, char>>(&mut self) -> ParseResult<V> {
+        self.run(0, |scanner| V::accept(scanner))
+    }
+
+    /// Run a recognition over a [Scanner] of the chars ahead of the cursor,
+    /// and move the cursor past the chars it consumed.
+    ///
+    /// The chars are decoded in a window, grown as long as the recognition
+    /// reaches its end before the end of the text, so that the result
+    /// doesn't depend on the window. A matcher failing on a window cut
+    /// shorter than its size, or than [WINDOW], is not run again.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of chars to decode at least.
+    /// * `f` - The recognition to run.
+    fn run<R>(
+        &mut self,
+        size: usize,
+        f: impl Fn(&mut Scanner<'_, char>) -> ParseResult<R>,
+    ) -> ParseResult<R> {
+        let mut window = size.max(WINDOW);
+        loop {
+            let mut chars = self.remaining().chars();
+            self.buffer.clear();
+            self.buffer.extend(chars.by_ref().take(window));
+            let complete = chars.as_str().is_empty();
+            let mut scanner = Scanner::new(&self.buffer);
+            let result = f(&mut scanner);
+            let reached_end = scanner.furthest_position() == self.buffer.len()
+                || matches!(
+                    result,
+                    Err(ParseError::UnexpectedEndOfInput | ParseError::Incomplete { .. })
+                );
+            if !complete && reached_end {
+                window *= 2;
+                continue;
+            }
+            let consumed = scanner.current_position();
+            // the offsets of the scanner count the chars of the window
+            let position = self.position;
+            let buffer = &self.buffer;
+            let to_bytes = |offset: usize| {
+                position
+                    + buffer[..offset.min(buffer.len())]
+                        .iter()
+                        .map(|char| char.len_utf8())
+                        .sum::<usize>()
+            };
+            let result = result.map_err(|err| err.map_offset(&to_bytes));
+            self.position = to_bytes(consumed);
+            return result;
+        }
+    }
+}
+
+/// The number of chars decoded ahead of the cursor to run a recognition,
+/// doubled as long as it needs more of the text.
+const WINDOW: usize = 64;
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::matchers::{
+        match_char, match_literal, match_number, match_unicode_whitespace,
+    };
+    use crate::chars::scanner::CharScanner;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::matcher::{Match, MatchSize};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    /// A char matcher of unknown size.
+    struct Matcher(fn(&[char]) -> (bool, usize));
+
+    impl Match<char> for Matcher {
+        fn matcher(&self, data: &[char]) -> (bool, usize) {
+            (self.0)(data)
+        }
+    }
+
+    impl MatchSize for Matcher {
+        fn size(&self) -> usize {
+            0
+        }
+    }
+
+    /// A literal, ignoring ASCII case.
+    struct Literal(&'static str);
+
+    impl Match<char> for Literal {
+        fn matcher(&self, data: &[char]) -> (bool, usize) {
+            match_literal(self.0, data)
+        }
+    }
+
+    impl MatchSize for Literal {
+        fn size(&self) -> usize {
+            self.0.chars().count()
+        }
+    }
+
+    #[test]
+    fn test_char_scanner() {
+        let mut scanner = CharScanner::new("été 42");
+        assert_eq!(scanner.peek(), Some('é'));
+        scanner.bump_by(2);
+        assert_eq!(scanner.current_position(), 3);
+        assert_eq!(scanner.remaining(), "é 42");
+        scanner.bump_by(1);
+        assert_eq!(scanner.current_position(), 5);
+        scanner.jump_to(0);
+        assert_eq!(scanner.remaining(), "été 42");
+    }
+
+    #[test]
+    fn test_recognize() {
+        let mut scanner = CharScanner::new("Ça va");
+        assert_eq!(
+            scanner.recognize(Some('Ç')).expect("failed to parse"),
+            Some("Ç")
+        );
+        assert_eq!(scanner.recognize(Some('x')).expect("failed to parse"), None);
+        assert_eq!(
+            scanner.recognize(Literal("A VA")).expect("failed to parse"),
+            Some("a va")
+        );
+        assert!(matches!(
+            scanner.recognize(Some('a')),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+
+        // the window grows with the match
+        let digits = "٣".repeat(3) + &"7".repeat(200) + "é";
+        let mut scanner = CharScanner::new(&digits);
+        assert_eq!(
+            scanner
+                .recognize(Matcher(match_number))
+                .expect("failed to parse"),
+            None
+        );
+        scanner.bump_by(3);
+        assert_eq!(
+            scanner
+                .recognize(Matcher(match_number))
+                .expect("failed to parse"),
+            Some(&digits[6..206])
+        );
+        assert_eq!(scanner.remaining(), "é");
+    }
+
+    #[test]
+    fn test_same_as_char_slices() {
+        let text = "\u{3000}\u{a0} 変数 123 é";
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut char_scanner = CharScanner::new(text);
+        let mut scanner = Scanner::new(&chars);
+
+        for matcher in [match_unicode_whitespace, match_number, match_number] {
+            let expected = recognize(Matcher(matcher), &mut scanner)
+                .ok()
+                .map(|chars| chars.iter().collect::<String>());
+            let recognized = char_scanner
+                .recognize(Matcher(matcher))
+                .expect("failed to parse");
+            assert_eq!(recognized.map(str::to_string), expected);
+            let consumed = chars[..scanner.current_position()]
+                .iter()
+                .collect::<String>();
+            assert_eq!(char_scanner.current_position(), consumed.len());
+        }
+    }
+
+    /// A number preceded by whitespaces.
+    #[derive(Debug, PartialEq)]
+    struct Number(usize);
+
+    impl<'a> Visitor<'a, char> for Number {
+        fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+            while match_char('\u{3000}', scanner.remaining()).0 {
+                scanner.bump_by(1);
+            }
+            let offset = scanner.current_position();
+            match match_number(scanner.remaining()) {
+                (true, size) => {
+                    let digits = scanner.remaining()[..size].iter().collect::<String>();
+                    scanner.bump_by(size);
+                    Ok(Number(digits.parse()?))
+                }
+                (false, _) => Err(ParseError::Syntax {
+                    offset,
+                    reason: "expected a number",
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let text = "\u{3000}12\u{3000}été";
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut char_scanner = CharScanner::new(text);
+        let mut scanner = Scanner::new(&chars);
+
+        assert_eq!(
+            char_scanner.visit::<Number>().expect("failed to parse"),
+            Number::accept(&mut scanner).expect("failed to parse")
+        );
+        assert_eq!(char_scanner.current_position(), 5);
+
+        // the offsets are byte offsets, rather than char ones
+        assert!(matches!(
+            Number::accept(&mut scanner),
+            Err(ParseError::Syntax { offset: 4, .. })
+        ));
+        assert!(matches!(
+            char_scanner.visit::<Number>(),
+            Err(ParseError::Syntax { offset: 8, .. })
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+//! Dump the tree built by a grammar, with the span of its nodes.
+//!
+//! A node implements [DebugTree], describing itself and its children to a
+//! [TreeBuilder]. Leaves only need a `Debug` implementation and the
+//! [DebugLeaf] marker, while [Spanned] records the span of any visitor:
+//!
+//! ```text
+//! Addition [0..9]
+//!   Number(1) [0..1]
+//!   Number(2) [4..5]
+//! ```
+
+use crate::bytes::primitives::identifier::Identifier;
+use crate::bytes::primitives::number::Number;
+use crate::separated_list::SeparatedList;
+use crate::visitor::Spanned;
+use std::fmt::{Debug, Display, Write};
+use std::ops::Range;
+
+/// Describe a value as a tree.
+pub trait DebugTree {
+    /// Write the value and its children to the builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The builder of the dump.
+    fn debug_tree(&self, out: &mut TreeBuilder);
+
+    /// Dump the value as an indented tree.
+    fn to_debug_tree(&self) -> String {
+        let mut out = TreeBuilder::new();
+        self.debug_tree(&mut out);
+        out.finish()
+    }
+}
+
+/// Mark a value as a leaf of the tree, dumped with its `Debug` implementation.
+pub trait DebugLeaf: Debug {}
+
+impl<L: DebugLeaf> DebugTree for L {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        out.leaf(format_args!("{self:?}"));
+    }
+}
+
+impl<T: Debug> DebugLeaf for Number<T> {}
+impl DebugLeaf for Identifier<'_> {}
+
+/// Build an indented dump, one line per node.
+#[derive(Debug, Default)]
+pub struct TreeBuilder {
+    out: String,
+    depth: usize,
+    /// The span of the next node.
+    span: Option<Range<usize>>,
+}
+
+impl TreeBuilder {
+    /// Create a builder of an empty dump.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write a node without children.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The description of the node.
+    pub fn leaf(&mut self, label: impl Display) {
+        let indent = self.depth * 2;
+        let _ = write!(self.out, "{:indent$}{label}", "");
+        if let Some(span) = self.span.take() {
+            let _ = write!(self.out, " [{}..{}]", span.start, span.end);
+        }
+        self.out.push('\n');
+    }
+
+    /// Write a node and its children, indented below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The description of the node.
+    /// * `children` - Writes the children of the node.
+    pub fn node(&mut self, label: impl Display, children: impl FnOnce(&mut TreeBuilder)) {
+        self.leaf(label);
+        self.depth += 1;
+        children(self);
+        self.depth -= 1;
+    }
+
+    /// Attach a span to the next node written.
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The span of the node.
+    pub fn span(&mut self, span: Range<usize>) -> &mut Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Return the dump.
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+impl<V: DebugTree> DebugTree for Spanned<V> {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        out.span(self.span.clone());
+        self.value.debug_tree(out);
+    }
+}
+
+/// `Some` is transparent, `None` is a leaf.
+impl<V: DebugTree> DebugTree for Option<V> {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        match self {
+            Some(value) => value.debug_tree(out),
+            None => out.leaf("None"),
+        }
+    }
+}
+
+impl<V: DebugTree> DebugTree for Vec<V> {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        out.node(format_args!("[{}]", self.len()), |out| {
+            self.iter().for_each(|element| element.debug_tree(out))
+        });
+    }
+}
+
+impl<T, V: DebugTree, S> DebugTree for SeparatedList<T, V, S> {
+    fn debug_tree(&self, out: &mut TreeBuilder) {
+        out.node("SeparatedList", |out| {
+            self.data.iter().for_each(|element| element.debug_tree(out))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::debug_tree::{DebugTree, TreeBuilder};
+    use crate::errors::ParseResult;
+    use crate::scanner::Scanner;
+    use crate::separated_list::SeparatedList;
+    use crate::visitor::{Spanned, Visitor};
+
+    struct Comma;
+
+    impl Visitor<'_, u8> for Comma {
+        fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+            crate::recognizer::recognize(Token::Comma, scanner)?;
+            Ok(Comma)
+        }
+    }
+
+    #[test]
+    fn test_debug_tree() {
+        let mut scanner = Scanner::new(b"1,22,3");
+        let list = SeparatedList::<u8, Spanned<Number<usize>>, Comma>::accept(&mut scanner)
+            .expect("failed to parse");
+        assert_eq!(
+            list.to_debug_tree(),
+            "SeparatedList\n  Number(1) [0..1]\n  Number(22) [2..4]\n  Number(3) [5..6]\n"
+        );
+
+        let values = vec![Some(Number(1)), None];
+        assert_eq!(values.to_debug_tree(), "[2]\n  Number(1)\n  None\n");
+    }
+
+    #[test]
+    fn test_tree_builder() {
+        let mut out = TreeBuilder::new();
+        out.span(0..3).node("Root", |out| {
+            out.node("Child", |out| out.span(1..2).leaf("Leaf"));
+            out.leaf("Other");
+        });
+        assert_eq!(
+            out.finish(),
+            "Root [0..3]\n  Child\n    Leaf [1..2]\n  Other\n"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Describe what a recognizer expects, without running it.
+//!
+//! A recognizer implementing [Describe] returns an [Expectation], the tree of
+//! what it can match. The combinators build their tree from their parts, so
+//! that the elements which may come first, [Expectation::first], are known
+//! for any recognizer: to offer completions or to report errors.
+//!
+//! [expect] recognizes an object and reports its expectation when it fails,
+//! see [ParseError::expectation_at_failure].
+
+use crate::bytes::primitives::identifier::TokenIdentifier;
+use crate::bytes::primitives::number::TokenNumber;
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::recognizer::{Recognizable, recognize};
+use crate::scanner::Scanner;
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+
+/// What a recognizer can match.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expectation {
+    /// Exactly these characters
+    Literal(Cow<'static, str>),
+    /// A class of characters, a number or an identifier for example
+    CharClass(&'static str),
+    /// Any of the alternatives
+    OneOf(Vec<Expectation>),
+    /// The elements in a row
+    Sequence(Vec<Expectation>),
+    /// A rule of a grammar
+    Named(&'static str),
+}
+
+impl Expectation {
+    /// Return the literals, classes and rules which may come first, without
+    /// duplicates.
+    pub fn first(&self) -> Vec<&Expectation> {
+        let mut first = vec![];
+        self.collect_first(&mut first);
+        first
+    }
+
+    fn collect_first<'e>(&'e self, first: &mut Vec<&'e Expectation>) {
+        match self {
+            Expectation::OneOf(alternatives) => alternatives
+                .iter()
+                .for_each(|alternative| alternative.collect_first(first)),
+            Expectation::Sequence(elements) => {
+                if let Some(element) = elements.first() {
+                    element.collect_first(first)
+                }
+            }
+            leaf if !first.contains(&leaf) => first.push(leaf),
+            _ => {}
+        }
+    }
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expectation::Literal(literal) => write!(f, "{literal:?}"),
+            Expectation::CharClass(name) | Expectation::Named(name) => f.write_str(name),
+            Expectation::OneOf(_) => {
+                let first = self.first();
+                let first = first.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                write!(f, "one of {}", first.join(", "))
+            }
+            Expectation::Sequence(elements) => {
+                let elements = elements.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                f.write_str(&elements.join(" "))
+            }
+        }
+    }
+}
+
+/// Describe what a recognizer can match.
+pub trait Describe {
+    /// Return the expectation of the recognizer.
+    fn expected(&self) -> Expectation;
+}
+
+/// Alternatives are flattened into a single `OneOf`.
+pub(crate) fn one_of(first: Expectation, second: Expectation) -> Expectation {
+    let mut alternatives = vec![];
+    for expectation in [first, second] {
+        match expectation {
+            Expectation::OneOf(inner) => alternatives.extend(inner),
+            expectation => alternatives.push(expectation),
+        }
+    }
+    Expectation::OneOf(alternatives)
+}
+
+impl<A: Describe, B: Describe> Describe for (A, B) {
+    fn expected(&self) -> Expectation {
+        Expectation::Sequence(vec![self.0.expected(), self.1.expected()])
+    }
+}
+
+impl<A: Describe, B: Describe, C: Describe> Describe for (A, B, C) {
+    fn expected(&self) -> Expectation {
+        Expectation::Sequence(vec![
+            self.0.expected(),
+            self.1.expected(),
+            self.2.expected(),
+        ])
+    }
+}
+
+impl Describe for Token {
+    fn expected(&self) -> Expectation {
+        let literal = match self {
+            Token::OpenParen => "(",
+            Token::CloseParen => ")",
+            Token::OpenBracket => "[",
+            Token::CloseBracket => "]",
+            Token::Comma => ",",
+            Token::Semicolon => ";",
+            Token::Colon => ":",
+            Token::Whitespace => " ",
+            Token::GreaterThan => ">",
+            Token::LessThan => "<",
+            Token::Exclamation => "!",
+            Token::Quote => "'",
+            Token::DoubleQuote => "\"",
+            Token::Equal => "=",
+            Token::Plus => "+",
+            Token::Dash => "-",
+            Token::Slash => "/",
+            Token::Star => "*",
+            Token::Percent => "%",
+            Token::Ampersand => "&",
+            Token::Pipe => "|",
+            Token::Caret => "^",
+            Token::Tilde => "~",
+            Token::Dot => ".",
+            Token::Question => "?",
+            Token::At => "@",
+            Token::Hash | Token::Sharp => "#",
+            Token::Dollar => "$",
+            Token::Backslash => "\\",
+            Token::Underscore => "_",
+            Token::Ln => "\n",
+            Token::Cr => "\r",
+            Token::Tab => "\t",
+            Token::CrLn => "\r\n",
+        };
+        Expectation::Literal(Cow::Borrowed(literal))
+    }
+}
+
+impl Describe for TokenNumber {
+    fn expected(&self) -> Expectation {
+        Expectation::CharClass("number")
+    }
+}
+
+impl Describe for TokenIdentifier {
+    fn expected(&self) -> Expectation {
+        Expectation::CharClass("identifier")
+    }
+}
+
+/// Recognize an object, reporting what it expects when it doesn't match.
+///
+/// # Arguments
+/// * `recognizable` - The object to recognize
+/// * `scanner` - The scanner to recognize the object for
+///
+/// # Returns
+/// * `Ok(V)` if the object was recognized,
+/// * `Err(ParseError::ExpectedStructured)` holding the expectation of the
+///   object if it doesn't match, at the end of the input included,
+/// * the other errors of [recognize].
+pub fn expect<'a, T, V, R>(recognizable: R, scanner: &mut Scanner<'a, T>) -> ParseResult<V>
+where
+    R: Recognizable<'a, T, V> + Describe,
+{
+    let offset = scanner.current_position();
+    let expected = recognizable.expected();
+    match recognize(recognizable, scanner) {
+        Err(ParseError::UnexpectedToken | ParseError::UnexpectedEndOfInput) => {
+            Err(ParseError::ExpectedStructured { offset, expected })
+        }
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::identifier::TokenIdentifier;
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::bytes::token::Token;
+    use crate::describe::{Describe, Expectation, expect};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::Recognizable;
+    use crate::scanner::Scanner;
+
+    crate::tokens! {
+        Keyword:
+            Let => keyword "let",
+    }
+
+    /// `let identifier = (number | "(" | identifier)`
+    fn assignment(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+        scanner.rollback_on_err(|scanner| {
+            expect(Keyword::Let, scanner)?;
+            expect(Token::Whitespace, scanner)?;
+            expect(TokenIdentifier, scanner)?;
+            expect(Token::Equal, scanner)?;
+            expect(
+                TokenNumber.or(Token::OpenParen).or(TokenIdentifier),
+                scanner,
+            )?;
+            Ok(())
+        })
+    }
+
+    fn failure(data: &[u8]) -> (Option<usize>, Expectation) {
+        let err = assignment(&mut Scanner::new(data)).expect_err("should fail");
+        let expectation = err.expectation_at_failure().expect("no expectation");
+        (err.offset(), expectation)
+    }
+
+    #[test]
+    fn test_expectation_at_failure() {
+        assert!(assignment(&mut Scanner::new(b"let x=1")).is_ok());
+
+        let (offset, expectation) = failure(b"var x=1");
+        assert_eq!(offset, Some(0));
+        assert_eq!(expectation.first(), [&Expectation::Literal("let".into())]);
+
+        let (offset, expectation) = failure(b"let x=;");
+        assert_eq!(offset, Some(6));
+        assert_eq!(
+            expectation.first(),
+            [
+                &Expectation::CharClass("number"),
+                &Expectation::Literal("(".into()),
+                &Expectation::CharClass("identifier"),
+            ]
+        );
+        assert_eq!(expectation.to_string(), "one of number, \"(\", identifier");
+
+        // the end of input expects the same
+        let (offset, expectation) = failure(b"let x=");
+        assert_eq!(offset, Some(6));
+        assert_eq!(expectation.first().len(), 3);
+    }
+
+    #[test]
+    fn test_expectation() {
+        let sequence = (Token::OpenParen.or(Token::OpenBracket), TokenNumber).expected();
+        assert_eq!(
+            sequence.first(),
+            [
+                &Expectation::Literal("(".into()),
+                &Expectation::Literal("[".into())
+            ]
+        );
+        assert_eq!(sequence.to_string(), "one of \"(\", \"[\" number");
+
+        // duplicates are removed, mapping keeps the expectation
+        let twice = Token::Plus
+            .or(Token::Plus.map(|_: &[u8]| &b"+"[..]))
+            .expected();
+        assert_eq!(twice.first(), [&Expectation::Literal("+".into())]);
+
+        // the names of the `alt!` branches are rules
+        let mut scanner = Scanner::new(b"x");
+        let err =
+            crate::alt!(&mut scanner => = TokenNumber, = Token::Plus).expect_err("should fail");
+        assert_eq!(
+            err.expectation_at_failure()
+                .expect("no expectation")
+                .first(),
+            [
+                &Expectation::Named("TokenNumber"),
+                &Expectation::Named("Token::Plus")
+            ]
+        );
+        assert!(
+            ParseError::UnexpectedToken
+                .expectation_at_failure()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Select a parser from a discriminant peeked at the cursor.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use std::fmt::Debug;
+
+/// A parser selected by a discriminant.
+pub type Branch<'a, T, V> = fn(&mut Scanner<'a, T>) -> ParseResult<V>;
+
+/// A type that runs only the branch whose discriminant stands at the cursor.
+///
+/// Each branch is associated with a discriminant, an element or a prefix,
+/// compared with the data at the cursor without consuming it. The first
+/// matching branch is run, the following ones are skipped.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to scan.
+/// * `V` - The type of the value returned by the branches.
+/// * `'a` - The lifetime of the data to scan.
+/// * `'b` - The lifetime of the dispatcher.
+#[derive(Debug)]
+pub struct Dispatch<'a, 'b, T, V> {
+    /// The scanner to use when consuming input.
+    scanner: &'b mut Scanner<'a, T>,
+    /// The result of the selected branch, if any.
+    result: Option<ParseResult<V>>,
+    /// Whether the discriminant is consumed before running the branch.
+    consume: bool,
+    /// The discriminants of the branches tried so far, laid end to end, for
+    /// the unknown discriminant error.
+    discriminants: Vec<T>,
+    /// The end of each discriminant in `discriminants`.
+    ends: Vec<usize>,
+}
+
+impl<'a, 'b, T, V> Dispatch<'a, 'b, T, V> {
+    /// Create a new dispatcher, the discriminant being left for the branch
+    /// to parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to use when consuming input.
+    pub fn new(scanner: &'b mut Scanner<'a, T>) -> Dispatch<'a, 'b, T, V> {
+        Dispatch {
+            scanner,
+            result: None,
+            consume: false,
+            discriminants: vec![],
+            ends: vec![],
+        }
+    }
+
+    /// Set whether the discriminant is consumed before the branch runs.
+    pub fn consume_discriminant(mut self, consume: bool) -> Self {
+        self.consume = consume;
+        self
+    }
+}
+
+impl<'a, T: PartialEq + Clone + Debug, V> Dispatch<'a, '_, T, V> {
+    /// Add a branch selected by the element at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `discriminant` - The element selecting the branch.
+    /// * `branch` - The parser to run if the branch is selected.
+    pub fn on<F>(self, discriminant: T, branch: F) -> Self
+    where
+        F: FnOnce(&mut Scanner<'a, T>) -> ParseResult<V>,
+    {
+        self.on_prefix(&[discriminant], branch)
+    }
+
+    /// Add a branch selected by a prefix of the data at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `discriminant` - The prefix selecting the branch, not empty.
+    /// * `branch` - The parser to run if the branch is selected.
+    pub fn on_prefix<F>(mut self, discriminant: &[T], branch: F) -> Self
+    where
+        F: FnOnce(&mut Scanner<'a, T>) -> ParseResult<V>,
+    {
+        if self.result.is_some() {
+            return self;
+        }
+        if !self.scanner.remaining().starts_with(discriminant) {
+            // the discriminants are only described if no branch is selected
+            self.discriminants.extend_from_slice(discriminant);
+            self.ends.push(self.discriminants.len());
+            return self;
+        }
+
+        let cursor = self.scanner.current_position();
+        if self.consume {
+            self.scanner.bump_by(discriminant.len());
+        }
+        let result = match branch(self.scanner) {
+            Err(err) => self.scanner.rewind_to(cursor).and(Err(err)),
+            result => result,
+        };
+        self.result = Some(result);
+        self
+    }
+
+    /// Add the branches of a table, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The discriminants and the parsers they select.
+    pub fn table(self, table: &[(T, Branch<'a, T, V>)]) -> Self {
+        table.iter().fold(self, |dispatch, (discriminant, branch)| {
+            dispatch.on_prefix(std::slice::from_ref(discriminant), branch)
+        })
+    }
+
+    /// Consume the dispatcher and return the result of the selected branch.
+    ///
+    /// # Errors
+    ///
+    /// The error of the selected branch, `ParseError::UnexpectedEndOfInput`
+    /// if the scanner is empty, `ParseError::UnknownDiscriminant` if no
+    /// branch is selected.
+    pub fn finish(self) -> ParseResult<V> {
+        if let Some(result) = self.result {
+            return result;
+        }
+        match self.scanner.remaining().first() {
+            None => Err(ParseError::UnexpectedEndOfInput),
+            Some(found) => {
+                let starts = std::iter::once(0).chain(self.ends.iter().copied());
+                let known = starts
+                    .zip(&self.ends)
+                    .map(|(start, &end)| describe(&self.discriminants[start..end]))
+                    .collect();
+                Err(ParseError::UnknownDiscriminant {
+                    offset: self.scanner.current_position(),
+                    found: format!("{found:?}"),
+                    known,
+                })
+            }
+        }
+    }
+}
+
+/// Describe a discriminant, an element or a prefix, for errors.
+fn describe<T: Debug>(discriminant: &[T]) -> String {
+    match discriminant {
+        [element] => format!("{element:?}"),
+        prefix => format!("{prefix:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dispatch::{Branch, Dispatch};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::scanner::Scanner;
+
+    fn parse(scanner: &mut Scanner<u8>, consume: bool) -> ParseResult<&'static str> {
+        Dispatch::new(scanner)
+            .consume_discriminant(consume)
+            .on(b'a', |scanner| {
+                scanner.bump_by(1);
+                Ok("a")
+            })
+            .on_prefix(b"bc", |_| Ok("bc"))
+            .on(b'b', |_| Ok("b"))
+            .on(b'e', |_| Err(ParseError::UnexpectedToken))
+            .on(b'f', |_| Ok("f"))
+            .finish()
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut scanner = Scanner::new(b"abd");
+        assert_eq!(parse(&mut scanner, false).expect("failed to parse"), "a");
+        assert_eq!(scanner.current_position(), 1);
+        assert_eq!(parse(&mut scanner, true).expect("failed to parse"), "b");
+        assert_eq!(scanner.current_position(), 2);
+
+        let mut scanner = Scanner::new(b"bcd");
+        assert_eq!(parse(&mut scanner, true).expect("failed to parse"), "bc");
+        assert_eq!(scanner.current_position(), 2);
+
+        // a failing branch leaves the scanner untouched
+        let mut scanner = Scanner::new(b"e");
+        assert!(matches!(
+            parse(&mut scanner, true),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_table() {
+        let table: &[(u8, Branch<u8, u8>)] = &[(b'0', |_| Ok(0)), (b'1', |_| Ok(1))];
+        let mut scanner = Scanner::new(b"10");
+        let value = Dispatch::new(&mut scanner)
+            .consume_discriminant(true)
+            .table(table)
+            .finish();
+        assert_eq!(value.expect("failed to parse"), 1);
+        assert_eq!(scanner.current_position(), 1);
+    }
+
+    #[test]
+    fn test_unknown_discriminant() {
+        let mut scanner = Scanner::new(b"xd");
+        scanner.bump_by(1);
+        let err = parse(&mut scanner, true).expect_err("should fail");
+        assert_eq!(
+            err.to_string(),
+            "Unknown discriminant 100 at offset 1, expected one of 97, [98, 99], 98, 101, 102"
+        );
+        assert_eq!(scanner.current_position(), 1);
+
+        let mut scanner = Scanner::new(b"");
+        assert!(matches!(
+            parse(&mut scanner, true),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+}
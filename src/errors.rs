@@ -1,4 +1,9 @@
 //! Error types
+
+use crate::describe::Expectation;
+use crate::matcher::Needed;
+use std::ops::Range;
+
 /// The result of a parse operation
 pub type ParseResult<T> = Result<T, ParseError>;
 
@@ -12,4 +17,421 @@ pub enum ParseError {
     Utf8Error(#[from] std::str::Utf8Error),
     #[error("ParseIntError: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
+    #[error("{reason} at offset {offset}")]
+    Syntax {
+        /// Byte offset of the error
+        offset: usize,
+        /// What went wrong
+        reason: &'static str,
+    },
+    #[error("Malformed CSV record {record}, field {field} at offset {offset}: {reason}")]
+    MalformedCsv {
+        /// Index of the record, starting at 0
+        record: usize,
+        /// Index of the field inside the record, starting at 0
+        field: usize,
+        /// Byte offset of the faulty quote
+        offset: usize,
+        /// What went wrong
+        reason: &'static str,
+    },
+    #[error("Expected one of {} at offset {offset}", expected.join(", "))]
+    Expected {
+        /// Byte offset of the furthest failure
+        offset: usize,
+        /// Names of the elements which failed at this offset
+        expected: Vec<&'static str>,
+    },
+    #[error("Expected {expected} at offset {offset}")]
+    ExpectedStructured {
+        /// Byte offset of the failing recognizer
+        offset: usize,
+        /// What the recognizer expected
+        expected: Expectation,
+    },
+    #[error("Unknown discriminant {found} at offset {offset}, expected one of {}", known.join(", "))]
+    UnknownDiscriminant {
+        /// Byte offset of the discriminant
+        offset: usize,
+        /// The discriminant found
+        found: String,
+        /// The discriminants having a branch
+        known: Vec<String>,
+    },
+    #[error("Repetition made no progress at offset {offset}")]
+    NoProgress {
+        /// Byte offset where an element matched without consuming anything
+        offset: usize,
+    },
+    #[error("Incomplete input, {needed} needed")]
+    Incomplete {
+        /// How much more data is needed
+        needed: Needed,
+    },
+    #[error("Number out of range at {}..{}", span.start, span.end)]
+    OutOfRange {
+        /// Byte range of the number
+        span: Range<usize>,
+    },
+    #[error("Parse budget exhausted at offset {consumed_offset}")]
+    BudgetExhausted {
+        /// Byte offset reached when the fuel ran out
+        consumed_offset: usize,
+    },
+    #[error("Backtrack limit exceeded at offset {offset}")]
+    BacktrackLimitExceeded {
+        /// Byte offset the parse would have backtracked to
+        offset: usize,
+    },
+    #[error("Element {index} at offset {offset}: {source}")]
+    Element {
+        /// Index of the failing element, starting at 0
+        index: usize,
+        /// Byte offset where the element started
+        offset: usize,
+        /// The error of the element
+        source: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    /// Return the offset of the error if it is known.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParseError::Syntax { offset, .. }
+            | ParseError::MalformedCsv { offset, .. }
+            | ParseError::Expected { offset, .. }
+            | ParseError::ExpectedStructured { offset, .. }
+            | ParseError::UnknownDiscriminant { offset, .. }
+            | ParseError::NoProgress { offset }
+            | ParseError::BudgetExhausted {
+                consumed_offset: offset,
+            }
+            | ParseError::BacktrackLimitExceeded { offset }
+            | ParseError::Element { offset, .. } => Some(*offset),
+            ParseError::OutOfRange { span } => Some(span.start),
+            _ => None,
+        }
+    }
+
+    /// Whether the parse can't go on from another alternative after the
+    /// error: more data is needed, the fuel ran out or the backtrack limit
+    /// has been exceeded, within an element of a sequence too.
+    pub fn is_unrecoverable(&self) -> bool {
+        match self {
+            ParseError::Incomplete { .. }
+            | ParseError::BudgetExhausted { .. }
+            | ParseError::BacktrackLimitExceeded { .. } => true,
+            ParseError::Element { source, .. } => source.is_unrecoverable(),
+            _ => false,
+        }
+    }
+
+    /// Move the offsets held by the error, to report them relatively to
+    /// other data than the one of the failing scanner.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Maps an offset of the error to the reported one.
+    pub(crate) fn map_offset(self, f: &impl Fn(usize) -> usize) -> ParseError {
+        match self {
+            ParseError::Syntax { offset, reason } => ParseError::Syntax {
+                offset: f(offset),
+                reason,
+            },
+            ParseError::MalformedCsv {
+                record,
+                field,
+                offset,
+                reason,
+            } => ParseError::MalformedCsv {
+                record,
+                field,
+                offset: f(offset),
+                reason,
+            },
+            ParseError::Expected { offset, expected } => ParseError::Expected {
+                offset: f(offset),
+                expected,
+            },
+            ParseError::ExpectedStructured { offset, expected } => ParseError::ExpectedStructured {
+                offset: f(offset),
+                expected,
+            },
+            ParseError::UnknownDiscriminant {
+                offset,
+                found,
+                known,
+            } => ParseError::UnknownDiscriminant {
+                offset: f(offset),
+                found,
+                known,
+            },
+            ParseError::NoProgress { offset } => ParseError::NoProgress { offset: f(offset) },
+            ParseError::OutOfRange { span } => ParseError::OutOfRange {
+                span: f(span.start)..f(span.end),
+            },
+            ParseError::BudgetExhausted { consumed_offset } => ParseError::BudgetExhausted {
+                consumed_offset: f(consumed_offset),
+            },
+            ParseError::BacktrackLimitExceeded { offset } => {
+                ParseError::BacktrackLimitExceeded { offset: f(offset) }
+            }
+            ParseError::Element {
+                index,
+                offset,
+                source,
+            } => ParseError::Element {
+                index,
+                offset: f(offset),
+                source: Box::new(source.map_offset(f)),
+            },
+            err => err,
+        }
+    }
+
+    /// Return what was expected where the parse failed, to offer
+    /// completions at this offset for example.
+    ///
+    /// # Returns
+    ///
+    /// The expectation of an error of [crate::describe::expect], the names
+    /// of the elements of a `ParseError::Expected` as rules, `None` for the
+    /// other errors.
+    pub fn expectation_at_failure(&self) -> Option<Expectation> {
+        match self {
+            ParseError::ExpectedStructured { expected, .. } => Some(expected.clone()),
+            ParseError::Expected { expected, .. } => Some(Expectation::OneOf(
+                expected.iter().copied().map(Expectation::Named).collect(),
+            )),
+            ParseError::Element { source, .. } => source.expectation_at_failure(),
+            _ => None,
+        }
+    }
+
+    /// Describe the error for an editor.
+    ///
+    /// An error holding an offset covers the character at this offset, the
+    /// end of input errors cover the empty range at the end of the input and
+    /// the other ones the whole input.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The data the error comes from.
+    pub fn to_diagnostic(&self, input: &[u8]) -> Diagnostic {
+        let (start, end) = match self {
+            ParseError::UnexpectedEndOfInput | ParseError::Incomplete { .. } => {
+                (input.len(), input.len())
+            }
+            _ => match self.offset() {
+                Some(offset) => {
+                    let start = offset.min(input.len());
+                    (start, start + char_len(&input[start..]))
+                }
+                None => (0, input.len()),
+            },
+        };
+        Diagnostic {
+            start,
+            end,
+            message: self.to_string(),
+            severity: Severity::Error,
+            range: position(input, start)..position(input, end),
+        }
+    }
+
+    /// Give a position to an unexpected token error.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset where the failing element started.
+    /// * `name` - The name of the failing element.
+    ///
+    /// # Returns
+    ///
+    /// A `ParseError::Expected` for an unexpected token, the error itself
+    /// otherwise.
+    pub fn positioned(self, offset: usize, name: &'static str) -> ParseError {
+        match self {
+            ParseError::UnexpectedToken => ParseError::Expected {
+                offset,
+                expected: vec![name],
+            },
+            err => err,
+        }
+    }
+}
+
+/// The severity of a [Diagnostic], as defined by the Language Server
+/// Protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The input can't be parsed
+    Error,
+    /// The input is parsed, but likely not as intended
+    Warning,
+    /// A remark on the input
+    Information,
+    /// A suggestion, to rewrite the input for example
+    Hint,
+}
+
+/// A position in the input, as a `Position` of the Language Server Protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineColumn {
+    /// The line, starting at 0
+    pub line: usize,
+    /// The offset in the line in UTF-16 code units, starting at 0
+    pub column: usize,
+}
+
+/// An error located in the input, see [ParseError::to_diagnostic].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Byte offset of the start of the range
+    pub start: usize,
+    /// Byte offset of the end of the range, excluded
+    pub end: usize,
+    /// The description of the error
+    pub message: String,
+    /// How serious the error is, [Severity::Error] for a `ParseError`
+    pub severity: Severity,
+    /// The range as lines and columns
+    pub range: Range<LineColumn>,
+}
+
+/// Return the length of the char starting the data, 1 for an invalid UTF-8
+/// sequence, 0 at the end of the data.
+fn char_len(data: &[u8]) -> usize {
+    match data[..data.len().min(4)].utf8_chunks().next() {
+        Some(chunk) => chunk.valid().chars().next().map_or(1, char::len_utf8),
+        None => 0,
+    }
+}
+
+/// Return the line and column of a byte offset.
+fn position(input: &[u8], offset: usize) -> LineColumn {
+    let before = &input[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    LineColumn {
+        line: before.iter().filter(|byte| **byte == b'\n').count(),
+        column: String::from_utf8_lossy(&before[line_start..])
+            .encode_utf16()
+            .count(),
+    }
+}
+
+/// Collect the failures of alternatives, only the furthest ones are kept.
+#[derive(Debug)]
+pub struct FurthestFailure {
+    offset: usize,
+    expected: Vec<&'static str>,
+}
+
+impl FurthestFailure {
+    /// Create a collector for alternatives starting at `offset`.
+    pub fn new(offset: usize) -> Self {
+        FurthestFailure {
+            offset,
+            expected: vec![],
+        }
+    }
+
+    /// Record the failure of an alternative.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error of the alternative.
+    /// * `start` - The position where the alternative started, used when the
+    ///   error doesn't hold an offset.
+    /// * `name` - The name of the alternative.
+    pub fn record(&mut self, error: &ParseError, start: usize, name: &'static str) {
+        let offset = error.offset().unwrap_or(start);
+        if offset > self.offset {
+            self.offset = offset;
+            self.expected.clear();
+        }
+        if offset == self.offset {
+            self.expected.push(name);
+        }
+    }
+
+    /// Build the error listing the alternatives failing the furthest.
+    pub fn into_error(self) -> ParseError {
+        ParseError::Expected {
+            offset: self.offset,
+            expected: self.expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{FurthestFailure, LineColumn, ParseError, Severity};
+
+    #[test]
+    fn test_furthest_failure() {
+        let mut failures = FurthestFailure::new(2);
+        failures.record(&ParseError::UnexpectedToken, 2, "a");
+        let syntax = ParseError::Syntax {
+            offset: 5,
+            reason: "bad",
+        };
+        failures.record(&syntax, 2, "b");
+        failures.record(&ParseError::UnexpectedEndOfInput, 5, "c");
+        failures.record(&ParseError::UnexpectedToken, 3, "d");
+        assert!(matches!(
+            failures.into_error(),
+            ParseError::Expected { offset: 5, expected } if expected == ["b", "c"]
+        ));
+    }
+
+    #[test]
+    fn test_is_unrecoverable() {
+        assert!(ParseError::BacktrackLimitExceeded { offset: 0 }.is_unrecoverable());
+        assert!(ParseError::BudgetExhausted { consumed_offset: 0 }.is_unrecoverable());
+        assert!(!ParseError::UnexpectedToken.is_unrecoverable());
+        assert!(!ParseError::NoProgress { offset: 0 }.is_unrecoverable());
+
+        let element = |source| ParseError::Element {
+            index: 1,
+            offset: 2,
+            source: Box::new(source),
+        };
+        assert!(element(ParseError::BacktrackLimitExceeded { offset: 0 }).is_unrecoverable());
+        assert!(!element(ParseError::UnexpectedToken).is_unrecoverable());
+    }
+
+    #[test]
+    fn test_to_diagnostic() {
+        let input = "a = 1\nb = é;".as_bytes();
+        let err = ParseError::UnexpectedToken.positioned(10, "Number");
+        let diagnostic = err.to_diagnostic(input);
+        assert_eq!((diagnostic.start, diagnostic.end), (10, 12));
+        assert_eq!(diagnostic.message, "Expected one of Number at offset 10");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(
+            diagnostic.range,
+            LineColumn { line: 1, column: 4 }..LineColumn { line: 1, column: 5 }
+        );
+
+        let diagnostic = ParseError::UnexpectedToken
+            .positioned(2, "a")
+            .to_diagnostic(input);
+        assert_eq!((diagnostic.start, diagnostic.end), (2, 3));
+        assert_eq!(
+            diagnostic.range,
+            LineColumn { line: 0, column: 2 }..LineColumn { line: 0, column: 3 }
+        );
+
+        let diagnostic = ParseError::UnexpectedEndOfInput.to_diagnostic(input);
+        assert_eq!((diagnostic.start, diagnostic.end), (13, 13));
+        assert_eq!(diagnostic.range.start, LineColumn { line: 1, column: 6 });
+
+        let diagnostic = ParseError::UnexpectedToken.to_diagnostic(input);
+        assert_eq!((diagnostic.start, diagnostic.end), (0, 13));
+    }
 }
@@ -0,0 +1,389 @@
+//! CSV records as described by RFC 4180.
+//!
+//! Fields are separated by a delimiter byte (`,` by default, `\t` for TSV),
+//! records are terminated by either `\n` or `\r\n`. A field may be enclosed in
+//! double quotes, in which case it can contain delimiters, line breaks and
+//! doubled quotes `""` standing for a single `"`.
+//!
+//! Fields are borrowed from the input whenever possible, only quoted fields
+//! containing escaped quotes need to be allocated.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// The default delimiter of a CSV field.
+pub const DEFAULT_DELIMITER: u8 = b',';
+
+const QUOTE: u8 = b'"';
+
+/// A single CSV record.
+#[derive(Debug, PartialEq)]
+pub struct CsvRecord<'a> {
+    /// The fields of the record.
+    pub fields: Vec<Cow<'a, [u8]>>,
+}
+
+impl<'a> CsvRecord<'a> {
+    /// Parse a record using a custom field delimiter.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to parse the record from.
+    /// * `delimiter` - The byte separating fields.
+    ///
+    /// # Returns
+    ///
+    /// The parsed record, the scanner is moved after the record terminator.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if the scanner is empty,
+    /// `ParseError::MalformedCsv` if a quoted field is malformed.
+    pub fn parse(scanner: &mut Scanner<'a, u8>, delimiter: u8) -> ParseResult<Self> {
+        parse_record(scanner, delimiter, 0)
+    }
+}
+
+impl<'a> Visitor<'a, u8> for CsvRecord<'a> {
+    /// Accept a comma separated record.
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        CsvRecord::parse(scanner, DEFAULT_DELIMITER)
+    }
+}
+
+/// A lazy iterator over the records of a scanner.
+///
+/// The iterator stops at the end of the input or after the first error.
+pub struct Records<'a, 'b> {
+    scanner: &'b mut Scanner<'a, u8>,
+    delimiter: u8,
+    record: usize,
+    done: bool,
+}
+
+impl<'a, 'b> Records<'a, 'b> {
+    /// Create a new iterator over the records of the scanner using the
+    /// default `,` delimiter.
+    pub fn new(scanner: &'b mut Scanner<'a, u8>) -> Self {
+        Records {
+            scanner,
+            delimiter: DEFAULT_DELIMITER,
+            record: 0,
+            done: false,
+        }
+    }
+
+    /// Use another field delimiter, `b'\t'` for TSV for example.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+impl<'a> Iterator for Records<'a, '_> {
+    type Item = ParseResult<CsvRecord<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.scanner.is_empty() {
+            return None;
+        }
+        let result = parse_record(self.scanner, self.delimiter, self.record);
+        self.record += 1;
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+/// How a field has been terminated.
+enum FieldEnd {
+    /// A delimiter follows, another field is expected.
+    Delimiter,
+    /// End of the record, either a line break or the end of input.
+    Record,
+}
+
+/// Parse a whole record, `record` is only used to report errors.
+fn parse_record<'a>(
+    scanner: &mut Scanner<'a, u8>,
+    delimiter: u8,
+    record: usize,
+) -> ParseResult<CsvRecord<'a>> {
+    if scanner.is_empty() {
+        return Err(ParseError::UnexpectedEndOfInput);
+    }
+
+    let cursor = scanner.current_position();
+    let mut fields = vec![];
+
+    loop {
+        let field = fields.len();
+        let result = match scanner.first() {
+            Some(&QUOTE) => parse_quoted_field(scanner, delimiter),
+            _ => parse_unquoted_field(scanner, delimiter),
+        };
+        match result {
+            Ok((value, end)) => {
+                fields.push(value);
+                if let FieldEnd::Record = end {
+                    break;
+                }
+            }
+            Err(FieldError::Limit(err)) => return scanner.rewind_to(cursor).and(Err(err)),
+            Err(FieldError::Malformed(offset, reason)) => {
+                scanner.rewind_to(cursor)?;
+                return Err(ParseError::MalformedCsv {
+                    record,
+                    field,
+                    offset,
+                    reason,
+                });
+            }
+        }
+    }
+
+    Ok(CsvRecord { fields })
+}
+
+/// The failure of a field parsing.
+enum FieldError {
+    /// The offset and reason of a malformed field.
+    Malformed(usize, &'static str),
+    /// The rewind after the failure exceeded the backtrack limit.
+    Limit(ParseError),
+}
+
+impl From<ParseError> for FieldError {
+    fn from(err: ParseError) -> Self {
+        FieldError::Limit(err)
+    }
+}
+
+/// Result of a field parsing.
+type FieldResult<'a> = Result<(Cow<'a, [u8]>, FieldEnd), FieldError>;
+
+/// Consume the bytes following a field, which must be either a delimiter,
+/// a line break or the end of input.
+fn consume_field_end(scanner: &mut Scanner<u8>, delimiter: u8) -> Option<FieldEnd> {
+    match scanner.remaining() {
+        [] => Some(FieldEnd::Record),
+        [b'\n', ..] => {
+            scanner.bump_by(1);
+            Some(FieldEnd::Record)
+        }
+        [b'\r', b'\n', ..] => {
+            scanner.bump_by(2);
+            Some(FieldEnd::Record)
+        }
+        [byte, ..] if *byte == delimiter => {
+            scanner.bump_by(1);
+            Some(FieldEnd::Delimiter)
+        }
+        _ => None,
+    }
+}
+
+fn parse_unquoted_field<'a>(scanner: &mut Scanner<'a, u8>, delimiter: u8) -> FieldResult<'a> {
+    let start = scanner.current_position();
+    let remaining = scanner.remaining();
+    let mut pos = 0;
+
+    while pos < remaining.len() {
+        match remaining[pos] {
+            QUOTE => {
+                return Err(FieldError::Malformed(
+                    start + pos,
+                    "quote inside an unquoted field",
+                ));
+            }
+            b'\n' => break,
+            b'\r' if remaining.get(pos + 1) == Some(&b'\n') => break,
+            byte if byte == delimiter => break,
+            _ => pos += 1,
+        }
+    }
+
+    let value = &scanner.data()[start..start + pos];
+    scanner.bump_by(pos);
+    // the loop stops only on a field end
+    let end = consume_field_end(scanner, delimiter).unwrap_or(FieldEnd::Record);
+    Ok((Cow::Borrowed(value), end))
+}
+
+fn parse_quoted_field<'a>(scanner: &mut Scanner<'a, u8>, delimiter: u8) -> FieldResult<'a> {
+    let start = scanner.current_position();
+    let data = scanner.data();
+    // skip the opening quote
+    let content_start = start + 1;
+    let mut pos = content_start;
+    let mut escaped = false;
+
+    let content_end = loop {
+        match data.get(pos) {
+            None => return Err(FieldError::Malformed(start, "unterminated quoted field")),
+            Some(&QUOTE) if data.get(pos + 1) == Some(&QUOTE) => {
+                escaped = true;
+                pos += 2;
+            }
+            Some(&QUOTE) => break pos,
+            Some(_) => pos += 1,
+        }
+    };
+
+    // move after the closing quote
+    scanner.jump_to(content_end + 1);
+    let end = match consume_field_end(scanner, delimiter) {
+        Some(end) => end,
+        None => {
+            scanner.rewind_to(start)?;
+            return Err(FieldError::Malformed(
+                content_end + 1,
+                "unexpected byte after a closing quote",
+            ));
+        }
+    };
+
+    let content = &data[content_start..content_end];
+    let value = if escaped {
+        Cow::Owned(unescape(content))
+    } else {
+        Cow::Borrowed(content)
+    };
+
+    Ok((value, end))
+}
+
+/// Replace doubled quotes by a single one.
+fn unescape(content: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(content.len());
+    let mut iter = content.iter().peekable();
+    while let Some(&byte) = iter.next() {
+        if byte == QUOTE && iter.peek() == Some(&&QUOTE) {
+            iter.next();
+        }
+        result.push(byte);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::csv::{CsvRecord, Records};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    fn fields<'a>(record: &'a CsvRecord) -> Vec<&'a [u8]> {
+        record.fields.iter().map(|field| field.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_simple_record() {
+        let data = b"a,b,c\nd,e,f";
+        let mut scanner = Scanner::new(data);
+        let record = scanner.visit::<CsvRecord>().expect("failed to parse");
+        assert_eq!(fields(&record), vec![b"a", b"b", b"c"]);
+        assert_eq!(scanner.current_position(), 6);
+        let record = scanner.visit::<CsvRecord>().expect("failed to parse");
+        assert_eq!(fields(&record), vec![b"d", b"e", b"f"]);
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        let data = b"\"a,b\",\"line\r\nbreak\",\"say \"\"hi\"\"\"\r\n";
+        let mut scanner = Scanner::new(data);
+        let record = scanner.visit::<CsvRecord>().expect("failed to parse");
+        assert!(matches!(record.fields[0], Cow::Borrowed(b"a,b")));
+        assert!(matches!(record.fields[1], Cow::Borrowed(b"line\r\nbreak")));
+        assert!(matches!(&record.fields[2], Cow::Owned(field) if field == b"say \"hi\""));
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_quote_at_end_of_field() {
+        let data = b"\"a\"\"\",b";
+        let mut scanner = Scanner::new(data);
+        let record = scanner.visit::<CsvRecord>().expect("failed to parse");
+        assert_eq!(fields(&record), vec![b"a\"".as_slice(), b"b"]);
+    }
+
+    #[test]
+    fn test_empty_fields() {
+        let data = b"a,,\n,\n";
+        let mut scanner = Scanner::new(data);
+        let records = Records::new(&mut scanner)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(records.len(), 2);
+        assert_eq!(fields(&records[0]), vec![b"a".as_slice(), b"", b""]);
+        assert_eq!(fields(&records[1]), vec![b"".as_slice(), b""]);
+    }
+
+    #[test]
+    fn test_tsv() {
+        let data = b"a\tb,c\r\n\"d\te\"\tf";
+        let mut scanner = Scanner::new(data);
+        let records = Records::new(&mut scanner)
+            .delimiter(b'\t')
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(fields(&records[0]), vec![b"a".as_slice(), b"b,c"]);
+        assert_eq!(fields(&records[1]), vec![b"d\te".as_slice(), b"f"]);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let data = b"a,b\nc,\"unterminated";
+        let mut scanner = Scanner::new(data);
+        let mut records = Records::new(&mut scanner);
+        assert!(records.next().expect("expected a record").is_ok());
+        assert!(matches!(
+            records.next(),
+            Some(Err(ParseError::MalformedCsv {
+                record: 1,
+                field: 1,
+                offset: 6,
+                ..
+            }))
+        ));
+        assert!(records.next().is_none());
+
+        let data = b"\"a\"b,c";
+        let mut scanner = Scanner::new(data);
+        let result = scanner.visit::<CsvRecord>();
+        assert!(matches!(
+            result,
+            Err(ParseError::MalformedCsv {
+                record: 0,
+                field: 0,
+                offset: 3,
+                ..
+            })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        // unless the rewind exceeds the backtrack limit
+        let mut scanner = Scanner::new(data);
+        scanner.set_backtrack_limit(2);
+        assert!(matches!(
+            scanner.visit::<CsvRecord>(),
+            Err(ParseError::BacktrackLimitExceeded { offset: 0 })
+        ));
+
+        let data = b"a,b\"c";
+        let mut scanner = Scanner::new(data);
+        let result = scanner.visit::<CsvRecord>();
+        assert!(matches!(
+            result,
+            Err(ParseError::MalformedCsv {
+                field: 1,
+                offset: 3,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+//! HTTP/1.1 request heads as described by RFC 7230.
+//!
+//! A request head is a request line followed by header fields and an empty
+//! line:
+//!
+//! ```text
+//! GET /index.html HTTP/1.1\r\n
+//! Host: example.com\r\n
+//! \r\n
+//! ```
+//!
+//! Every component borrows from the input.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Parsing options.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HttpOptions {
+    /// Accept a bare `\n` as line ending in addition to `\r\n`.
+    pub lenient_line_endings: bool,
+}
+
+/// The request line `GET /path HTTP/1.1`.
+#[derive(Debug, PartialEq)]
+pub struct RequestLine<'a> {
+    /// The method, `GET` for example.
+    pub method: &'a str,
+    /// The request target, `/path` for example.
+    pub target: &'a str,
+    /// The protocol version, `HTTP/1.1` for example.
+    pub version: &'a str,
+}
+
+/// A header field `Name: value`.
+#[derive(Debug, PartialEq)]
+pub struct Header<'a> {
+    /// The field name.
+    pub name: &'a str,
+    /// The field value without surrounding whitespaces.
+    pub value: &'a str,
+}
+
+/// A request line and its header fields.
+#[derive(Debug, PartialEq)]
+pub struct RequestHead<'a> {
+    /// The request line.
+    pub request_line: RequestLine<'a>,
+    /// The header fields in order of appearance.
+    pub headers: Vec<Header<'a>>,
+}
+
+impl<'a> RequestHead<'a> {
+    /// Get the value of the first header with the given name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case(name))
+            .map(|header| header.value)
+    }
+}
+
+/// Token characters allowed in methods and header names.
+fn is_tchar(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// Optional whitespace.
+fn is_ows(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+fn syntax_error(scanner: &Scanner<u8>, reason: &'static str) -> ParseError {
+    ParseError::Syntax {
+        offset: scanner.current_position(),
+        reason,
+    }
+}
+
+/// Consume bytes while the predicate holds.
+fn take_while<'a>(scanner: &mut Scanner<'a, u8>, predicate: impl Fn(u8) -> bool) -> &'a [u8] {
+    let start = scanner.current_position();
+    let length = scanner.iter().take_while(|byte| predicate(**byte)).count();
+    scanner.bump_by(length);
+    &scanner.data()[start..start + length]
+}
+
+/// Consume a line ending.
+fn line_ending(scanner: &mut Scanner<u8>, options: HttpOptions) -> ParseResult<()> {
+    match scanner.remaining() {
+        [b'\r', b'\n', ..] => scanner.bump_by(2),
+        [b'\n', ..] if options.lenient_line_endings => scanner.bump_by(1),
+        [] | [b'\r'] => return Err(ParseError::UnexpectedEndOfInput),
+        _ => return Err(syntax_error(scanner, "expected end of line")),
+    }
+    Ok(())
+}
+
+fn single_space(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    match scanner.first() {
+        Some(b' ') => {
+            scanner.bump_by(1);
+            Ok(())
+        }
+        None => Err(ParseError::UnexpectedEndOfInput),
+        Some(_) => Err(syntax_error(scanner, "expected a single space")),
+    }
+}
+
+fn is_line_ending(data: &[u8], options: HttpOptions) -> bool {
+    data.starts_with(b"\r\n") || (options.lenient_line_endings && data.starts_with(b"\n"))
+}
+
+impl<'a> RequestLine<'a> {
+    /// Parse a request line and its line ending.
+    pub fn parse(scanner: &mut Scanner<'a, u8>, options: HttpOptions) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            let method = take_while(scanner, is_tchar);
+            if method.is_empty() {
+                return Err(syntax_error(scanner, "invalid method"));
+            }
+            single_space(scanner)?;
+
+            let target = take_while(scanner, |byte| byte.is_ascii_graphic());
+            if target.is_empty() {
+                return Err(syntax_error(scanner, "invalid request target"));
+            }
+            single_space(scanner)?;
+
+            let version = take_while(scanner, |byte| byte.is_ascii_graphic());
+            match version {
+                [b'H', b'T', b'T', b'P', b'/', major, b'.', minor]
+                    if major.is_ascii_digit() && minor.is_ascii_digit() => {}
+                _ => {
+                    return Err(ParseError::Syntax {
+                        offset: scanner.current_position() - version.len(),
+                        reason: "invalid HTTP version",
+                    });
+                }
+            }
+            line_ending(scanner, options)?;
+
+            Ok(RequestLine {
+                method: std::str::from_utf8(method)?,
+                target: std::str::from_utf8(target)?,
+                version: std::str::from_utf8(version)?,
+            })
+        })
+    }
+}
+
+impl<'a> Header<'a> {
+    /// Parse a header field and its line ending.
+    pub fn parse(scanner: &mut Scanner<'a, u8>, options: HttpOptions) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            if scanner.first().is_some_and(|byte| is_ows(*byte)) {
+                return Err(syntax_error(scanner, "obsolete line folding"));
+            }
+            let name = take_while(scanner, is_tchar);
+            match scanner.first() {
+                None => return Err(ParseError::UnexpectedEndOfInput),
+                Some(b':') if !name.is_empty() => scanner.bump_by(1),
+                Some(_) => return Err(syntax_error(scanner, "invalid header name")),
+            }
+
+            take_while(scanner, is_ows);
+            let start = scanner.current_position();
+            while !is_line_ending(scanner.remaining(), options) {
+                match scanner.first() {
+                    None => return Err(ParseError::UnexpectedEndOfInput),
+                    Some(byte) if is_ows(*byte) || !byte.is_ascii_control() => scanner.bump_by(1),
+                    Some(_) => return Err(syntax_error(scanner, "invalid header value")),
+                }
+            }
+            let value = &scanner.data()[start..scanner.current_position()];
+            let length = value
+                .iter()
+                .rposition(|byte| !is_ows(*byte))
+                .map_or(0, |end| end + 1);
+            line_ending(scanner, options)?;
+
+            if scanner.first().is_some_and(|byte| is_ows(*byte)) {
+                return Err(syntax_error(scanner, "obsolete line folding"));
+            }
+
+            Ok(Header {
+                name: std::str::from_utf8(name)?,
+                value: std::str::from_utf8(&value[..length])?,
+            })
+        })
+    }
+}
+
+impl<'a> RequestHead<'a> {
+    /// Parse a request line, its header fields and the final empty line.
+    pub fn parse(scanner: &mut Scanner<'a, u8>, options: HttpOptions) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            let request_line = RequestLine::parse(scanner, options)?;
+            let mut headers = vec![];
+            while !is_line_ending(scanner.remaining(), options) {
+                headers.push(Header::parse(scanner, options)?);
+            }
+            line_ending(scanner, options)?;
+            Ok(RequestHead {
+                request_line,
+                headers,
+            })
+        })
+    }
+}
+
+/// Implement the `Visitor` trait using strict options.
+macro_rules! impl_visitor {
+    ($type:ident) => {
+        impl<'a> Visitor<'a, u8> for $type<'a> {
+            fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+                $type::parse(scanner, HttpOptions::default())
+            }
+        }
+    };
+}
+
+impl_visitor!(RequestLine);
+impl_visitor!(Header);
+impl_visitor!(RequestHead);
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::http::{Header, HttpOptions, RequestHead, RequestLine};
+    use crate::scanner::Scanner;
+
+    fn parse(data: &[u8]) -> Result<RequestHead<'_>, ParseError> {
+        Scanner::new(data).visit::<RequestHead>()
+    }
+
+    #[test]
+    fn test_request_line() {
+        let mut scanner = Scanner::new(b"GET /path?q=1 HTTP/1.1\r\n");
+        let line = scanner.visit::<RequestLine>().expect("failed to parse");
+        assert_eq!(
+            line,
+            RequestLine {
+                method: "GET",
+                target: "/path?q=1",
+                version: "HTTP/1.1"
+            }
+        );
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_header() {
+        let mut scanner = Scanner::new(b"Content-Type: \t text/html; charset=utf-8  \r\n");
+        let header = scanner.visit::<Header>().expect("failed to parse");
+        assert_eq!(header.name, "Content-Type");
+        assert_eq!(header.value, "text/html; charset=utf-8");
+
+        let mut scanner = Scanner::new(b"X-Empty:\r\n");
+        let header = scanner.visit::<Header>().expect("failed to parse");
+        assert_eq!(header.value, "");
+    }
+
+    #[test]
+    fn test_request_head() {
+        let data = b"POST /submit HTTP/1.1\r\nHost: example.com\r\nContent-Length: 5\r\n\r\nhello";
+        let mut scanner = Scanner::new(data);
+        let head = scanner.visit::<RequestHead>().expect("failed to parse");
+        assert_eq!(head.request_line.method, "POST");
+        assert_eq!(head.headers.len(), 2);
+        assert_eq!(head.header("content-length"), Some("5"));
+        assert_eq!(scanner.remaining(), b"hello");
+
+        let head = parse(b"GET / HTTP/1.0\r\n\r\n").expect("failed to parse");
+        assert!(head.headers.is_empty());
+    }
+
+    #[test]
+    fn test_lenient_line_endings() {
+        let data = b"GET / HTTP/1.1\nHost: a\r\n\n";
+        assert!(matches!(
+            parse(data),
+            Err(ParseError::Syntax { offset: 14, .. })
+        ));
+        let options = HttpOptions {
+            lenient_line_endings: true,
+        };
+        let head = RequestHead::parse(&mut Scanner::new(data), options).expect("failed to parse");
+        assert_eq!(head.header("host"), Some("a"));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let cases: [(&[u8], usize, &str); 8] = [
+            (
+                b"GET / HTTP/1.1\r\nBad Name: x\r\n\r\n",
+                19,
+                "invalid header name",
+            ),
+            (
+                b"GET / HTTP/1.1\r\nName : x\r\n\r\n",
+                20,
+                "invalid header name",
+            ),
+            (b"GET / HTTP/1.1\r\n: x\r\n\r\n", 16, "invalid header name"),
+            (
+                b"GET / HTTP/1.1\r\nA: x\r\n  y\r\n\r\n",
+                22,
+                "obsolete line folding",
+            ),
+            (
+                b"GET / HTTP/1.1\r\nA: x\x01\r\n\r\n",
+                20,
+                "invalid header value",
+            ),
+            (b"G(T / HTTP/1.1\r\n\r\n", 1, "expected a single space"),
+            (b"GET  / HTTP/1.1\r\n\r\n", 4, "invalid request target"),
+            (b"GET / HTTP/11\r\n\r\n", 6, "invalid HTTP version"),
+        ];
+        for (data, expected_offset, expected_reason) in cases {
+            match parse(data) {
+                Err(ParseError::Syntax { offset, reason }) => {
+                    assert_eq!((offset, reason), (expected_offset, expected_reason));
+                }
+                result => panic!("unexpected result {result:?}"),
+            }
+        }
+
+        let mut scanner = Scanner::new(b"GET / HTTP/1.1\r\nHost: a\r\n");
+        let result = scanner.visit::<RequestHead>();
+        assert!(matches!(result, Err(ParseError::UnexpectedEndOfInput)));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
@@ -0,0 +1,430 @@
+//! INI configuration files.
+//!
+//! A file is a sequence of lines, each line being either:
+//!
+//! * a section header `[name]`,
+//! * a key/value pair `key = value`, the value running to the end of the line,
+//! * a comment starting with `;` or `#`,
+//! * a blank line.
+//!
+//! Surrounding whitespaces of names, keys and values are trimmed.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// A section header `[name]`.
+#[derive(Debug, PartialEq)]
+pub struct Section<'a> {
+    /// The name of the section.
+    pub name: &'a str,
+}
+
+/// A `key = value` pair.
+#[derive(Debug, PartialEq)]
+pub struct Pair<'a> {
+    /// The key of the pair.
+    pub key: &'a str,
+    /// The value of the pair.
+    pub value: &'a str,
+    /// Byte offset of the key.
+    pub offset: usize,
+}
+
+/// A comment line, the text excludes the comment character.
+#[derive(Debug, PartialEq)]
+pub struct Comment<'a>(pub &'a str);
+
+/// A line containing only whitespaces.
+#[derive(Debug, PartialEq)]
+pub struct BlankLine;
+
+/// Any line of an INI file.
+#[derive(Debug, PartialEq)]
+pub enum IniLine<'a> {
+    /// A section header.
+    Section(Section<'a>),
+    /// A key/value pair.
+    Pair(Pair<'a>),
+    /// A comment.
+    Comment(Comment<'a>),
+    /// A blank line.
+    Blank(BlankLine),
+}
+
+/// Consume a line and its terminator.
+///
+/// # Returns
+///
+/// The offset of the line and its content without the terminator.
+fn next_line<'a>(scanner: &mut Scanner<'a, u8>) -> (usize, &'a [u8]) {
+    let start = scanner.current_position();
+    let remaining = &scanner.data()[start..];
+    let (line, consumed) = match remaining.iter().position(|byte| *byte == b'\n') {
+        Some(end) => (&remaining[..end], end + 1),
+        None => (remaining, remaining.len()),
+    };
+    scanner.bump_by(consumed);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    (start, line)
+}
+
+fn is_blank(byte: &u8) -> bool {
+    *byte == b' ' || *byte == b'\t'
+}
+
+fn is_comment(byte: &u8) -> bool {
+    *byte == b';' || *byte == b'#'
+}
+
+/// Trim the surrounding blanks of the data.
+///
+/// # Returns
+///
+/// The number of leading blanks and the trimmed data.
+fn trim(data: &[u8]) -> (usize, &[u8]) {
+    let start = data.iter().position(|byte| !is_blank(byte));
+    let Some(start) = start else {
+        return (data.len(), &[]);
+    };
+    let end = data
+        .iter()
+        .rposition(|byte| !is_blank(byte))
+        .unwrap_or(start);
+    (start, &data[start..=end])
+}
+
+fn classify<'a>(offset: usize, line: &'a [u8]) -> ParseResult<IniLine<'a>> {
+    let (leading, line) = trim(line);
+    let offset = offset + leading;
+
+    match line.first() {
+        None => Ok(IniLine::Blank(BlankLine)),
+        Some(byte) if is_comment(byte) => {
+            let comment = std::str::from_utf8(&line[1..])?;
+            Ok(IniLine::Comment(Comment(comment)))
+        }
+        Some(b'[') => {
+            let end = line
+                .iter()
+                .position(|byte| *byte == b']')
+                .ok_or(ParseError::Syntax {
+                    offset,
+                    reason: "unclosed section header",
+                })?;
+            // only a comment may follow the header
+            let (after, trailing) = trim(&line[end + 1..]);
+            if trailing.first().is_some_and(|byte| !is_comment(byte)) {
+                return Err(ParseError::Syntax {
+                    offset: offset + end + 1 + after,
+                    reason: "unexpected data after section header",
+                });
+            }
+            let name = std::str::from_utf8(trim(&line[1..end]).1)?;
+            Ok(IniLine::Section(Section { name }))
+        }
+        Some(_) => {
+            let equal = line
+                .iter()
+                .position(|byte| *byte == b'=')
+                .ok_or(ParseError::Syntax {
+                    offset,
+                    reason: "missing `=` in key/value pair",
+                })?;
+            let key = trim(&line[..equal]).1;
+            let value = trim(&line[equal + 1..]).1;
+            Ok(IniLine::Pair(Pair {
+                key: std::str::from_utf8(key)?,
+                value: std::str::from_utf8(value)?,
+                offset,
+            }))
+        }
+    }
+}
+
+impl<'a> Visitor<'a, u8> for IniLine<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        if scanner.is_empty() {
+            return Err(ParseError::UnexpectedEndOfInput);
+        }
+        scanner.rollback_on_err(|scanner| {
+            let (offset, line) = next_line(scanner);
+            classify(offset, line)
+        })
+    }
+}
+
+/// Implement the `Visitor` trait for a specific kind of line.
+macro_rules! impl_line {
+    ($type:ident, $variant:ident) => {
+        impl<'a> Visitor<'a, u8> for $type<'a> {
+            fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+                scanner.rollback_on_err(|scanner| match IniLine::accept(scanner)? {
+                    IniLine::$variant(line) => Ok(line),
+                    _ => Err(ParseError::UnexpectedToken),
+                })
+            }
+        }
+    };
+}
+
+impl_line!(Section, Section);
+impl_line!(Pair, Pair);
+impl_line!(Comment, Comment);
+
+impl<'a> Visitor<'a, u8> for BlankLine {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| match IniLine::accept(scanner)? {
+            IniLine::Blank(line) => Ok(line),
+            _ => Err(ParseError::UnexpectedToken),
+        })
+    }
+}
+
+/// What to do when a key appears several times in the same section.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DuplicateKeys {
+    /// Keep every pair in order of appearance.
+    #[default]
+    KeepAll,
+    /// Keep the first value.
+    KeepFirst,
+    /// Keep the last value, at the position of the first one.
+    KeepLast,
+    /// Fail with a positioned error.
+    Reject,
+}
+
+/// What to do with keys appearing before any section header.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GlobalKeys {
+    /// Gather them in a section with an empty name.
+    #[default]
+    Allow,
+    /// Fail with a positioned error.
+    Reject,
+}
+
+/// Policies applied while assembling an [Ini].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct IniOptions {
+    /// Policy for duplicate keys.
+    pub duplicate_keys: DuplicateKeys,
+    /// Policy for keys outside any section.
+    pub global_keys: GlobalKeys,
+}
+
+/// Ordered key/value pairs of a section.
+pub type Properties = Vec<(String, String)>;
+
+/// A whole INI file.
+#[derive(Debug, PartialEq)]
+pub struct Ini {
+    /// The sections in order of appearance, with their pairs.
+    pub sections: Vec<(String, Properties)>,
+}
+
+impl Ini {
+    /// Parse a whole INI file using the given policies.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to parse the file from.
+    /// * `options` - The policies to apply.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::Syntax` on malformed lines or policy violations.
+    pub fn parse(scanner: &mut Scanner<u8>, options: IniOptions) -> ParseResult<Self> {
+        let mut sections: Vec<(String, Properties)> = vec![];
+
+        while !scanner.is_empty() {
+            match IniLine::accept(scanner)? {
+                IniLine::Section(section) => {
+                    sections.push((section.name.to_string(), vec![]));
+                }
+                IniLine::Pair(pair) => {
+                    if sections.is_empty() {
+                        if options.global_keys == GlobalKeys::Reject {
+                            return Err(ParseError::Syntax {
+                                offset: pair.offset,
+                                reason: "key outside of any section",
+                            });
+                        }
+                        sections.push((String::new(), vec![]));
+                    }
+                    let current = sections.len() - 1;
+                    insert(&mut sections[current].1, pair, options.duplicate_keys)?;
+                }
+                IniLine::Comment(_) | IniLine::Blank(_) => {}
+            }
+        }
+
+        Ok(Ini { sections })
+    }
+
+    /// Get the value of a key in a section, the first one if duplicated.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .filter(|(name, _)| name == section)
+            .flat_map(|(_, properties)| properties.iter())
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn insert(properties: &mut Properties, pair: Pair, policy: DuplicateKeys) -> ParseResult<()> {
+    let existing = properties.iter_mut().find(|(key, _)| key == pair.key);
+    match (existing, policy) {
+        (None, _) | (Some(_), DuplicateKeys::KeepAll) => {
+            properties.push((pair.key.to_string(), pair.value.to_string()));
+        }
+        (Some(_), DuplicateKeys::KeepFirst) => {}
+        (Some((_, value)), DuplicateKeys::KeepLast) => {
+            *value = pair.value.to_string();
+        }
+        (Some(_), DuplicateKeys::Reject) => {
+            return Err(ParseError::Syntax {
+                offset: pair.offset,
+                reason: "duplicate key",
+            });
+        }
+    }
+    Ok(())
+}
+
+impl<'a> Visitor<'a, u8> for Ini {
+    /// Accept a whole INI file using the default policies.
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Ini::parse(scanner, IniOptions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::ini::{Comment, DuplicateKeys, GlobalKeys, Ini, IniOptions, Pair, Section};
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    const CONFIG: &[u8] = b"; global settings
+name = demo
+
+[server]
+host = 127.0.0.1
+port= 8080 ; not a comment
+# comment
+[database]  ; trailing comment
+
+\turl =  postgres://localhost/db\r
+pool =
+";
+
+    fn pairs(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut scanner = Scanner::new(b"[ server ]\nkey = value\n; comment");
+        let section = Section::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(section.name, "server");
+        assert!(Section::accept(&mut scanner).is_err());
+        assert_eq!(scanner.current_position(), 11);
+        let pair = Pair::accept(&mut scanner).expect("failed to parse");
+        assert_eq!((pair.key, pair.value, pair.offset), ("key", "value", 11));
+        let comment = Comment::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(comment, Comment(" comment"));
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_config_file() {
+        let mut scanner = Scanner::new(CONFIG);
+        let ini = scanner.visit::<Ini>().expect("failed to parse");
+        assert_eq!(
+            ini.sections,
+            vec![
+                ("".to_string(), pairs(&[("name", "demo")])),
+                (
+                    "server".to_string(),
+                    pairs(&[("host", "127.0.0.1"), ("port", "8080 ; not a comment")])
+                ),
+                (
+                    "database".to_string(),
+                    pairs(&[("url", "postgres://localhost/db"), ("pool", "")])
+                ),
+            ]
+        );
+        assert_eq!(ini.get("database", "url"), Some("postgres://localhost/db"));
+        assert_eq!(ini.get("server", "missing"), None);
+    }
+
+    #[test]
+    fn test_policies() {
+        let data = b"[a]\nk = 1\nk = 2\n";
+        let parse = |duplicate_keys| {
+            let options = IniOptions {
+                duplicate_keys,
+                ..Default::default()
+            };
+            Ini::parse(&mut Scanner::new(data), options)
+        };
+        let values = |ini: Ini| ini.sections[0].1.clone();
+
+        assert_eq!(
+            values(parse(DuplicateKeys::KeepAll).expect("failed to parse")),
+            pairs(&[("k", "1"), ("k", "2")])
+        );
+        assert_eq!(
+            values(parse(DuplicateKeys::KeepFirst).expect("failed to parse")),
+            pairs(&[("k", "1")])
+        );
+        assert_eq!(
+            values(parse(DuplicateKeys::KeepLast).expect("failed to parse")),
+            pairs(&[("k", "2")])
+        );
+        assert!(matches!(
+            parse(DuplicateKeys::Reject),
+            Err(ParseError::Syntax { offset: 10, .. })
+        ));
+
+        let options = IniOptions {
+            global_keys: GlobalKeys::Reject,
+            ..Default::default()
+        };
+        let result = Ini::parse(&mut Scanner::new(CONFIG), options);
+        assert!(matches!(result, Err(ParseError::Syntax { offset: 18, .. })));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut scanner = Scanner::new(b"[a]\n  [unclosed\n");
+        let result = scanner.visit::<Ini>();
+        assert!(matches!(
+            result,
+            Err(ParseError::Syntax {
+                offset: 6,
+                reason: "unclosed section header"
+            })
+        ));
+
+        let mut scanner = Scanner::new(b"[a]\nk = 1\nmissing equal\n");
+        let result = scanner.visit::<Ini>();
+        assert!(matches!(
+            result,
+            Err(ParseError::Syntax {
+                offset: 10,
+                reason: "missing `=` in key/value pair"
+            })
+        ));
+
+        let mut scanner = Scanner::new(b"[a] b\n");
+        let result = scanner.visit::<Ini>();
+        assert!(matches!(result, Err(ParseError::Syntax { offset: 4, .. })));
+    }
+}
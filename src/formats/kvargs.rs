@@ -0,0 +1,206 @@
+//! Key=value argument strings.
+//!
+//! Arguments are separated by whitespaces, each argument is either a bare
+//! flag `verbose` or a `key=value` pair. A value may be enclosed in single or
+//! double quotes, in which case it can contain whitespaces and `=`, and the
+//! quote can be escaped with a backslash.
+//!
+//! ```text
+//! retries=3 timeout=5s name="my job" verbose
+//! ```
+
+use crate::bytes::components::groups::match_for_delimited_group;
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::peek::PeekResult;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Arguments in order of appearance, duplicated keys are kept.
+#[derive(Debug, PartialEq)]
+pub struct KvArgs(pub Vec<(String, Option<String>)>);
+
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    let count = scanner
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count();
+    scanner.bump_by(count);
+}
+
+/// Consume bytes until a whitespace or any of the `stops` bytes.
+fn take_until<'a>(scanner: &mut Scanner<'a, u8>, stops: &[u8]) -> &'a [u8] {
+    let start = scanner.current_position();
+    let length = scanner
+        .iter()
+        .take_while(|byte| !byte.is_ascii_whitespace() && !stops.contains(byte))
+        .count();
+    scanner.bump_by(length);
+    &scanner.data()[start..start + length]
+}
+
+fn parse_quoted(scanner: &mut Scanner<u8>, quote: Token) -> ParseResult<String> {
+    let start = scanner.current_position();
+    let result = match_for_delimited_group(quote, Token::Backslash)(scanner.remaining())?;
+    let PeekResult::Found { end_slice, .. } = result else {
+        return Err(ParseError::Syntax {
+            offset: start,
+            reason: "unterminated quote",
+        });
+    };
+    let quote_byte = scanner.remaining()[0];
+    let content = &scanner.remaining()[1..end_slice - 1];
+    let mut value = Vec::with_capacity(content.len());
+    let mut iter = content.iter();
+    while let Some(&byte) = iter.next() {
+        match (byte, iter.as_slice().first()) {
+            (b'\\', Some(&next)) if next == b'\\' || next == quote_byte => {
+                value.push(next);
+                iter.next();
+            }
+            (byte, _) => value.push(byte),
+        }
+    }
+    scanner.bump_by(end_slice);
+    String::from_utf8(value).map_err(|err| err.utf8_error().into())
+}
+
+fn parse_value(scanner: &mut Scanner<u8>) -> ParseResult<String> {
+    let quote = match scanner.first() {
+        Some(b'"') => Token::DoubleQuote,
+        Some(b'\'') => Token::Quote,
+        _ => {
+            let value = take_until(scanner, &[]);
+            return Ok(std::str::from_utf8(value)?.to_string());
+        }
+    };
+    let value = parse_quoted(scanner, quote)?;
+    if scanner
+        .first()
+        .is_some_and(|byte| !byte.is_ascii_whitespace())
+    {
+        return Err(ParseError::Syntax {
+            offset: scanner.current_position(),
+            reason: "unexpected data after a quoted value",
+        });
+    }
+    Ok(value)
+}
+
+impl<'a> Visitor<'a, u8> for KvArgs {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            let mut arguments = vec![];
+            loop {
+                skip_whitespaces(scanner);
+                if scanner.is_empty() {
+                    break;
+                }
+                let offset = scanner.current_position();
+                let key = take_until(scanner, b"=");
+                if key.is_empty() {
+                    return Err(ParseError::Syntax {
+                        offset,
+                        reason: "missing key",
+                    });
+                }
+                let key = std::str::from_utf8(key)?.to_string();
+                let value = match scanner.first() {
+                    Some(b'=') => {
+                        scanner.bump_by(1);
+                        Some(parse_value(scanner)?)
+                    }
+                    _ => None,
+                };
+                arguments.push((key, value));
+            }
+            Ok(KvArgs(arguments))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::kvargs::KvArgs;
+    use crate::scanner::Scanner;
+
+    fn parse(data: &[u8]) -> Result<Vec<(String, Option<String>)>, ParseError> {
+        Scanner::new(data).visit::<KvArgs>().map(|args| args.0)
+    }
+
+    fn args(values: &[(&str, Option<&str>)]) -> Vec<(String, Option<String>)> {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.map(str::to_string)))
+            .collect()
+    }
+
+    #[test]
+    fn test_kvargs() {
+        let result = parse(br#"retries=3 timeout=5s name="my job" verbose"#);
+        assert_eq!(
+            result.expect("failed to parse"),
+            args(&[
+                ("retries", Some("3")),
+                ("timeout", Some("5s")),
+                ("name", Some("my job")),
+                ("verbose", None),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        let result = parse(br#"query="a = b and c" msg='it\'s' path="C:\\tmp \"x\"""#);
+        assert_eq!(
+            result.expect("failed to parse"),
+            args(&[
+                ("query", Some("a = b and c")),
+                ("msg", Some("it's")),
+                ("path", Some(r#"C:\tmp "x""#)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(parse(b"").expect("failed to parse"), args(&[]));
+        assert_eq!(parse(b"   ").expect("failed to parse"), args(&[]));
+        assert_eq!(
+            parse(b"  a=1\tflag  b=  \n").expect("failed to parse"),
+            args(&[("a", Some("1")), ("flag", None), ("b", Some(""))])
+        );
+        assert_eq!(
+            parse(b"a=1 a=2 a").expect("failed to parse"),
+            args(&[("a", Some("1")), ("a", Some("2")), ("a", None)])
+        );
+        assert_eq!(
+            parse(b"url=http://x?a=b").expect("failed to parse"),
+            args(&[("url", Some("http://x?a=b"))])
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut scanner = Scanner::new(br#"a=1 name="my job"#);
+        let result = scanner.visit::<KvArgs>();
+        assert!(matches!(
+            result,
+            Err(ParseError::Syntax {
+                offset: 9,
+                reason: "unterminated quote"
+            })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        assert!(matches!(
+            parse(b"a=1 =2"),
+            Err(ParseError::Syntax { offset: 4, .. })
+        ));
+        assert!(matches!(
+            parse(br#"a="b"c"#),
+            Err(ParseError::Syntax { offset: 5, .. })
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+//! Ready to use parsers for common data formats.
+pub mod csv;
+pub mod http;
+pub mod ini;
+pub mod kvargs;
+pub mod path;
+pub mod sexpr;
+pub mod shellwords;
+pub mod url;
+pub mod urlencoded;
@@ -0,0 +1,175 @@
+//! Dotted paths used as lookup keys.
+//!
+//! A path is a sequence of segments, each segment being either an identifier
+//! introduced by a `.` or an index enclosed in brackets:
+//!
+//! ```text
+//! server.http.port
+//! a.b[3].c
+//! ```
+
+use crate::bytes::primitives::identifier::Identifier;
+use crate::bytes::primitives::number::Number;
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::recognizer::Recognizable;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// A segment of a [Path].
+#[derive(Debug, PartialEq)]
+pub enum PathSegment<'a> {
+    /// A named segment.
+    Key(&'a str),
+    /// An index segment `[n]`.
+    Index(usize),
+}
+
+/// A dotted path.
+#[derive(Debug, PartialEq)]
+pub struct Path<'a>(pub Vec<PathSegment<'a>>);
+
+fn empty_segment(scanner: &Scanner<u8>) -> ParseError {
+    ParseError::Syntax {
+        offset: scanner.current_position(),
+        reason: "empty path segment",
+    }
+}
+
+/// Recognize a token, returning false at the end of the input.
+fn next_is(token: Token, scanner: &mut Scanner<u8>) -> ParseResult<bool> {
+    if scanner.is_empty() {
+        return Ok(false);
+    }
+    Ok(token.recognize(scanner)?.is_some())
+}
+
+fn parse_key<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<PathSegment<'a>> {
+    match Identifier::accept(scanner) {
+        Ok(Identifier(key)) => Ok(PathSegment::Key(key)),
+        Err(ParseError::UnexpectedToken | ParseError::UnexpectedEndOfInput) => {
+            Err(empty_segment(scanner))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Parse an index, the opening bracket being already consumed.
+fn parse_index<'a>(scanner: &mut Scanner<'a, u8>, open: usize) -> ParseResult<PathSegment<'a>> {
+    let index = match Number::<usize>::accept(scanner) {
+        Ok(Number(index)) => index,
+        Err(ParseError::UnexpectedEndOfInput) => {
+            return Err(ParseError::Syntax {
+                offset: open,
+                reason: "unclosed index",
+            });
+        }
+        Err(ParseError::UnexpectedToken) => {
+            return Err(ParseError::Syntax {
+                offset: scanner.current_position(),
+                reason: "invalid index",
+            });
+        }
+        Err(err) => return Err(err),
+    };
+    if !next_is(Token::CloseBracket, scanner)? {
+        return Err(ParseError::Syntax {
+            offset: open,
+            reason: "unclosed index",
+        });
+    }
+    Ok(PathSegment::Index(index))
+}
+
+impl<'a> Visitor<'a, u8> for Path<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            let mut segments = vec![];
+
+            let open = scanner.current_position();
+            if next_is(Token::OpenBracket, scanner)? {
+                segments.push(parse_index(scanner, open)?);
+            } else {
+                segments.push(parse_key(scanner)?);
+            }
+
+            loop {
+                let open = scanner.current_position();
+                if next_is(Token::Dot, scanner)? {
+                    segments.push(parse_key(scanner)?);
+                } else if next_is(Token::OpenBracket, scanner)? {
+                    segments.push(parse_index(scanner, open)?);
+                } else {
+                    break;
+                }
+            }
+
+            Ok(Path(segments))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::path::{Path, PathSegment};
+    use crate::scanner::Scanner;
+
+    fn parse(data: &[u8]) -> Result<Vec<PathSegment<'_>>, ParseError> {
+        Scanner::new(data).visit::<Path>().map(|path| path.0)
+    }
+
+    #[test]
+    fn test_dotted_path() {
+        let mut scanner = Scanner::new(b"server.http.port = 80");
+        let path = scanner.visit::<Path>().expect("failed to parse");
+        assert_eq!(
+            path.0,
+            vec![
+                PathSegment::Key("server"),
+                PathSegment::Key("http"),
+                PathSegment::Key("port")
+            ]
+        );
+        assert_eq!(scanner.remaining(), b" = 80");
+    }
+
+    #[test]
+    fn test_indexed_path() {
+        assert_eq!(
+            parse(b"a.b[3].c").expect("failed to parse"),
+            vec![
+                PathSegment::Key("a"),
+                PathSegment::Key("b"),
+                PathSegment::Index(3),
+                PathSegment::Key("c")
+            ]
+        );
+        assert_eq!(
+            parse(b"[0][12]").expect("failed to parse"),
+            vec![PathSegment::Index(0), PathSegment::Index(12)]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases: [(&[u8], usize, &str); 6] = [
+            (b"a.", 2, "empty path segment"),
+            (b".a", 0, "empty path segment"),
+            (b"a..b", 2, "empty path segment"),
+            (b"a[3", 1, "unclosed index"),
+            (b"a[3.b", 1, "unclosed index"),
+            (b"a[x]", 2, "invalid index"),
+        ];
+        for (data, expected_offset, expected_reason) in cases {
+            let mut scanner = Scanner::new(data);
+            match scanner.visit::<Path>() {
+                Err(ParseError::Syntax { offset, reason }) => {
+                    assert_eq!((offset, reason), (expected_offset, expected_reason));
+                }
+                result => panic!("unexpected result {result:?}"),
+            }
+            assert_eq!(scanner.current_position(), 0);
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! S-expressions.
+//!
+//! An expression is either an atom or a parenthesized list of expressions.
+//! Atoms are integers, symbols and double quoted strings supporting the
+//! `\"`, `\\`, `\n`, `\r` and `\t` escapes. Whitespaces separate elements
+//! and `;` starts a comment running to the end of the line.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+/// Maximum nesting of lists, prevents stack overflows on hostile input.
+pub const MAX_DEPTH: usize = 128;
+
+/// An S-expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SExpr<'a> {
+    /// A signed integer.
+    Integer(i64),
+    /// A symbol.
+    Symbol(&'a str),
+    /// A string, borrowed when it doesn't contain escapes.
+    String(Cow<'a, str>),
+    /// A list of expressions.
+    List(Vec<SExpr<'a>>),
+}
+
+/// Skip whitespaces and comments.
+fn skip_trivia(scanner: &mut Scanner<u8>) {
+    loop {
+        match scanner.first() {
+            Some(byte) if byte.is_ascii_whitespace() => scanner.bump_by(1),
+            Some(b';') => {
+                let end = scanner
+                    .iter()
+                    .position(|byte| *byte == b'\n')
+                    .unwrap_or(scanner.len());
+                scanner.bump_by(end);
+            }
+            _ => break,
+        }
+    }
+}
+
+fn is_delimiter(byte: &u8) -> bool {
+    byte.is_ascii_whitespace() || matches!(byte, b'(' | b')' | b'"' | b';')
+}
+
+fn is_integer(atom: &[u8]) -> bool {
+    let digits = match atom {
+        [b'+' | b'-', digits @ ..] => digits,
+        digits => digits,
+    };
+    !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+}
+
+fn parse_atom<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<SExpr<'a>> {
+    let start = scanner.current_position();
+    let length = scanner
+        .iter()
+        .position(is_delimiter)
+        .unwrap_or(scanner.len());
+    let atom = &scanner.data()[start..start + length];
+    let atom_str = std::str::from_utf8(atom)?;
+    let expr = if is_integer(atom) {
+        SExpr::Integer(atom_str.parse()?)
+    } else {
+        SExpr::Symbol(atom_str)
+    };
+    scanner.bump_by(length);
+    Ok(expr)
+}
+
+fn parse_string<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<SExpr<'a>> {
+    let start = scanner.current_position();
+    let data = scanner.data();
+    let mut pos = start + 1;
+    let mut escaped = false;
+
+    loop {
+        match data.get(pos) {
+            None => {
+                return Err(ParseError::Syntax {
+                    offset: start,
+                    reason: "unterminated string",
+                });
+            }
+            Some(b'\\') => {
+                escaped = true;
+                pos += 2;
+            }
+            Some(b'"') => break,
+            Some(_) => pos += 1,
+        }
+    }
+
+    let content = &data[start + 1..pos];
+    let value = if escaped {
+        Cow::Owned(unescape(content, start + 1)?)
+    } else {
+        Cow::Borrowed(std::str::from_utf8(content)?)
+    };
+    scanner.jump_to(pos + 1);
+    Ok(SExpr::String(value))
+}
+
+fn unescape(content: &[u8], offset: usize) -> ParseResult<String> {
+    let mut result = Vec::with_capacity(content.len());
+    let mut pos = 0;
+    while pos < content.len() {
+        let byte = match content[pos] {
+            b'\\' => {
+                pos += 1;
+                match content[pos] {
+                    b'"' => b'"',
+                    b'\\' => b'\\',
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    _ => {
+                        return Err(ParseError::Syntax {
+                            offset: offset + pos - 1,
+                            reason: "invalid escape sequence",
+                        });
+                    }
+                }
+            }
+            byte => byte,
+        };
+        result.push(byte);
+        pos += 1;
+    }
+    String::from_utf8(result).map_err(|err| err.utf8_error().into())
+}
+
+fn parse_list<'a>(scanner: &mut Scanner<'a, u8>, depth: usize) -> ParseResult<SExpr<'a>> {
+    let open = scanner.current_position();
+    if depth >= MAX_DEPTH {
+        return Err(ParseError::Syntax {
+            offset: open,
+            reason: "lists nested too deeply",
+        });
+    }
+    // skip the opening parenthesis
+    scanner.bump_by(1);
+    let mut elements = vec![];
+
+    loop {
+        skip_trivia(scanner);
+        match scanner.first() {
+            None => {
+                return Err(ParseError::Syntax {
+                    offset: open,
+                    reason: "unclosed list",
+                });
+            }
+            Some(b')') => {
+                scanner.bump_by(1);
+                return Ok(SExpr::List(elements));
+            }
+            Some(_) => elements.push(parse_expr(scanner, depth + 1)?),
+        }
+    }
+}
+
+fn parse_expr<'a>(scanner: &mut Scanner<'a, u8>, depth: usize) -> ParseResult<SExpr<'a>> {
+    match scanner.first() {
+        None => Err(ParseError::UnexpectedEndOfInput),
+        Some(b'(') => parse_list(scanner, depth),
+        Some(b')') => Err(ParseError::Syntax {
+            offset: scanner.current_position(),
+            reason: "unexpected closing parenthesis",
+        }),
+        Some(b'"') => parse_string(scanner),
+        Some(_) => parse_atom(scanner),
+    }
+}
+
+impl<'a> Visitor<'a, u8> for SExpr<'a> {
+    /// Accept one expression, leading whitespaces and comments are skipped.
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        scanner.rollback_on_err(|scanner| {
+            skip_trivia(scanner);
+            parse_expr(scanner, 0)
+        })
+    }
+}
+
+impl Display for SExpr<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SExpr::Integer(value) => write!(f, "{value}"),
+            SExpr::Symbol(symbol) => write!(f, "{symbol}"),
+            SExpr::String(value) => {
+                write!(f, "\"")?;
+                for char in value.chars() {
+                    match char {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        char => write!(f, "{char}")?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            SExpr::List(elements) => {
+                write!(f, "(")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::sexpr::{MAX_DEPTH, SExpr};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    fn parse(data: &[u8]) -> Result<SExpr<'_>, ParseError> {
+        Scanner::new(data).visit::<SExpr>()
+    }
+
+    #[test]
+    fn test_atoms() {
+        assert_eq!(parse(b"42").expect("failed to parse"), SExpr::Integer(42));
+        assert_eq!(parse(b"-7").expect("failed to parse"), SExpr::Integer(-7));
+        assert_eq!(parse(b"-").expect("failed to parse"), SExpr::Symbol("-"));
+        assert_eq!(
+            parse(b"  foo-bar?)").expect("failed to parse"),
+            SExpr::Symbol("foo-bar?")
+        );
+        assert_eq!(
+            parse(br#""a \"b\"\n""#).expect("failed to parse"),
+            SExpr::String(Cow::Owned("a \"b\"\n".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let data = b"; comment\n(a (b 1) \"c\" ; inline\n ())";
+        let mut scanner = Scanner::new(data);
+        let expr = scanner.visit::<SExpr>().expect("failed to parse");
+        assert_eq!(
+            expr,
+            SExpr::List(vec![
+                SExpr::Symbol("a"),
+                SExpr::List(vec![SExpr::Symbol("b"), SExpr::Integer(1)]),
+                SExpr::String(Cow::Borrowed("c")),
+                SExpr::List(vec![]),
+            ])
+        );
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = br#"(define (greet name) (print "hello \"" name "\"\t" -12))"#;
+        let expr = parse(data).expect("failed to parse");
+        let printed = expr.to_string();
+        assert_eq!(
+            printed,
+            r#"(define (greet name) (print "hello \"" name "\"\t" -12))"#
+        );
+        let reparsed = parse(printed.as_bytes()).expect("failed to parse");
+        assert_eq!(expr, reparsed);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            parse(b"(a (b 1) (c"),
+            Err(ParseError::Syntax {
+                offset: 9,
+                reason: "unclosed list"
+            })
+        ));
+        assert!(matches!(
+            parse(b"(a (b 1 c"),
+            Err(ParseError::Syntax { offset: 3, .. })
+        ));
+        assert!(matches!(
+            parse(b"(a \"b)"),
+            Err(ParseError::Syntax {
+                offset: 3,
+                reason: "unterminated string"
+            })
+        ));
+        assert!(matches!(
+            parse(b")"),
+            Err(ParseError::Syntax { offset: 0, .. })
+        ));
+        assert!(matches!(
+            parse(br#""\q""#),
+            Err(ParseError::Syntax { offset: 1, .. })
+        ));
+
+        let deep = "(".repeat(MAX_DEPTH + 1);
+        let mut scanner = Scanner::new(deep.as_bytes());
+        let result = scanner.visit::<SExpr>();
+        assert!(matches!(
+            result,
+            Err(ParseError::Syntax {
+                reason: "lists nested too deeply",
+                ..
+            })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
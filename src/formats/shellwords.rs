@@ -0,0 +1,227 @@
+//! Split a command line into arguments the way a POSIX shell does.
+//!
+//! * whitespaces separate arguments,
+//! * single quotes preserve everything literally up to the closing quote,
+//! * double quotes preserve everything except `\"`, `\\`, `` \` `` and `\$`
+//!   which stand for the escaped character,
+//! * outside quotes, a backslash escapes the next character and a backslash
+//!   followed by a line feed is removed,
+//! * adjacent quoted and unquoted runs are concatenated into a single
+//!   argument: `foo"bar baz"` is `foobar baz`.
+//!
+//! Neither expansions nor comments are supported.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The arguments of a command line.
+#[derive(Debug, PartialEq)]
+pub struct ShellWords(pub Vec<String>);
+
+/// Bytes which may be escaped inside double quotes.
+const DOUBLE_QUOTE_ESCAPES: &[u8] = b"\"\\`$";
+
+fn unterminated(offset: usize) -> ParseError {
+    ParseError::Syntax {
+        offset,
+        reason: "unterminated quote",
+    }
+}
+
+/// Consume a single quoted run, the opening quote being at `start`.
+fn single_quoted(data: &[u8], start: usize, word: &mut Vec<u8>) -> ParseResult<usize> {
+    let length = data[start + 1..]
+        .iter()
+        .position(|byte| *byte == b'\'')
+        .ok_or_else(|| unterminated(start))?;
+    word.extend_from_slice(&data[start + 1..start + 1 + length]);
+    Ok(start + length + 2)
+}
+
+/// Consume a double quoted run, the opening quote being at `start`.
+fn double_quoted(data: &[u8], start: usize, word: &mut Vec<u8>) -> ParseResult<usize> {
+    let mut pos = start + 1;
+    loop {
+        match data.get(pos) {
+            None => return Err(unterminated(start)),
+            Some(b'"') => return Ok(pos + 1),
+            Some(b'\\') => match data.get(pos + 1) {
+                Some(byte) if DOUBLE_QUOTE_ESCAPES.contains(byte) => {
+                    word.push(*byte);
+                    pos += 2;
+                }
+                Some(b'\n') => pos += 2,
+                _ => {
+                    word.push(b'\\');
+                    pos += 1;
+                }
+            },
+            Some(byte) => {
+                word.push(*byte);
+                pos += 1;
+            }
+        }
+    }
+}
+
+fn split(data: &[u8], offset: usize) -> ParseResult<Vec<String>> {
+    let mut words = vec![];
+    let mut word: Option<Vec<u8>> = None;
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let byte = data[pos];
+        if byte.is_ascii_whitespace() {
+            if let Some(word) = word.take() {
+                words.push(word);
+            }
+            pos += 1;
+            continue;
+        }
+
+        // a line continuation is removed, without starting a word
+        if byte == b'\\' && data.get(pos + 1) == Some(&b'\n') {
+            pos += 2;
+            continue;
+        }
+
+        let current = word.get_or_insert_with(Vec::new);
+        pos = match byte {
+            b'\'' => single_quoted(data, pos, current).map_err(|err| shift(err, offset))?,
+            b'"' => double_quoted(data, pos, current).map_err(|err| shift(err, offset))?,
+            b'\\' => match data.get(pos + 1) {
+                None => {
+                    return Err(ParseError::Syntax {
+                        offset: offset + pos,
+                        reason: "unterminated escape",
+                    });
+                }
+                Some(byte) => {
+                    current.push(*byte);
+                    pos + 2
+                }
+            },
+            byte => {
+                current.push(byte);
+                pos + 1
+            }
+        };
+    }
+    if let Some(word) = word {
+        words.push(word);
+    }
+
+    words
+        .into_iter()
+        .map(|word| String::from_utf8(word).map_err(|err| err.utf8_error().into()))
+        .collect()
+}
+
+/// Move the offset of a syntax error by `offset`.
+fn shift(err: ParseError, offset: usize) -> ParseError {
+    match err {
+        ParseError::Syntax {
+            offset: pos,
+            reason,
+        } => ParseError::Syntax {
+            offset: offset + pos,
+            reason,
+        },
+        err => err,
+    }
+}
+
+impl<'a> Visitor<'a, u8> for ShellWords {
+    /// Accept the remaining data as a command line.
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let words = split(scanner.remaining(), scanner.current_position())?;
+        scanner.bump_by(scanner.remaining().len());
+        Ok(ShellWords(words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::shellwords::ShellWords;
+    use crate::scanner::Scanner;
+
+    fn split(data: &str) -> Result<Vec<String>, ParseError> {
+        Scanner::new(data.as_bytes())
+            .visit::<ShellWords>()
+            .map(|words| words.0)
+    }
+
+    fn assert_split(data: &str, expected: &[&str]) {
+        assert_eq!(
+            split(data).expect("failed to parse"),
+            expected,
+            "splitting {data:?}"
+        );
+    }
+
+    #[test]
+    fn test_whitespaces() {
+        assert_split("", &[]);
+        assert_split("   \t\n ", &[]);
+        assert_split("foo bar", &["foo", "bar"]);
+        assert_split("  foo \t bar\n baz  ", &["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn test_single_quotes() {
+        assert_split("'foo bar'", &["foo bar"]);
+        assert_split(r#"'a "b" \c $d'"#, &[r#"a "b" \c $d"#]);
+        assert_split("'' ''", &["", ""]);
+        assert_split(r"'it'\''s'", &["it's"]);
+    }
+
+    #[test]
+    fn test_double_quotes() {
+        assert_split(r#""foo bar""#, &["foo bar"]);
+        assert_split(r#""say \"hi\"""#, &[r#"say "hi""#]);
+        assert_split(r#""a\\b\c\$d""#, &[r"a\b\c$d"]);
+        assert_split(r#""it's""#, &["it's"]);
+        assert_split(r#""""#, &[""]);
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_split(r"foo\ bar", &["foo bar"]);
+        assert_split(r#"\'\"\\"#, &[r#"'"\"#]);
+        assert_split("foo\\\nbar", &["foobar"]);
+        assert_split("foo \\\n bar", &["foo", "bar"]);
+    }
+
+    #[test]
+    fn test_concatenation() {
+        assert_split(r#"foo"bar baz""#, &["foobar baz"]);
+        assert_split(r#"a'b c'"d e"f g"#, &["ab cd ef", "g"]);
+        assert_split(r#"--name="my job" -x"#, &["--name=my job", "-x"]);
+        assert_split(r#"""a''"#, &["a"]);
+    }
+
+    #[test]
+    fn test_utf8() {
+        assert_split("échec 'mat à' \"l'œil\"", &["échec", "mat à", "l'œil"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases: [(&str, usize, &str); 4] = [
+            ("foo 'bar", 4, "unterminated quote"),
+            (r#"foo "bar\""#, 4, "unterminated quote"),
+            (r#"a"b'c"'d"#, 6, "unterminated quote"),
+            ("foo\\", 3, "unterminated escape"),
+        ];
+        for (data, expected_offset, expected_reason) in cases {
+            match split(data) {
+                Err(ParseError::Syntax { offset, reason }) => {
+                    assert_eq!((offset, reason), (expected_offset, expected_reason));
+                }
+                result => panic!("unexpected result {result:?} for {data:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! URL query strings and `application/x-www-form-urlencoded` payloads.
+//!
+//! Pairs are separated by `&`, keys and values by the first `=`. A key
+//! without `=` has no value, empty pairs are skipped and repeated keys are
+//! kept in order of appearance.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// How the `+` character must be interpreted.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// URL query string, `+` is a literal `+`.
+    #[default]
+    Query,
+    /// Form payload, `+` stands for a space.
+    Form,
+}
+
+/// Options of the decoding.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct UrlEncodedOptions {
+    /// Interpretation of `+`.
+    pub mode: Mode,
+    /// Keep invalid percent escapes literally instead of failing.
+    pub lenient: bool,
+}
+
+/// A key and its optional value.
+pub type UrlEncodedPair<'a> = (Cow<'a, str>, Option<Cow<'a, str>>);
+
+/// Decoded pairs of an urlencoded input.
+#[derive(Debug, PartialEq)]
+pub struct UrlEncoded<'a> {
+    /// The pairs in order of appearance.
+    pub pairs: Vec<UrlEncodedPair<'a>>,
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode the percent escapes of the data.
+///
+/// # Arguments
+///
+/// * `data` - The data to decode.
+/// * `options` - The decoding options.
+///
+/// # Returns
+///
+/// The decoded data, borrowed if nothing had to be decoded.
+///
+/// # Errors
+///
+/// The offset in `data` of the first invalid escape when not lenient.
+pub fn percent_decode(data: &[u8], options: UrlEncodedOptions) -> Result<Cow<'_, [u8]>, usize> {
+    let plus_as_space = options.mode == Mode::Form;
+    if !data
+        .iter()
+        .any(|byte| *byte == b'%' || (plus_as_space && *byte == b'+'))
+    {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut pos = 0;
+    while pos < data.len() {
+        match data[pos] {
+            b'%' => {
+                let high = data.get(pos + 1).copied().and_then(hex_value);
+                let low = data.get(pos + 2).copied().and_then(hex_value);
+                match (high, low) {
+                    (Some(high), Some(low)) => {
+                        decoded.push(high << 4 | low);
+                        pos += 3;
+                        continue;
+                    }
+                    _ if options.lenient => decoded.push(b'%'),
+                    _ => return Err(pos),
+                }
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        pos += 1;
+    }
+    Ok(Cow::Owned(decoded))
+}
+
+/// Decode a component located at `offset` in the input as UTF-8 text.
+fn decode_component(
+    data: &[u8],
+    offset: usize,
+    options: UrlEncodedOptions,
+) -> ParseResult<Cow<'_, str>> {
+    let decoded = percent_decode(data, options).map_err(|pos| ParseError::Syntax {
+        offset: offset + pos,
+        reason: "invalid percent escape",
+    })?;
+    match decoded {
+        Cow::Borrowed(data) => Ok(Cow::Borrowed(std::str::from_utf8(data)?)),
+        Cow::Owned(data) => match String::from_utf8(data) {
+            Ok(text) => Ok(Cow::Owned(text)),
+            Err(err) => Err(err.utf8_error().into()),
+        },
+    }
+}
+
+impl<'a> UrlEncoded<'a> {
+    /// Decode the remaining data of the scanner.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to decode, it is consumed entirely.
+    /// * `options` - The decoding options.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::Syntax` on an invalid percent escape when not lenient,
+    /// `ParseError::Utf8Error` if the decoded data is not UTF-8.
+    pub fn parse(scanner: &mut Scanner<'a, u8>, options: UrlEncodedOptions) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let data = &scanner.data()[start..];
+        let mut pairs = vec![];
+        let mut offset = start;
+
+        for pair in data.split(|byte| *byte == b'&') {
+            let pair_offset = offset;
+            offset += pair.len() + 1;
+            if pair.is_empty() {
+                continue;
+            }
+            let (key, value) = match pair.iter().position(|byte| *byte == b'=') {
+                Some(equal) => (&pair[..equal], Some((equal + 1, &pair[equal + 1..]))),
+                None => (pair, None),
+            };
+            let key = decode_component(key, pair_offset, options)?;
+            let value = match value {
+                Some((value_offset, value)) => Some(decode_component(
+                    value,
+                    pair_offset + value_offset,
+                    options,
+                )?),
+                None => None,
+            };
+            pairs.push((key, value));
+        }
+
+        scanner.bump_by(data.len());
+        Ok(UrlEncoded { pairs })
+    }
+}
+
+impl<'a> Visitor<'a, u8> for UrlEncoded<'a> {
+    /// Accept a strict query string.
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        UrlEncoded::parse(scanner, UrlEncodedOptions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::urlencoded::{Mode, UrlEncoded, UrlEncodedOptions};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    fn pairs<'a>(encoded: &'a UrlEncoded) -> Vec<(&'a str, Option<&'a str>)> {
+        encoded
+            .pairs
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let mut scanner = Scanner::new(b"a=1&b=hello%20world&flag&&a=2&c=&d=x+y");
+        let encoded = scanner.visit::<UrlEncoded>().expect("failed to parse");
+        assert_eq!(
+            pairs(&encoded),
+            vec![
+                ("a", Some("1")),
+                ("b", Some("hello world")),
+                ("flag", None),
+                ("a", Some("2")),
+                ("c", Some("")),
+                ("d", Some("x+y")),
+            ]
+        );
+        assert!(matches!(encoded.pairs[0].0, Cow::Borrowed(_)));
+        assert!(matches!(encoded.pairs[1].1, Some(Cow::Owned(_))));
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_form() {
+        let options = UrlEncodedOptions {
+            mode: Mode::Form,
+            ..Default::default()
+        };
+        let mut scanner = Scanner::new(b"name=John+Doe&op=1%2B1");
+        let encoded = UrlEncoded::parse(&mut scanner, options).expect("failed to parse");
+        assert_eq!(
+            pairs(&encoded),
+            vec![("name", Some("John Doe")), ("op", Some("1+1"))]
+        );
+    }
+
+    #[test]
+    fn test_utf8() {
+        let mut scanner = Scanner::new(b"city=S%C3%A3o%20Paulo&%E5%90%8D=%E5%80%A4");
+        let encoded = scanner.visit::<UrlEncoded>().expect("failed to parse");
+        assert_eq!(
+            pairs(&encoded),
+            vec![("city", Some("São Paulo")), ("名", Some("値"))]
+        );
+
+        let mut scanner = Scanner::new(b"a=%FF");
+        let result = scanner.visit::<UrlEncoded>();
+        assert!(matches!(result, Err(ParseError::Utf8Error(_))));
+    }
+
+    #[test]
+    fn test_invalid_escape() {
+        let mut scanner = Scanner::new(b"a=1&b=50%&c=%zz");
+        let result = scanner.visit::<UrlEncoded>();
+        assert!(matches!(
+            result,
+            Err(ParseError::Syntax {
+                offset: 8,
+                reason: "invalid percent escape"
+            })
+        ));
+
+        let options = UrlEncodedOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let mut scanner = Scanner::new(b"a=1&b=50%&c=%zz%41");
+        let encoded = UrlEncoded::parse(&mut scanner, options).expect("failed to parse");
+        assert_eq!(
+            pairs(&encoded),
+            vec![("a", Some("1")), ("b", Some("50%")), ("c", Some("%zzA"))]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Runtime support of the [crate::grammar!] macro.
+//!
+//! The rules generated by the macro share a [RuleContext] during a parse.
+//! Every element leaves the scanner untouched when it fails and records its
+//! failure, so that the error of a rule lists the elements failing the
+//! furthest in the data. A rewind exceeding the backtrack limit of the
+//! scanner, or a visitor failing with an error which can't be recovered
+//! from, stops the parse, every element failing from then on.
+
+use crate::errors::ParseError;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The failures recorded while parsing the rules of a grammar.
+#[derive(Debug)]
+pub struct RuleContext {
+    offset: usize,
+    expected: Vec<&'static str>,
+    /// Number of silent rules being parsed.
+    silent: usize,
+    /// The error which stopped the parse, a rewind exceeding the backtrack
+    /// limit or a visitor failing unrecoverably.
+    stopped: Option<ParseError>,
+}
+
+/// The state of a [RuleContext] when a rule started.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMark {
+    start: usize,
+    offset: usize,
+    len: usize,
+    silent: bool,
+}
+
+impl RuleContext {
+    /// Create a context for a parse starting at `offset`.
+    pub fn new(offset: usize) -> Self {
+        RuleContext {
+            offset,
+            expected: vec![],
+            silent: 0,
+            stopped: None,
+        }
+    }
+
+    /// Whether the parse has been stopped by an error which can't be
+    /// recovered from, see [ParseError::is_unrecoverable].
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.is_some()
+    }
+
+    /// Move the cursor back to `position`, stopping the parse if the rewind
+    /// exceeds the backtrack limit, see [Scanner::rewind_to].
+    pub fn rewind(&mut self, scanner: &mut Scanner<u8>, position: usize) {
+        if let Err(err) = scanner.rewind_to(position) {
+            self.stopped.get_or_insert(err);
+        }
+    }
+
+    /// Record the failure of an element at `offset`.
+    pub fn fail(&mut self, offset: usize, name: &'static str) {
+        if self.silent > 0 {
+            return;
+        }
+        if offset > self.offset {
+            self.offset = offset;
+            self.expected.clear();
+        }
+        if offset == self.offset && !self.expected.contains(&name) {
+            self.expected.push(name);
+        }
+    }
+
+    /// Match a literal at the cursor and consume it.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to match against.
+    /// * `pattern` - The bytes to match exactly.
+    /// * `name` - The name of the literal, used in errors.
+    ///
+    /// # Returns
+    ///
+    /// true if the literal has been consumed, false otherwise.
+    pub fn literal(
+        &mut self,
+        scanner: &mut Scanner<u8>,
+        pattern: &[u8],
+        name: &'static str,
+    ) -> bool {
+        if self.is_stopped() {
+            return false;
+        }
+        if scanner.remaining().starts_with(pattern) {
+            scanner.bump_by(pattern.len());
+            return true;
+        }
+        self.fail(scanner.current_position(), name);
+        false
+    }
+
+    /// Match a byte between `start` and `end` included and consume it.
+    pub fn range(
+        &mut self,
+        scanner: &mut Scanner<u8>,
+        start: u8,
+        end: u8,
+        name: &'static str,
+    ) -> bool {
+        if self.is_stopped() {
+            return false;
+        }
+        match scanner.remaining().first() {
+            Some(byte) if (start..=end).contains(byte) => {
+                scanner.bump_by(1);
+                true
+            }
+            _ => {
+                self.fail(scanner.current_position(), name);
+                false
+            }
+        }
+    }
+
+    /// Consume any byte.
+    pub fn any(&mut self, scanner: &mut Scanner<u8>) -> bool {
+        if self.is_stopped() {
+            return false;
+        }
+        if scanner.is_empty() {
+            self.fail(scanner.current_position(), "any byte");
+            return false;
+        }
+        scanner.bump_by(1);
+        true
+    }
+
+    /// Run a visitor at the cursor, dropping its value.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to visit.
+    /// * `name` - The name of the visitor, used in errors.
+    ///
+    /// # Returns
+    ///
+    /// true if the visitor succeeded, false otherwise, the cursor is then
+    /// moved back to its position before the visit.
+    pub fn visitor<'a, V: Visitor<'a, u8>>(
+        &mut self,
+        scanner: &mut Scanner<'a, u8>,
+        name: &'static str,
+    ) -> bool {
+        if self.is_stopped() {
+            return false;
+        }
+        let start = scanner.current_position();
+        match V::accept(scanner) {
+            Ok(_) => true,
+            Err(err) => {
+                self.rewind(scanner, start);
+                if err.is_unrecoverable() {
+                    self.stopped.get_or_insert(err);
+                } else {
+                    self.fail(err.offset().unwrap_or(start), name);
+                }
+                false
+            }
+        }
+    }
+
+    /// Mark the start of a rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner of the parse.
+    /// * `silent` - Whether the failures of the elements of the rule are
+    ///   ignored, the rule being reported under its own name when it fails.
+    pub fn enter(&mut self, scanner: &Scanner<u8>, silent: bool) -> RuleMark {
+        if silent {
+            self.silent += 1;
+        }
+        RuleMark {
+            start: scanner.current_position(),
+            offset: self.offset,
+            len: self.expected.len(),
+            silent,
+        }
+    }
+
+    /// Mark the end of a rule.
+    ///
+    /// A rule failing without any of its elements getting further than its
+    /// start is reported under its own name instead of the names of its
+    /// elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner of the parse, moved back to the start of
+    ///   the rule on failure.
+    /// * `mark` - The mark returned by [RuleContext::enter].
+    /// * `matched` - Whether the rule succeeded.
+    /// * `name` - The name of the rule.
+    ///
+    /// # Returns
+    ///
+    /// `matched`
+    pub fn leave(
+        &mut self,
+        scanner: &mut Scanner<u8>,
+        mark: RuleMark,
+        matched: bool,
+        name: &'static str,
+    ) -> bool {
+        if mark.silent {
+            self.silent -= 1;
+        }
+        if matched {
+            return true;
+        }
+        self.rewind(scanner, mark.start);
+        if mark.silent {
+            self.fail(mark.start, name);
+        } else if self.offset == mark.start {
+            if mark.offset == mark.start {
+                self.expected.truncate(mark.len);
+            } else {
+                self.expected.clear();
+            }
+            self.fail(mark.start, name);
+        }
+        false
+    }
+
+    /// Build the error listing the elements failing the furthest, or the
+    /// error which stopped the parse.
+    pub fn into_error(self) -> ParseError {
+        if let Some(err) = self.stopped {
+            return err;
+        }
+        ParseError::Expected {
+            offset: self.offset,
+            expected: self.expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    crate::grammar! {
+        /// A list of numbers between brackets.
+        List = "[" Spaces (Item ("," Spaces Item)*)? "]" !.;
+        Item = Number<usize> Spaces / Word Spaces;
+        Word = ('a'..='z')+;
+        #[silent]
+        Spaces = " "*;
+    }
+
+    fn parse(data: &[u8]) -> Result<usize, ParseError> {
+        let mut scanner = Scanner::new(data);
+        List::accept(&mut scanner).map(|list| list.data.len())
+    }
+
+    #[test]
+    fn test_grammar() {
+        assert_eq!(parse(b"[]").expect("failed to parse"), 2);
+        assert_eq!(parse(b"[ 1, abc , 23 ]").expect("failed to parse"), 15);
+
+        let mut scanner = Scanner::new(b"x, 1");
+        let word = Word::accept(&mut scanner).expect("failed to parse");
+        assert_eq!((word.offset, word.data), (0, &b"x"[..]));
+        assert_eq!(scanner.current_position(), 1);
+    }
+
+    #[test]
+    fn test_grammar_errors() {
+        // the rule name is reported when the rule fails at its start
+        let err = parse(b"[1, ]").expect_err("should fail");
+        assert!(
+            matches!(err, ParseError::Expected { offset: 4, ref expected } if expected == &["Item"])
+        );
+        assert_eq!(err.to_string(), "Expected one of Item at offset 4");
+
+        let err = parse(b"[1 2]").expect_err("should fail");
+        assert!(
+            matches!(err, ParseError::Expected { offset: 3, ref expected } if expected == &["\",\"", "\"]\""])
+        );
+
+        let err = parse(b"[1] x").expect_err("should fail");
+        assert!(
+            matches!(err, ParseError::Expected { offset: 3, ref expected } if expected == &["end of input"])
+        );
+
+        let mut scanner = Scanner::new(b"[1");
+        assert!(List::accept(&mut scanner).is_err());
+        assert_eq!(scanner.current_position(), 0);
+
+        // a rewind exceeding the backtrack limit stops the parse
+        let mut scanner = Scanner::new(b"[ 1, abc , 23 x");
+        scanner.set_backtrack_limit(4);
+        assert!(matches!(
+            List::accept(&mut scanner),
+            Err(ParseError::BacktrackLimitExceeded { .. })
+        ));
+
+        // so do the errors of the visitors which can't be recovered from
+        let mut scanner = Scanner::streaming(b"[1");
+        assert!(matches!(
+            List::accept(&mut scanner),
+            Err(ParseError::Incomplete { .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        let mut scanner = Scanner::new(b"[1]");
+        scanner.set_fuel(0);
+        assert!(matches!(
+            List::accept(&mut scanner),
+            Err(ParseError::BudgetExhausted { .. })
+        ));
+    }
+}
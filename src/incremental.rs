@@ -0,0 +1,277 @@
+//! Re-parse a document after an edit, visiting only the edited region.
+//!
+//! A document is a sequence of top-level items, each accepted where the
+//! previous one ends until the data is exhausted. [Incremental] records the
+//! span of every item; [Incremental::apply_edit] drops the items touched by an
+//! edit and shifts the following ones, then [Incremental::reparse] visits the
+//! data again from the first dropped item until it lands back on the start of
+//! a kept one.
+//!
+//! The result is the same as a full parse as long as an item only depends on
+//! the data from its start to the element following its span, which is the
+//! case of visitors matching forward.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Spanned;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// A lazy iterator over the items of a scanner, along with their span.
+///
+/// The iterator stops at the end of the input or after the first error.
+pub struct Items<'a, 'b, T, V> {
+    scanner: &'b mut Scanner<'a, T>,
+    done: bool,
+    _marker: PhantomData<V>,
+}
+
+impl<'a, 'b, T, V> Items<'a, 'b, T, V> {
+    /// Create a new iterator over the items of the scanner.
+    pub fn new(scanner: &'b mut Scanner<'a, T>) -> Self {
+        Items {
+            scanner,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, V: Visitor<'a, T>> Iterator for Items<'a, '_, T, V> {
+    type Item = ParseResult<Spanned<V>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.scanner.is_empty() {
+            return None;
+        }
+        let result = accept_item(self.scanner);
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+/// Accept an item, which must consume data not to loop forever.
+fn accept_item<'a, T, V: Visitor<'a, T>>(scanner: &mut Scanner<'a, T>) -> ParseResult<Spanned<V>> {
+    let item = Spanned::<V>::accept(scanner)?;
+    if item.span.is_empty() {
+        return Err(ParseError::NoProgress {
+            offset: item.span.start,
+        });
+    }
+    Ok(item)
+}
+
+/// The items of a document, kept up to date with the edits of its data.
+///
+/// The items own their value, so that they outlive the data they have been
+/// accepted from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incremental<V> {
+    items: Vec<Spanned<V>>,
+}
+
+impl<V> Incremental<V> {
+    /// Parse the whole data.
+    ///
+    /// # Errors
+    ///
+    /// The error of the first item failing.
+    pub fn parse<T>(data: &[T]) -> ParseResult<Self>
+    where
+        V: for<'a> Visitor<'a, T>,
+    {
+        let mut scanner = Scanner::new(data);
+        let items = Items::new(&mut scanner).collect::<ParseResult<_>>()?;
+        Ok(Incremental { items })
+    }
+
+    /// Return the items of the last parse, minus the ones dropped by edits.
+    pub fn items(&self) -> &[Spanned<V>] {
+        &self.items
+    }
+
+    /// Record an edit of the data.
+    ///
+    /// The items overlapping the edited range, or ending right at its start,
+    /// are dropped, the items following it are shifted by the change of
+    /// length. Several edits can be applied before calling
+    /// [Incremental::reparse].
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the data replaced, in the data before the
+    ///   edit.
+    /// * `new_len` - The length of the replacement.
+    pub fn apply_edit(&mut self, range: Range<usize>, new_len: usize) {
+        let end = range.start + new_len;
+        self.items.retain_mut(|item| {
+            if item.span.end < range.start {
+                return true;
+            }
+            if item.span.start < range.end {
+                return false;
+            }
+            item.span = item.span.start - range.end + end..item.span.end - range.end + end;
+            true
+        });
+    }
+
+    /// Visit the regions left without items by the edits.
+    ///
+    /// The kept items are spliced back as soon as the parse reaches their
+    /// start, the ones skipped over by a re-parsed item are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data after the edits.
+    ///
+    /// # Errors
+    ///
+    /// The error of the first item failing, the same as [Incremental::parse]
+    /// would return. The items are then all dropped, so that the next call
+    /// parses the whole data.
+    pub fn reparse<T>(&mut self, data: &[T]) -> ParseResult<()>
+    where
+        V: for<'a> Visitor<'a, T>,
+    {
+        let mut scanner = Scanner::new(data);
+        let mut kept = std::mem::take(&mut self.items).into_iter().peekable();
+        let mut items = vec![];
+
+        while !scanner.is_empty() {
+            let position = scanner.current_position();
+            match kept.next_if(|item| item.span.start <= position) {
+                Some(item) if item.span.start == position => {
+                    scanner.jump_to(item.span.end);
+                    items.push(item);
+                }
+                // skipped over by a re-parsed item
+                Some(_) => {}
+                None => items.push(accept_item(&mut scanner)?),
+            }
+        }
+        self.items = items;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::incremental::Incremental;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    /// A number followed by an optional `,`, a word followed by spaces, or
+    /// the spaces and commas in between.
+    #[derive(Debug, Clone, PartialEq)]
+    enum Entry {
+        Number(usize),
+        Word(String),
+        Blank,
+    }
+
+    impl Visitor<'_, u8> for Entry {
+        fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+            if let Ok(Number(number)) = scanner.rollback_on_err(Number::accept) {
+                scanner.eat(Token::Comma);
+                return Ok(Entry::Number(number));
+            }
+            let word = scanner
+                .remaining()
+                .iter()
+                .take_while(|byte| byte.is_ascii_lowercase())
+                .count();
+            if word > 0 {
+                let value = String::from_utf8_lossy(&scanner.remaining()[..word]).to_string();
+                scanner.bump_by(word);
+                while scanner.eat(Token::Whitespace) {}
+                return Ok(Entry::Word(value));
+            }
+            let mut blank = false;
+            while scanner.eat(Token::Whitespace) || scanner.eat(Token::Comma) {
+                blank = true;
+            }
+            if !blank {
+                return Err(ParseError::UnexpectedToken);
+            }
+            Ok(Entry::Blank)
+        }
+    }
+
+    fn full(data: &[u8]) -> Result<Incremental<Entry>, String> {
+        Incremental::parse(data).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut data = b"1,ab 23,cd".to_vec();
+        let mut document = Incremental::<Entry>::parse(&data).expect("failed to parse");
+        assert_eq!(document.items().len(), 4);
+
+        // "1,ab 234,cd", the number is dropped and re-parsed, `cd` shifted
+        data.insert(7, b'4');
+        document.apply_edit(7..7, 1);
+        assert_eq!(document.items().len(), 3);
+        assert_eq!(document.items()[2].span, 9..11);
+        document.reparse(&data).expect("failed to parse");
+        assert_eq!(document.items()[2].value, Entry::Number(234));
+        assert_eq!(Ok(&document), full(&data).as_ref());
+
+        // "1,ab 234!cd" is an error, as is a full parse
+        data[8] = b'!';
+        document.apply_edit(8..9, 1);
+        let err = document.reparse(&data).expect_err("should fail");
+        assert_eq!(Err(err.to_string()), full(&data));
+        assert!(document.items().is_empty());
+
+        data[8] = b',';
+        document.apply_edit(8..9, 1);
+        document.reparse(&data).expect("failed to parse");
+        assert_eq!(Ok(&document), full(&data).as_ref());
+    }
+
+    #[test]
+    fn test_incremental_random_edits() {
+        const ALPHABET: &[u8] = b"0123456789ab, ,a1!";
+
+        // a small linear congruential generator, deterministic
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut random = |bound: usize| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) as usize % bound
+        };
+
+        for _ in 0..50 {
+            let mut data = b"12,ab cd 3,45,e 6,".to_vec();
+            let mut document = Incremental::<Entry>::parse(&data).expect("failed to parse");
+            for _ in 0..40 {
+                // one to three edits between two parses
+                for _ in 0..random(3) + 1 {
+                    let start = random(data.len() + 1);
+                    let end = (start + random(3)).min(data.len());
+                    let replacement = (0..random(3))
+                        .map(|_| ALPHABET[random(ALPHABET.len())])
+                        .collect::<Vec<_>>();
+                    document.apply_edit(start..end, replacement.len());
+                    data.splice(start..end, replacement);
+                }
+                let result = document.reparse(&data).map_err(|err| err.to_string());
+                let expected = full(&data);
+                assert_eq!(
+                    result.map(|_| &document),
+                    expected.as_ref().map_err(String::clone),
+                    "{:?}",
+                    String::from_utf8_lossy(&data)
+                );
+            }
+        }
+    }
+}
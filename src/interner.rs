@@ -0,0 +1,180 @@
+//! Intern the names recognized in the data.
+//!
+//! An [Interner] stores every distinct name once and hands out a [Symbol],
+//! a 4 bytes index, for each of its occurrences. An AST holding a `String`
+//! per identifier costs 24 bytes plus a heap allocation for every
+//! occurrence, while interning a few hundred names repeated a million times
+//! costs 4 MB of ids plus the few hundred names.
+//!
+//! The interner is passed along the scanner to the visitors which need it:
+//!
+//! ```
+//! use noa_parser::bytes::primitives::identifier::TokenIdentifier;
+//! use noa_parser::interner::{Interner, recognize_interned};
+//! use noa_parser::scanner::Scanner;
+//!
+//! let mut interner = Interner::new();
+//! let mut scanner = Scanner::new(b"x");
+//! let id = recognize_interned(TokenIdentifier, &mut scanner, &mut interner).unwrap();
+//! assert_eq!(interner.resolve(id), "x");
+//! ```
+
+use crate::errors::ParseResult;
+use crate::recognizer::{Recognizable, recognize};
+use crate::scanner::Scanner;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The index of a name in an [Interner].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Return the index of the name, the names of an interner being
+    /// numbered from 0 in their order of interning.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Store the names once, giving them a [Symbol].
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    /// The names by id, sharing their allocation with the keys of `ids`.
+    names: Vec<Rc<str>>,
+    ids: HashMap<Rc<str>, Symbol>,
+}
+
+impl Interner {
+    /// Create an interner holding no name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to intern, the invalid UTF-8 sequences being
+    ///   replaced by `U+FFFD`, see [recognize_interned] to reject them.
+    ///
+    /// # Returns
+    ///
+    /// The id of the name, the same for every occurrence of the name.
+    pub fn intern(&mut self, name: &[u8]) -> Symbol {
+        let name = String::from_utf8_lossy(name);
+        if let Some(&id) = self.ids.get(name.as_ref()) {
+            return id;
+        }
+        let id =
+            Symbol(u32::try_from(self.names.len()).expect("more than u32::MAX names interned"));
+        let name: Rc<str> = name.into();
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        id
+    }
+
+    /// Return the name of an id.
+    ///
+    /// # Panics
+    ///
+    /// If the id comes from another interner holding more names.
+    pub fn resolve(&self, id: Symbol) -> &str {
+        &self.names[id.index()]
+    }
+
+    /// Return the number of distinct names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Return true if no name has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Recognize an object and intern it.
+///
+/// # Arguments
+/// * `recognizable` - The object to recognize
+/// * `scanner` - The scanner to recognize the object for
+/// * `interner` - The interner of the names
+///
+/// # Returns
+/// * `Ok(Symbol)` the id of the recognized name,
+/// * `Err(ParseError::Utf8Error)` if the recognized bytes are not valid
+///   UTF-8, the scanner is then rewound to its position before the object,
+/// * the errors of [recognize] otherwise.
+pub fn recognize_interned<'a, R: Recognizable<'a, u8, &'a [u8]>>(
+    recognizable: R,
+    scanner: &mut Scanner<'a, u8>,
+    interner: &mut Interner,
+) -> ParseResult<Symbol> {
+    scanner.rollback_on_err(|scanner| {
+        let data = recognize(recognizable, scanner)?;
+        std::str::from_utf8(data)?;
+        Ok(interner.intern(data))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::identifier::TokenIdentifier;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::interner::{Interner, recognize_interned};
+    use crate::scanner::Scanner;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        let x = interner.intern(b"x");
+        let y = interner.intern(b"y");
+        assert_eq!(interner.intern(b"x"), x);
+        assert_ne!(x, y);
+        assert_eq!((x.index(), y.index()), (0, 1));
+        assert_eq!(interner.resolve(x), "x");
+        assert_eq!(interner.resolve(y), "y");
+        assert_eq!(interner.len(), 2);
+
+        let mut counts = HashMap::new();
+        for id in [x, y, x] {
+            *counts.entry(id).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&x], 2);
+    }
+
+    #[test]
+    fn test_recognize_interned() {
+        let mut interner = Interner::new();
+        let mut scanner = Scanner::new(b"a,b,a");
+        let mut ids = vec![];
+        loop {
+            ids.push(
+                recognize_interned(TokenIdentifier, &mut scanner, &mut interner)
+                    .expect("failed to parse"),
+            );
+            if !scanner.eat(Token::Comma) {
+                break;
+            }
+        }
+        assert_eq!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(interner.resolve(ids[1]), "b");
+        assert_eq!(interner.len(), 2);
+
+        let mut scanner = Scanner::new(b"\xff\xfe");
+        assert!(matches!(
+            recognize_interned(Token::OpenParen, &mut scanner, &mut interner),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert!(matches!(
+            recognize_interned(Some(0xff_u8), &mut scanner, &mut interner),
+            Err(ParseError::Utf8Error(_))
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
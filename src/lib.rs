@@ -1,11 +1,116 @@
 #![doc = include_str!("../Readme.md")]
+// allow the derive macros to refer to `::noa_parser` from inside the crate
+extern crate self as noa_parser;
+
 pub mod acceptor;
 pub mod bytes;
+pub mod chars;
+pub mod debug_tree;
+pub mod describe;
+pub mod dispatch;
 pub mod errors;
+pub mod formats;
+#[cfg(feature = "grammar")]
+pub mod grammar;
+pub mod incremental;
+pub mod interner;
+mod macros;
 pub mod matcher;
 pub mod peek;
 pub mod peeker;
 pub mod recognizer;
 pub mod scanner;
 pub mod separated_list;
+pub mod stream;
+pub mod symbol;
+mod trace;
 pub mod visitor;
+
+/// Derive `Match<u8>` and `MatchSize` for a literal pattern.
+///
+/// ```
+/// use noa_parser::Match;
+/// use noa_parser::matcher::{Match as _, MatchSize};
+///
+/// #[derive(Match)]
+/// #[pattern("select")]
+/// #[no_case]
+/// #[keyword]
+/// struct Select;
+///
+/// assert_eq!(Select.matcher(b"SELECT *"), (true, 6));
+/// assert_eq!(Select.matcher(b"selection"), (false, 0));
+/// assert_eq!(Select.size(), 6);
+/// ```
+///
+/// The pattern attribute is required, and must be a non empty string or byte
+/// string.
+#[cfg(feature = "derive")]
+pub use noa_parser_derive::Match;
+
+/// Derive `Visitor<'a, u8>` for a struct whose fields are visitors, accepted
+/// in their declaration order.
+///
+/// ```
+/// use noa_parser::Visitor;
+/// use noa_parser::bytes::primitives::number::Number;
+/// use noa_parser::bytes::token::Token;
+/// use noa_parser::scanner::Scanner;
+/// use noa_parser::visitor::Visitor as _;
+///
+/// #[derive(Visitor)]
+/// #[separator(Token::Comma)]
+/// struct Point {
+///     x: Number<usize>,
+///     y: Number<usize>,
+/// }
+///
+/// let point = Point::accept(&mut Scanner::new(b"3,4")).expect("failed to parse");
+/// assert_eq!((point.x.0, point.y.0), (3, 4));
+/// ```
+///
+/// Only structs can be derived:
+///
+/// ```compile_fail
+/// #[derive(noa_parser::Visitor)]
+/// enum Sign {
+///     Plus,
+///     Minus,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use noa_parser_derive::Visitor;
+
+/// Define `Visitor` types from PEG rules.
+///
+/// Each rule `Name = expression;` defines a `Name<'a>` struct holding the
+/// recognized data and its offset. Rules may refer to each other, the
+/// recursion being left-free, and to any type implementing `Visitor<'a, u8>`.
+///
+/// ```
+/// use noa_parser::bytes::primitives::number::Number;
+/// use noa_parser::scanner::Scanner;
+/// use noa_parser::visitor::Visitor;
+///
+/// noa_parser::grammar! {
+///     Sum = Number<usize> (("+" / "-") Number<usize>)* !.;
+/// }
+///
+/// let mut scanner = Scanner::new(b"1+2-3");
+/// assert_eq!(Sum::accept(&mut scanner).expect("failed to parse").data, b"1+2-3");
+///
+/// let mut scanner = Scanner::new(b"1+");
+/// let err = Sum::accept(&mut scanner).expect_err("should fail");
+/// assert_eq!(err.to_string(), "Expected one of Number<usize> at offset 2");
+/// ```
+///
+/// The rules must be separated by `;`:
+///
+/// ```compile_fail
+/// noa_parser::grammar! {
+///     A = "a"
+///     B = "b";
+/// }
+/// ```
+#[cfg(feature = "grammar")]
+pub use noa_parser_derive::grammar;
@@ -0,0 +1,560 @@
+//! Macros generating matchers and visitors.
+
+/// Define an enum of keywords along with a matcher recognizing them.
+///
+/// The matcher ignores case, requires the keyword not to be followed by an
+/// identifier character and picks the longest keyword when several match.
+/// It returns whether the match succeeded, the number of bytes consumed and
+/// the matched variant, the variant being the first one when the match fails.
+///
+/// The enum also implements `Match<u8>` and `MatchSize`, each variant
+/// matching its keyword, so that the keywords can be recognized from a
+/// [crate::stream::StreamScanner] too.
+///
+/// ```
+/// use noa_parser::keyword_enum;
+///
+/// keyword_enum! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Kind => match_kind {
+///         If = "if",
+///         Else = "else",
+///     }
+/// }
+///
+/// assert_eq!(match_kind(b"else {"), (true, 4, Kind::Else));
+/// assert!(!match_kind(b"elsewhere").0);
+/// ```
+#[macro_export]
+macro_rules! keyword_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident => $matcher:ident {
+            $first:ident = $first_keyword:literal
+            $(, $variant:ident = $keyword:literal)* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $first,
+            $($variant,)*
+        }
+
+        #[doc = concat!("Match the longest `", stringify!($name), "` keyword against a byte slice.")]
+        $vis fn $matcher(data: &[u8]) -> (bool, usize, $name) {
+            let mut longest = (false, 0, $name::$first);
+            let keywords = [
+                ($first_keyword, $name::$first),
+                $(($keyword, $name::$variant),)*
+            ];
+            for (keyword, kind) in keywords {
+                let (found, size) = $crate::bytes::matchers::match_keyword(keyword.as_bytes(), data);
+                if found && size > longest.1 {
+                    longest = (true, size, kind);
+                }
+            }
+            longest
+        }
+
+        impl $name {
+            /// The keyword of the variant.
+            $vis fn keyword(&self) -> &'static str {
+                match self {
+                    $name::$first => $first_keyword,
+                    $($name::$variant => $keyword,)*
+                }
+            }
+        }
+
+        impl $crate::matcher::Match<u8> for $name {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                $crate::bytes::matchers::match_keyword(self.keyword().as_bytes(), data)
+            }
+
+            fn matcher2(&self, data: &[u8]) -> $crate::matcher::MatchResult {
+                $crate::bytes::matchers::match_keyword_streaming(self.keyword().as_bytes(), data)
+            }
+        }
+
+        impl $crate::matcher::MatchSize for $name {
+            fn size(&self) -> usize {
+                self.keyword().len()
+            }
+        }
+    };
+}
+
+/// Define an enum of tokens along with its `Match<u8>`, `MatchSize`,
+/// `Display` and `Describe` implementations.
+///
+/// Each token is a variant associated to its literal, the literal is
+/// matched exactly unless prefixed by `keyword`, in which case it is matched
+/// as [crate::bytes::matchers::match_keyword] does.
+///
+/// ```
+/// use noa_parser::recognizer::recognize;
+/// use noa_parser::scanner::Scanner;
+/// use noa_parser::tokens;
+///
+/// tokens! {
+///     pub MyToken:
+///         Arrow => "->",
+///         Let => keyword "let",
+/// }
+///
+/// let mut scanner = Scanner::new(b"->let");
+/// assert_eq!(recognize(MyToken::Arrow, &mut scanner).expect("failed to parse"), b"->");
+/// assert_eq!(MyToken::Let.to_string(), "let");
+/// ```
+#[macro_export]
+macro_rules! tokens {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident:
+        $($variant:ident => $($kind:ident)? $literal:literal),+ $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant,)+
+        }
+
+        impl $name {
+            /// The literal of the token.
+            $vis fn literal(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $literal,)+
+                }
+            }
+        }
+
+        impl $crate::matcher::Match<u8> for $name {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                match self {
+                    $($name::$variant => $crate::tokens!(@matcher $($kind)? $literal, data),)+
+                }
+            }
+
+            fn matcher2(&self, data: &[u8]) -> $crate::matcher::MatchResult {
+                match self {
+                    $($name::$variant => $crate::tokens!(@matcher2 $($kind)? $literal, data),)+
+                }
+            }
+        }
+
+        impl $crate::matcher::MatchSize for $name {
+            fn size(&self) -> usize {
+                self.literal().len()
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.literal())
+            }
+        }
+
+        impl $crate::describe::Describe for $name {
+            fn expected(&self) -> $crate::describe::Expectation {
+                $crate::describe::Expectation::Literal(self.literal().into())
+            }
+        }
+    };
+    (@matcher keyword $literal:literal, $data:ident) => {
+        $crate::bytes::matchers::match_keyword($literal.as_bytes(), $data)
+    };
+    (@matcher $literal:literal, $data:ident) => {
+        if $data.starts_with($literal.as_bytes()) {
+            (true, $literal.len())
+        } else {
+            (false, 0)
+        }
+    };
+    (@matcher2 keyword $literal:literal, $data:ident) => {
+        $crate::bytes::matchers::match_keyword_streaming($literal.as_bytes(), $data)
+    };
+    (@matcher2 $literal:literal, $data:ident) => {
+        $crate::bytes::matchers::match_literal_streaming($literal.as_bytes(), $data)
+    };
+}
+
+/// Recognize a sequence of elements, binding the interesting ones.
+///
+/// Each step is either `name: Type`, accepting the `Type` visitor, or
+/// `name = recognizable`, recognizing the object as `recognize` does. Use
+/// `_` as name to drop the result of a step. The names are bound in the
+/// enclosing scope. A visitor step may destructure its value instead of
+/// naming it, `Number(lhs): Number<usize>` binding the number itself.
+///
+/// A recognizable step is written with `=` rather than `_: Token::Plus`:
+/// a macro can't tell a type such as `Token` from a value such as
+/// `Token::Plus`, so `:` is kept for the visitor types.
+///
+/// If a step fails, the scanner is moved back to its position before the
+/// sequence and the error is returned from the enclosing function. An
+/// unexpected token is reported as a `ParseError::Expected` at the offset
+/// where the failing step started.
+///
+/// ```
+/// use noa_parser::bytes::primitives::number::Number;
+/// use noa_parser::bytes::token::Token;
+/// use noa_parser::errors::ParseResult;
+/// use noa_parser::scanner::Scanner;
+/// use noa_parser::seq;
+///
+/// fn addition(scanner: &mut Scanner<u8>) -> ParseResult<usize> {
+///     seq!(scanner => Number(lhs): Number<usize>, _ = Token::Plus, Number(rhs): Number<usize>);
+///     Ok(lhs + rhs)
+/// }
+///
+/// let mut scanner = Scanner::new(b"1+2");
+/// assert_eq!(addition(&mut scanner).expect("failed to parse"), 3);
+///
+/// let mut scanner = Scanner::new(b"1-2");
+/// assert!(addition(&mut scanner).is_err());
+/// assert_eq!(scanner.current_position(), 0);
+/// ```
+#[macro_export]
+macro_rules! seq {
+    ($scanner:expr => $($steps:tt)+) => {
+        let cursor = $scanner.current_position();
+        $crate::seq!(@step $scanner, cursor, $($steps)+);
+    };
+    (@step $scanner:expr, $cursor:ident, $name:tt : $visitor:ty $(, $($rest:tt)*)?) => {
+        $crate::seq!(@visit $scanner, $cursor, ($name), $visitor; $($($rest)*)?);
+    };
+    (@step $scanner:expr, $cursor:ident,
+        $type:ident ( $($fields:tt)* ) : $visitor:ty $(, $($rest:tt)*)?) => {
+        $crate::seq!(@visit $scanner, $cursor, ($type($($fields)*)), $visitor; $($($rest)*)?);
+    };
+    (@step $scanner:expr, $cursor:ident,
+        $type:ident { $($fields:tt)* } : $visitor:ty $(, $($rest:tt)*)?) => {
+        $crate::seq!(@visit $scanner, $cursor, ($type { $($fields)* }), $visitor; $($($rest)*)?);
+    };
+    (@visit $scanner:expr, $cursor:ident, ($($pattern:tt)*), $visitor:ty; $($rest:tt)*) => {
+        let start = $scanner.current_position();
+        let $($pattern)* = match <$visitor as $crate::visitor::Visitor<_>>::accept($scanner) {
+            Ok(value) => value,
+            Err(err) => {
+                $scanner.rewind_to($cursor)?;
+                return Err(err.positioned(start, stringify!($visitor)).into());
+            }
+        };
+        $crate::seq!(@step $scanner, $cursor, $($rest)*);
+    };
+    (@step $scanner:expr, $cursor:ident, $name:tt = $recognizable:expr $(, $($rest:tt)*)?) => {
+        let start = $scanner.current_position();
+        let $name = match $crate::recognizer::recognize($recognizable, $scanner) {
+            Ok(value) => value,
+            Err(err) => {
+                $scanner.rewind_to($cursor)?;
+                return Err(err.positioned(start, stringify!($recognizable)).into());
+            }
+        };
+        $($crate::seq!(@step $scanner, $cursor, $($rest)*);)?
+    };
+    (@step $scanner:expr, $cursor:ident,) => {};
+}
+
+/// Try alternatives in order from the same position, returning the first
+/// success.
+///
+/// Each branch is either a `Type` accepting the visitor or `= recognizable`
+/// recognizing the object, optionally followed by `=> mapping` to convert
+/// the result, so that all branches return the same type. The scanner is
+/// moved back to its starting position between the attempts.
+///
+/// If no branch succeeds, a `ParseError::Expected` lists the branches which
+/// failed the furthest. An error which can't be recovered from, see
+/// [crate::errors::ParseError::is_unrecoverable], is returned at once.
+///
+/// ```
+/// use noa_parser::alt;
+/// use noa_parser::bytes::primitives::number::Number;
+/// use noa_parser::bytes::token::Token;
+/// use noa_parser::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"+12");
+/// let sign = alt!(&mut scanner => = Token::Plus => |_| 1, = Token::Dash => |_| -1);
+/// assert_eq!(sign.expect("failed to parse"), 1);
+/// let number = alt!(&mut scanner => Number<u8> => |n: Number<u8>| n.0 as usize);
+/// assert_eq!(number.expect("failed to parse"), 12);
+/// ```
+#[macro_export]
+macro_rules! alt {
+    ($scanner:expr => $($branches:tt)+) => {{
+        let scanner = $scanner;
+        let cursor = scanner.current_position();
+        let mut failures = $crate::errors::FurthestFailure::new(cursor);
+        'alt: {
+            $crate::alt!(@branch 'alt, scanner, cursor, failures, $($branches)+);
+            Err(failures.into_error())
+        }
+    }};
+    (@branch $label:lifetime, $scanner:ident, $cursor:ident, $failures:ident,
+        = $recognizable:expr $(=> $map:expr)? $(, $($rest:tt)*)?) => {
+        match $crate::recognizer::recognize($recognizable, $scanner) {
+            Ok(value) => break $label Ok($crate::alt!(@map value $(, $map)?)),
+            Err(err) => {
+                if let Err(limit) = $scanner.rewind_to($cursor) {
+                    break $label Err(limit);
+                }
+                if err.is_unrecoverable() {
+                    break $label Err(err);
+                }
+                $failures.record(&err, $cursor, stringify!($recognizable));
+            }
+        }
+        $($crate::alt!(@branch $label, $scanner, $cursor, $failures, $($rest)*);)?
+    };
+    (@branch $label:lifetime, $scanner:ident, $cursor:ident, $failures:ident,
+        $visitor:ty $(=> $map:expr)? $(, $($rest:tt)*)?) => {
+        match <$visitor as $crate::visitor::Visitor<_>>::accept($scanner) {
+            Ok(value) => break $label Ok($crate::alt!(@map value $(, $map)?)),
+            Err(err) => {
+                if let Err(limit) = $scanner.rewind_to($cursor) {
+                    break $label Err(limit);
+                }
+                if err.is_unrecoverable() {
+                    break $label Err(err);
+                }
+                $failures.record(&err, $cursor, stringify!($visitor));
+            }
+        }
+        $($crate::alt!(@branch $label, $scanner, $cursor, $failures, $($rest)*);)?
+    };
+    (@branch $label:lifetime, $scanner:ident, $cursor:ident, $failures:ident,) => {};
+    (@map $value:ident) => {
+        $value
+    };
+    (@map $value:ident, $map:expr) => {
+        ($map)($value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    keyword_enum! {
+        #[derive(Debug, PartialEq)]
+        enum Kind => match_kind {
+            If = "if",
+            Else = "else",
+            ElseIf = "else if",
+            While = "while",
+        }
+    }
+
+    #[test]
+    fn test_keyword_enum() {
+        assert_eq!(match_kind(b"else "), (true, 4, Kind::Else));
+        assert_eq!(match_kind(b"IF x"), (true, 2, Kind::If));
+        assert_eq!(match_kind(b"else if x"), (true, 7, Kind::ElseIf));
+        assert_eq!(match_kind(b"while"), (true, 5, Kind::While));
+        assert!(!match_kind(b"iffy").0);
+        assert!(!match_kind(b"loop").0);
+
+        // each variant matches its keyword
+        use crate::matcher::{Match, MatchResult, MatchSize, Needed};
+        assert_eq!(Kind::Else.matcher(b"else {"), (true, 4));
+        assert_eq!(Kind::Else.matcher(b"elsewhere"), (false, 0));
+        assert_eq!(Kind::While.size(), 5);
+        assert_eq!(
+            Kind::Else.matcher2(b"els"),
+            MatchResult::Incomplete(Needed::Size(2))
+        );
+        assert_eq!(Kind::Else.matcher2(b"else {"), MatchResult::Matched(4));
+    }
+
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::{Recognizer, recognize};
+    use crate::scanner::Scanner;
+    use crate::visitor::Spanned;
+
+    tokens! {
+        /// Tokens of a tiny functional language.
+        MyToken:
+            FatArrow => "=>",
+            Arrow => "->",
+            Pipe => "|",
+            Let => keyword "let",
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut scanner = Scanner::new(b"let|->=>letter");
+        let mut tokens = vec![];
+        while !scanner.is_empty() {
+            let token = Recognizer::new(&mut scanner)
+                .try_or(MyToken::FatArrow)
+                .and_then(|recognizer| recognizer.try_or(MyToken::Arrow))
+                .and_then(|recognizer| recognizer.try_or(MyToken::Pipe))
+                .and_then(|recognizer| recognizer.try_or(MyToken::Let))
+                .expect("failed to parse")
+                .finish();
+            match token {
+                Some(token) => tokens.push(token),
+                None => break,
+            }
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                MyToken::Let,
+                MyToken::Pipe,
+                MyToken::Arrow,
+                MyToken::FatArrow
+            ]
+        );
+        // `letter` is not the `let` keyword
+        assert_eq!(scanner.remaining(), b"letter");
+
+        let mut scanner = Scanner::new(b"->");
+        assert_eq!(
+            recognize(MyToken::Arrow, &mut scanner).expect("failed to parse"),
+            b"->"
+        );
+        assert_eq!(MyToken::FatArrow.to_string(), "=>");
+    }
+
+    fn range(scanner: &mut Scanner<u8>) -> ParseResult<(usize, usize)> {
+        seq!(scanner =>
+            _ = Token::OpenBracket,
+            Number(start): Number<usize>,
+            _ = Token::Dot,
+            _ = Token::Dot,
+            Number(end): Number<usize>,
+            _ = Token::CloseBracket,
+        );
+        Ok((start, end))
+    }
+
+    #[test]
+    fn test_seq() {
+        let mut scanner = Scanner::new(b"[1..12]");
+        assert_eq!(range(&mut scanner).expect("failed to parse"), (1, 12));
+        assert!(scanner.is_empty());
+
+        // the error points to the start of the failing step
+        let mut scanner = Scanner::new(b"[1..12;");
+        assert!(matches!(
+            range(&mut scanner),
+            Err(ParseError::Expected { offset: 6, ref expected }) if expected == &["Token::CloseBracket"]
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        let mut scanner = Scanner::new(b"[1.x");
+        assert!(matches!(
+            range(&mut scanner),
+            Err(ParseError::Expected { offset: 3, .. })
+        ));
+
+        // a struct is destructured too
+        let mut scanner = Scanner::new(b"12");
+        let span = (|| {
+            seq!(&mut scanner => Spanned { span, .. }: Spanned<Number<usize>>);
+            ParseResult::Ok(span)
+        })();
+        assert_eq!(span.expect("failed to parse"), 0..2);
+
+        // the rewind is checked against the backtrack limit
+        let mut scanner = Scanner::new(b"[1..12;");
+        scanner.set_backtrack_limit(3);
+        assert!(matches!(
+            range(&mut scanner),
+            Err(ParseError::BacktrackLimitExceeded { offset: 0 })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    /// A visitor failing after having consumed a digit.
+    #[derive(Debug)]
+    struct Range;
+
+    impl<'a> crate::visitor::Visitor<'a, u8> for Range {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            range(scanner)?;
+            Ok(Range)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        Number(usize),
+        Range(usize, usize),
+        Open,
+    }
+
+    fn value(scanner: &mut Scanner<u8>) -> ParseResult<Value> {
+        alt!(scanner =>
+            Number<usize> => |number: Number<usize>| Value::Number(number.0),
+            = Token::OpenBracket => |_| Value::Open,
+        )
+    }
+
+    #[test]
+    fn test_alt() {
+        let mut scanner = Scanner::new(b"42");
+        assert_eq!(
+            value(&mut scanner).expect("failed to parse"),
+            Value::Number(42)
+        );
+        assert!(scanner.is_empty());
+
+        // the first matching branch wins
+        let mut scanner = Scanner::new(b"[1..2]");
+        assert_eq!(value(&mut scanner).expect("failed to parse"), Value::Open);
+        assert_eq!(scanner.current_position(), 1);
+
+        // branches start from the same position
+        let mut scanner = Scanner::new(b"[1..2]");
+        let result = alt!(&mut scanner =>
+            = Token::OpenBracket => |_| Value::Open,
+            Range => |_| Value::Range(1, 2),
+        );
+        assert_eq!(result.expect("failed to parse"), Value::Open);
+        let mut scanner = Scanner::new(b"[1..2]");
+        let result = alt!(&mut scanner =>
+            Number<usize> => |number: Number<usize>| Value::Number(number.0),
+            Range => |_| Value::Range(1, 2),
+        );
+        assert_eq!(result.expect("failed to parse"), Value::Range(1, 2));
+    }
+
+    #[test]
+    fn test_alt_error() {
+        let mut scanner = Scanner::new(b"x");
+        let result = value(&mut scanner);
+        let Err(err) = result else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Expected one of Number<usize>, Token::OpenBracket at offset 0"
+        );
+        assert_eq!(scanner.current_position(), 0);
+
+        let mut scanner = Scanner::new(b"-12");
+        scanner.bump_by(1);
+        let result = alt!(&mut scanner => = Token::Plus, = Token::Dash);
+        assert!(matches!(
+            result,
+            Err(ParseError::Expected { offset: 1, ref expected }) if expected.len() == 2
+        ));
+
+        // the fuel and the streaming are not recovered from
+        let mut scanner = Scanner::new(b"42");
+        scanner.set_fuel(0);
+        assert!(matches!(
+            value(&mut scanner),
+            Err(ParseError::BudgetExhausted { .. })
+        ));
+        let mut scanner = Scanner::streaming(b"12");
+        assert!(matches!(
+            value(&mut scanner),
+            Err(ParseError::Incomplete { .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
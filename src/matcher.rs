@@ -1,5 +1,47 @@
 //! Provides the `Match` trait.
 
+use std::fmt;
+
+/// How much data a matcher needs to decide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Needed {
+    /// The missing length is unknown, a run of digits may go on for example.
+    Unknown,
+    /// At least this many more elements are needed.
+    Size(usize),
+}
+
+impl fmt::Display for Needed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Needed::Unknown => write!(f, "more data"),
+            Needed::Size(size) => write!(f, "{size} more elements"),
+        }
+    }
+}
+
+/// The result of a matcher aware of the end of the available data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchResult {
+    /// The data matches, the match being this long.
+    Matched(usize),
+    /// The data doesn't match.
+    NoMatch,
+    /// The data matches so far, but the match may end further in data not
+    /// available yet.
+    Incomplete(Needed),
+}
+
+/// Convert the result of [Match::matcher].
+impl From<(bool, usize)> for MatchResult {
+    fn from((found, size): (bool, usize)) -> Self {
+        match found {
+            true => MatchResult::Matched(size),
+            false => MatchResult::NoMatch,
+        }
+    }
+}
+
 /// Describes a matchable object.
 pub trait Match<T> {
     /// Returns true if the data matches the pattern.
@@ -11,6 +53,21 @@ pub trait Match<T> {
     /// (true, index) if the data matches the pattern,
     /// (false, index) otherwise
     fn matcher(&self, data: &[T]) -> (bool, usize);
+
+    /// Match the data, telling whether more data could change the result.
+    ///
+    /// Streaming scanners rely on this method. The default implementation
+    /// calls `matcher`, and never reports an incomplete match.
+    ///
+    /// # Arguments
+    /// data - the data available so far
+    ///
+    /// # Returns
+    /// The result of the match, [MatchResult::Incomplete] if the data is too
+    /// short to decide
+    fn matcher2(&self, data: &[T]) -> MatchResult {
+        self.matcher(data).into()
+    }
 }
 
 /// Size of the matchable object.
@@ -18,3 +75,164 @@ pub trait MatchSize {
     /// Returns the size of the matchable object.
     fn size(&self) -> usize;
 }
+
+/// Match an optional element, `Some(element)` matching one element equal to
+/// it and `None` always matching without consuming anything.
+impl<T: PartialEq> Match<T> for Option<T> {
+    fn matcher(&self, data: &[T]) -> (bool, usize) {
+        match self {
+            None => (true, 0),
+            Some(element) if data.first() == Some(element) => (true, 1),
+            Some(_) => (false, 0),
+        }
+    }
+}
+
+impl<T> MatchSize for Option<T> {
+    fn size(&self) -> usize {
+        match self {
+            None => 0,
+            Some(_) => 1,
+        }
+    }
+}
+
+/// Match only where both matchers match at the same start, a letter which
+/// isn't a `q` for example.
+///
+/// The size of the match is the one of the first matcher, both matchers
+/// being expected to agree on it.
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<T, A: Match<T>, B: Match<T>> Match<T> for And<A, B> {
+    fn matcher(&self, data: &[T]) -> (bool, usize) {
+        match self.0.matcher(data) {
+            (true, size) if self.1.matcher(data).0 => (true, size),
+            _ => (false, 0),
+        }
+    }
+}
+
+impl<A: MatchSize, B> MatchSize for And<A, B> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+/// Match only if the matcher consumes the whole data, to validate a complete
+/// input.
+#[derive(Debug, Clone, Copy)]
+pub struct Anchored<M>(pub M);
+
+impl<T, M: Match<T>> Match<T> for Anchored<M> {
+    fn matcher(&self, data: &[T]) -> (bool, usize) {
+        match self.0.matcher(data) {
+            (true, size) if size == data.len() => (true, size),
+            _ => (false, 0),
+        }
+    }
+}
+
+impl<M: MatchSize> MatchSize for Anchored<M> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::matcher::{Anchored, And, Match, MatchResult, MatchSize};
+
+    #[test]
+    fn test_option() {
+        assert_eq!(Some(b'-').matcher(b"-42"), (true, 1));
+        assert_eq!(Some(b'-').matcher(b"42"), (false, 0));
+        assert_eq!(Some(b'-').matcher(b""), (false, 0));
+        assert_eq!(Some(b'-').size(), 1);
+
+        assert_eq!(None.matcher(b"42"), (true, 0));
+        assert_eq!(None::<u8>.matcher(b""), (true, 0));
+        assert_eq!(None::<u8>.size(), 0);
+    }
+
+    #[test]
+    fn test_and() {
+        /// A lowercase ASCII letter.
+        struct Lower;
+
+        impl Match<u8> for Lower {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                match data.first() {
+                    Some(byte) if byte.is_ascii_lowercase() => (true, 1),
+                    _ => (false, 0),
+                }
+            }
+        }
+
+        /// Any byte but the given one.
+        struct Not(u8);
+
+        impl Match<u8> for Not {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                match data.first() {
+                    Some(byte) if *byte != self.0 => (true, 1),
+                    _ => (false, 0),
+                }
+            }
+        }
+
+        let letter = And(Lower, Not(b'q'));
+        assert_eq!(letter.matcher(b"a"), (true, 1));
+        assert_eq!(letter.matcher(b"q"), (false, 0));
+        assert_eq!(letter.matcher(b"A"), (false, 0));
+        assert_eq!(letter.matcher(b""), (false, 0));
+        assert_eq!(And(Some(b'a'), None::<u8>).size(), 1);
+    }
+
+    #[test]
+    fn test_anchored() {
+        assert_eq!(Anchored(TokenNumber).matcher(b"123"), (true, 3));
+        assert_eq!(Anchored(TokenNumber).matcher(b"123x"), (false, 0));
+        assert_eq!(Anchored(TokenNumber).matcher(b""), (false, 0));
+        assert_eq!(Anchored(None::<u8>).matcher(b""), (true, 0));
+    }
+
+    #[test]
+    fn test_matcher2_shim() {
+        assert_eq!(Some(b'-').matcher2(b"-42"), MatchResult::Matched(1));
+        assert_eq!(Some(b'-').matcher2(b""), MatchResult::NoMatch);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[derive(crate::Match)]
+        #[pattern(b"->")]
+        #[chars]
+        struct Arrow;
+
+        #[derive(crate::Match)]
+        #[pattern("let")]
+        #[keyword]
+        struct Let;
+
+        assert_eq!(Arrow.matcher(b"->x"), (true, 2));
+        assert_eq!(Arrow.matcher(&['-', '>']), (true, 2));
+        assert_eq!(Arrow.matcher(b"-"), (false, 0));
+        assert_eq!(Let.matcher(b"let x"), (true, 3));
+        assert_eq!(Let.matcher(b"LET x"), (false, 0));
+        assert_eq!(Let.matcher(b"let_x"), (false, 0));
+
+        // a prefix may be completed by more data, `let_x` for a keyword
+        use crate::matcher::Needed;
+        let incomplete = |size| MatchResult::Incomplete(Needed::Size(size));
+        assert_eq!(Arrow.matcher2(b"-"), incomplete(1));
+        assert_eq!(Arrow.matcher2(b"->"), MatchResult::Matched(2));
+        assert_eq!(Let.matcher2(b"le"), incomplete(2));
+        assert_eq!(Let.matcher2(b"let"), incomplete(1));
+        assert_eq!(Let.matcher2(b"let x"), MatchResult::Matched(3));
+        assert_eq!(Let.matcher2(b"lex"), MatchResult::NoMatch);
+    }
+}
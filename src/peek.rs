@@ -131,7 +131,7 @@ pub fn peek<'a, T, S, E, P: Peekable<'a, T, S, E>>(
             }))
         }
         PeekResult::NotFound => {
-            scanner.jump_to(source_cursor);
+            scanner.rewind_to(source_cursor)?;
             Ok(None)
         }
     }
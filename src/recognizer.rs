@@ -1,8 +1,13 @@
 //! Defines how to recognize an object.
 
+use crate::describe::{Describe, Expectation, expect, one_of};
 use crate::errors::{ParseError, ParseResult};
-use crate::matcher::{Match, MatchSize};
+use crate::matcher::{Match, MatchResult, MatchSize, Needed};
 use crate::scanner::Scanner;
+use crate::trace::trace;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::Range;
 
 /// A trait that defines how to recognize an object.
 ///
@@ -10,6 +15,11 @@ use crate::scanner::Scanner;
 /// * `V` - The type of the object to recognize
 /// * `T` - The type of the data to scan
 /// * `'a` - The lifetime of the data to scan
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not recognizable",
+    label = "doesn't implement `Recognizable`",
+    note = "in `seq!` and `alt!`, a step naming a type accepts a visitor, a step after `=` recognizes an object"
+)]
 pub trait Recognizable<'a, T, V>: MatchSize {
     /// Try to recognize the object for the given scanner.
     ///
@@ -24,7 +34,481 @@ pub trait Recognizable<'a, T, V>: MatchSize {
     /// * `Ok(None)` if the object was not recognized,
     /// * `Err(ParseError)` if an error occurred
     ///
+    /// An object recognized without consuming anything is a success, the
+    /// repetitions fail with `ParseError::NoProgress` on such objects
+    /// instead of looping forever.
     fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>>;
+
+    /// Recognize the object repeatedly, between `min` and `max` times.
+    ///
+    /// The repetition stops at the first non-match or once `max` objects have
+    /// been recognized.
+    ///
+    /// # Arguments
+    /// * `min` - The minimum number of repetitions
+    /// * `max` - The maximum number of repetitions
+    /// * `scanner` - The scanner to recognize the objects for.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<V>)` the recognized objects,
+    /// * `Err(ParseError)` if fewer than `min` objects were recognized, the
+    ///   scanner is then rewound to its position before the repetition.
+    fn repeat_range(
+        self,
+        min: usize,
+        max: usize,
+        scanner: &mut Scanner<'a, T>,
+    ) -> ParseResult<Vec<V>>
+    where
+        Self: Copy,
+    {
+        recognize_range(min..max.saturating_add(1), || self, scanner)
+    }
+
+    /// Recognize the object, which must consume all the remaining data, a
+    /// region of the data delimited beforehand for example.
+    ///
+    /// # Arguments
+    /// * `scanner` - The scanner to recognize the object for.
+    ///
+    /// # Returns
+    /// * `Ok(V)` if the object was recognized up to the end of the data,
+    /// * `Err(ParseError::UnexpectedToken)` if data remains after the
+    ///   object, the cursor then standing on the first remaining element,
+    /// * the errors of [recognize] otherwise.
+    fn recognize_all(self, scanner: &mut Scanner<'a, T>) -> ParseResult<V>
+    where
+        Self: Sized,
+    {
+        let value = recognize(self, scanner)?;
+        if !scanner.is_empty() {
+            return Err(ParseError::UnexpectedToken);
+        }
+        Ok(value)
+    }
+
+    /// Transform the recognized object.
+    ///
+    /// # Arguments
+    /// * `f` - Builds the new object from the recognized one.
+    fn map<U, F: FnOnce(V) -> U>(self, f: F) -> Map<Self, F, V>
+    where
+        Self: Sized,
+    {
+        Map {
+            inner: self,
+            f,
+            marker: PhantomData,
+        }
+    }
+
+    /// Observe the recognized object without altering it, to debug a
+    /// combinator.
+    ///
+    /// # Arguments
+    /// * `f` - Called with the recognized object, not called if the object
+    ///   is not recognized.
+    fn inspect<F: Fn(&V)>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+    {
+        Inspect { inner: self, f }
+    }
+
+    /// Recognize `other` if the object is not recognized.
+    ///
+    /// # Arguments
+    /// * `other` - The fallback recognizable object.
+    fn or<R: Recognizable<'a, T, V>>(self, other: R) -> Or<Self, R>
+    where
+        Self: Sized,
+    {
+        Or {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// The name identifying the object in traces, its type name by default.
+    fn debug_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Wrap the object so that its recognition is logged at the `trace`
+    /// level, under the `noa_parser` target, with the `log` feature.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the object in the traces.
+    fn traced(self, name: &'static str) -> Traced<Self>
+    where
+        Self: Sized,
+    {
+        Traced { inner: self, name }
+    }
+
+    /// Recognize the object enclosed in delimiters, `(42)` for example.
+    ///
+    /// The delimiters are discarded, the object is recognized only if both
+    /// of them are, the scanner being rewound otherwise.
+    ///
+    /// # Arguments
+    /// * `open` - The matcher of the opening delimiter.
+    /// * `close` - The matcher of the closing delimiter.
+    fn between<O, C>(self, open: O, close: C) -> Between<O, Self, C>
+    where
+        Self: Sized,
+        O: Match<T> + MatchSize,
+        C: Match<T> + MatchSize,
+    {
+        Between {
+            open,
+            inner: self,
+            close,
+        }
+    }
+
+    /// Wrap the object so that it is no longer recognized once it failed to
+    /// match, like [Iterator::fuse].
+    ///
+    /// The fused object is recognized through a reference, each call
+    /// recognizing a clone of the object until the first `Ok(None)`, the
+    /// later calls returning `Ok(None)` without touching the scanner.
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse {
+            inner: self,
+            done: Cell::new(false),
+        }
+    }
+}
+
+/// A recognizable object logging its entry, exit and consumed length, see
+/// [Recognizable::traced].
+#[derive(Debug, Clone, Copy)]
+pub struct Traced<R> {
+    inner: R,
+    name: &'static str,
+}
+
+impl<R: Describe> Describe for Traced<R> {
+    fn expected(&self) -> Expectation {
+        self.inner.expected()
+    }
+}
+
+impl<R: MatchSize> MatchSize for Traced<R> {
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+impl<'a, T, V, R: Recognizable<'a, T, V>> Recognizable<'a, T, V> for Traced<R> {
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        let name = self.name;
+        let cursor = scanner.current_position();
+        trace(format_args!("{name}: enter at {cursor}"));
+        let result = self.inner.recognize(scanner);
+        match &result {
+            Ok(Some(_)) => {
+                let consumed = scanner.current_position() - cursor;
+                trace(format_args!("{name}: exit, consumed {consumed}"));
+            }
+            Ok(None) => trace(format_args!("{name}: exit, no match")),
+            Err(err) => trace(format_args!("{name}: exit, {err}")),
+        }
+        result
+    }
+
+    fn debug_name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// A recognizable object transforming the object recognized by another one,
+/// see [Recognizable::map].
+#[derive(Debug, Clone, Copy)]
+pub struct Map<R, F, V> {
+    inner: R,
+    f: F,
+    marker: PhantomData<fn(V)>,
+}
+
+impl<R: Describe, F, V> Describe for Map<R, F, V> {
+    fn expected(&self) -> Expectation {
+        self.inner.expected()
+    }
+}
+
+impl<R: MatchSize, F, V> MatchSize for Map<R, F, V> {
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+impl<'a, T, V, U, R, F> Recognizable<'a, T, U> for Map<R, F, V>
+where
+    R: Recognizable<'a, T, V>,
+    F: FnOnce(V) -> U,
+{
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<U>> {
+        Ok(self.inner.recognize(scanner)?.map(self.f))
+    }
+}
+
+/// A recognizable object observing the object recognized by another one, see
+/// [Recognizable::inspect].
+#[derive(Debug, Clone, Copy)]
+pub struct Inspect<R, F> {
+    inner: R,
+    f: F,
+}
+
+impl<R: Describe, F> Describe for Inspect<R, F> {
+    fn expected(&self) -> Expectation {
+        self.inner.expected()
+    }
+}
+
+impl<R: MatchSize, F> MatchSize for Inspect<R, F> {
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+impl<'a, T, V, R, F> Recognizable<'a, T, V> for Inspect<R, F>
+where
+    R: Recognizable<'a, T, V>,
+    F: Fn(&V),
+{
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        let value = self.inner.recognize(scanner)?;
+        if let Some(value) = &value {
+            (self.f)(value);
+        }
+        Ok(value)
+    }
+
+    fn debug_name(&self) -> &'static str {
+        self.inner.debug_name()
+    }
+}
+
+/// A recognizable object falling back to a second one, see
+/// [Recognizable::or].
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Both alternatives may come first.
+impl<A: Describe, B: Describe> Describe for Or<A, B> {
+    fn expected(&self) -> Expectation {
+        one_of(self.first.expected(), self.second.expected())
+    }
+}
+
+impl<A: MatchSize, B: MatchSize> MatchSize for Or<A, B> {
+    fn size(&self) -> usize {
+        self.first.size().min(self.second.size())
+    }
+}
+
+impl<'a, T, V, A, B> Recognizable<'a, T, V> for Or<A, B>
+where
+    A: Recognizable<'a, T, V>,
+    B: Recognizable<'a, T, V>,
+{
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        match self.first.recognize(scanner) {
+            Ok(Some(value)) => Ok(Some(value)),
+            // the second alternative may match the end of the data
+            Ok(None) | Err(ParseError::UnexpectedEndOfInput) => self.second.recognize(scanner),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// A recognizable object enclosed in delimiters, see [Recognizable::between].
+#[derive(Debug, Clone, Copy)]
+pub struct Between<O, R, C> {
+    open: O,
+    inner: R,
+    close: C,
+}
+
+impl<O: Describe, R: Describe, C: Describe> Describe for Between<O, R, C> {
+    fn expected(&self) -> Expectation {
+        Expectation::Sequence(vec![
+            self.open.expected(),
+            self.inner.expected(),
+            self.close.expected(),
+        ])
+    }
+}
+
+impl<O: MatchSize, R: MatchSize, C: MatchSize> MatchSize for Between<O, R, C> {
+    fn size(&self) -> usize {
+        self.open.size() + self.inner.size() + self.close.size()
+    }
+}
+
+impl<'a, T, V, O, R, C> Recognizable<'a, T, V> for Between<O, R, C>
+where
+    O: Match<T> + MatchSize,
+    R: Recognizable<'a, T, V>,
+    C: Match<T> + MatchSize,
+{
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        let cursor = scanner.current_position();
+        let attempt = |scanner: &mut Scanner<'a, T>| {
+            let Some(size) = match_at(&self.open, scanner)? else {
+                return Ok(None);
+            };
+            scanner.bump_by(size);
+            let Some(value) = self.inner.recognize(scanner)? else {
+                return Ok(None);
+            };
+            let Some(size) = match_at(&self.close, scanner)? else {
+                return Ok(None);
+            };
+            scanner.bump_by(size);
+            Ok(Some(value))
+        };
+        let result = attempt(scanner);
+        if !matches!(result, Ok(Some(_))) {
+            scanner.rewind_to(cursor)?;
+        }
+        result
+    }
+}
+
+/// A recognizable object which stops matching after its first non-match, see
+/// [Recognizable::fuse].
+#[derive(Debug, Clone)]
+pub struct Fuse<R> {
+    inner: R,
+    done: Cell<bool>,
+}
+
+impl<R> Fuse<R> {
+    /// Return true once the object failed to match.
+    pub fn is_done(&self) -> bool {
+        self.done.get()
+    }
+}
+
+impl<R: Describe> Describe for &Fuse<R> {
+    fn expected(&self) -> Expectation {
+        self.inner.expected()
+    }
+}
+
+/// A fused object needs nothing once done, so that [recognize] reports it as
+/// unexpected rather than running out of input.
+impl<R: MatchSize> MatchSize for &Fuse<R> {
+    fn size(&self) -> usize {
+        match self.done.get() {
+            true => 0,
+            false => self.inner.size(),
+        }
+    }
+}
+
+impl<'a, T, V, R: Recognizable<'a, T, V> + Clone> Recognizable<'a, T, V> for &Fuse<R> {
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        if self.done.get() {
+            return Ok(None);
+        }
+        let result = self.inner.clone().recognize(scanner);
+        if let Ok(None) = result {
+            self.done.set(true);
+        }
+        result
+    }
+
+    fn debug_name(&self) -> &'static str {
+        self.inner.debug_name()
+    }
+}
+
+/// A recognizable object running a closure, see [from_fn].
+#[derive(Debug, Clone, Copy)]
+pub struct FromFn<F>(F);
+
+/// Build a recognizable object from a closure, to inline a recognizer
+/// without defining a type.
+///
+/// The closure follows the contract of [Recognizable::recognize]: it returns
+/// `Ok(None)` without consuming anything when the object is not recognized.
+///
+/// # Arguments
+/// * `f` - Recognizes the object, validating it if needed.
+pub fn from_fn<'a, T, V, F>(f: F) -> FromFn<F>
+where
+    F: FnOnce(&mut Scanner<'a, T>) -> ParseResult<Option<V>>,
+{
+    FromFn(f)
+}
+
+/// The size of a closure is unknown, it may recognize any length.
+impl<F> MatchSize for FromFn<F> {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+impl<'a, T, V, F> Recognizable<'a, T, V> for FromFn<F>
+where
+    F: FnOnce(&mut Scanner<'a, T>) -> ParseResult<Option<V>>,
+{
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<V>> {
+        (self.0)(scanner)
+    }
+}
+
+/// A recognizable object matching where another one doesn't, see [not].
+#[derive(Debug, Clone, Copy)]
+pub struct NotPredicate<R, V> {
+    inner: R,
+    marker: PhantomData<fn() -> V>,
+}
+
+/// Build a negative lookahead, recognized without consuming anything where
+/// `recognizable` is not, to guard a grammar rule.
+///
+/// # Arguments
+/// * `recognizable` - The object which must not follow.
+pub fn not<'a, T, V, R: Recognizable<'a, T, V>>(recognizable: R) -> NotPredicate<R, V> {
+    NotPredicate {
+        inner: recognizable,
+        marker: PhantomData,
+    }
+}
+
+/// The lookahead consumes nothing.
+impl<R, V> MatchSize for NotPredicate<R, V> {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+impl<'a, T, V, R: Recognizable<'a, T, V>> Recognizable<'a, T, ()> for NotPredicate<R, V> {
+    /// Recognize `()` if the inner object fails, the end of the data
+    /// included, `None` if it matches. The cursor is left untouched, the
+    /// errors which can't be recovered from are propagated.
+    fn recognize(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<()>> {
+        let cursor = scanner.current_position();
+        let result = self.inner.recognize(scanner);
+        scanner.rewind_to(cursor)?;
+        match result {
+            Ok(Some(_)) => Ok(None),
+            Err(err) if err.is_unrecoverable() => Err(err),
+            Ok(None) | Err(_) => Ok(Some(())),
+        }
+    }
 }
 
 pub trait RecognizeSelf<'a, T, V>: MatchSize {
@@ -63,7 +547,12 @@ pub fn recognize<'a, T, V, R: Recognizable<'a, T, V>>(
     recognizable: R,
     scanner: &mut Scanner<'a, T>,
 ) -> ParseResult<V> {
-    if recognizable.size() > scanner.remaining().len() {
+    let remaining = scanner.remaining().len();
+    if recognizable.size() > remaining {
+        if scanner.is_streaming() {
+            let needed = Needed::Size(recognizable.size() - remaining);
+            return Err(ParseError::Incomplete { needed });
+        }
         return Err(ParseError::UnexpectedEndOfInput);
     }
     recognizable
@@ -71,24 +560,339 @@ pub fn recognize<'a, T, V, R: Recognizable<'a, T, V>>(
         .ok_or(ParseError::UnexpectedToken)
 }
 
+/// Recognize byte slices as text.
+///
+/// Implemented by every object recognizing byte slices, a `Match<u8>` for
+/// example.
+pub trait RecognizeString<'a>: Recognizable<'a, u8, &'a [u8]> + Sized {
+    /// Recognize the object and copy it into a `String`.
+    ///
+    /// # Arguments
+    /// * `scanner` - The scanner to recognize the object for.
+    ///
+    /// # Returns
+    /// * `Ok(String)` the recognized text,
+    /// * `Err(ParseError::Utf8Error)` if the recognized bytes are not valid
+    ///   UTF-8, the scanner is then rewound to its position before the
+    ///   object,
+    /// * the errors of [recognize] otherwise.
+    fn to_string(self, scanner: &mut Scanner<'a, u8>) -> ParseResult<String> {
+        scanner.rollback_on_err(|scanner| {
+            let data = recognize(self, scanner)?;
+            Ok(std::str::from_utf8(data)?.to_string())
+        })
+    }
+}
+
+impl<'a, R: Recognizable<'a, u8, &'a [u8]>> RecognizeString<'a> for R {}
+
+/// Recognize objects from the scanner itself, to return early with `?` from
+/// a visitor.
+pub trait TryRecognize<'a, T> {
+    /// Recognize an object, failing with a positioned error if it doesn't
+    /// match.
+    ///
+    /// # Arguments
+    /// * `recognizable` - The object to recognize
+    ///
+    /// # Returns
+    /// * `Ok(V)` if the object was recognized, the slice it matched for a
+    ///   `Match` object,
+    /// * the errors of [crate::describe::expect] otherwise,
+    ///   `Err(ParseError::ExpectedStructured)` holding the offset and the
+    ///   expectation of the object if it doesn't match.
+    fn recognize_req<V, R>(&mut self, recognizable: R) -> ParseResult<V>
+    where
+        R: Recognizable<'a, T, V> + Describe;
+}
+
+impl<'a, T> TryRecognize<'a, T> for Scanner<'a, T> {
+    fn recognize_req<V, R>(&mut self, recognizable: R) -> ParseResult<V>
+    where
+        R: Recognizable<'a, T, V> + Describe,
+    {
+        expect(recognizable, self)
+    }
+}
+
+/// Recognize objects repeatedly, the number of repetitions being bounded by
+/// a range.
+///
+/// # Type Parameters
+/// * `V` - The type of the object to recognize
+/// * `R` - The type of the recognizable object
+///
+/// # Arguments
+/// * `bounds` - The minimum (inclusive) and maximum (exclusive) number of
+///   repetitions
+/// * `make` - Builds the recognizable object for each repetition
+/// * `scanner` - The scanner to recognize the objects for
+///
+/// # Returns
+/// * `Ok(Vec<V>)` the recognized objects,
+/// * `Err(ParseError)` if fewer than `bounds.start` objects were recognized,
+///   or `ParseError::NoProgress` if an object is recognized without consuming
+///   anything, the scanner is then rewound to its position before the
+///   repetition.
+pub fn recognize_range<'a, T, V, R: Recognizable<'a, T, V>>(
+    bounds: Range<usize>,
+    make: impl Fn() -> R,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<Vec<V>> {
+    let mut elements = vec![];
+    repeat(bounds, make, scanner, |element| elements.push(element))?;
+    Ok(elements)
+}
+
+/// Count the repetitions of an object, discarding the recognized objects.
+///
+/// # Arguments
+/// * `make` - Builds the recognizable object for each repetition
+/// * `scanner` - The scanner to recognize the objects for
+///
+/// # Returns
+/// * `Ok(usize)` the number of recognized objects, possibly 0,
+/// * `Err(ParseError::NoProgress)` if an object is recognized without
+///   consuming anything, the scanner is then rewound to its position before
+///   the repetition.
+pub fn many0_count<'a, T, V, R: Recognizable<'a, T, V>>(
+    make: impl Fn() -> R,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<usize> {
+    repeat(0..usize::MAX, make, scanner, drop)
+}
+
+/// Count the repetitions of an object, at least one, discarding the
+/// recognized objects.
+///
+/// # Arguments
+/// * `make` - Builds the recognizable object for each repetition
+/// * `scanner` - The scanner to recognize the objects for
+///
+/// # Returns
+/// * `Ok(usize)` the number of recognized objects,
+/// * `Err(ParseError)` if no object was recognized or if an object is
+///   recognized without consuming anything, the scanner is then rewound to
+///   its position before the repetition.
+pub fn many1_count<'a, T, V, R: Recognizable<'a, T, V>>(
+    make: impl Fn() -> R,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<usize> {
+    repeat(1..usize::MAX, make, scanner, drop)
+}
+
+/// Recognize objects separated by a separator, `element (separator element)*`.
+///
+/// # Arguments
+/// * `element` - Builds the recognizable object for each element
+/// * `separator` - Builds the recognizable separator, discarded
+/// * `scanner` - The scanner to recognize the objects for
+///
+/// # Returns
+/// * `Ok(Vec<V>)` the recognized objects, possibly none,
+/// * `Err(ParseError)` if a separator isn't followed by an object, or
+///   `ParseError::NoProgress` if an object and its separator consume
+///   nothing, the scanner is then rewound to its position before the list.
+pub fn recognize_separated<'a, T, V, S, R, Sep>(
+    element: impl Fn() -> R,
+    separator: impl Fn() -> Sep,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<Vec<V>>
+where
+    R: Recognizable<'a, T, V>,
+    Sep: Recognizable<'a, T, S>,
+{
+    separated(element, separator, scanner, false)
+}
+
+/// Recognize objects separated by a separator like [recognize_separated], a
+/// separator after the last object being consumed and discarded.
+///
+/// # Arguments
+/// * `element` - Builds the recognizable object for each element
+/// * `separator` - Builds the recognizable separator, discarded
+/// * `scanner` - The scanner to recognize the objects for
+///
+/// # Returns
+/// * `Ok(Vec<V>)` the recognized objects, possibly none,
+/// * `Err(ParseError)` if an object fails after its separator matched, or
+///   `ParseError::NoProgress` if an object and its separator consume
+///   nothing, the scanner is then rewound to its position before the list.
+pub fn recognize_separated_trailing<'a, T, V, S, R, Sep>(
+    element: impl Fn() -> R,
+    separator: impl Fn() -> Sep,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<Vec<V>>
+where
+    R: Recognizable<'a, T, V>,
+    Sep: Recognizable<'a, T, S>,
+{
+    separated(element, separator, scanner, true)
+}
+
+/// Recognize a separated list, a separator without a following object
+/// ending the list if `trailing` is set.
+fn separated<'a, T, V, S, R, Sep>(
+    element: impl Fn() -> R,
+    separator: impl Fn() -> Sep,
+    scanner: &mut Scanner<'a, T>,
+    trailing: bool,
+) -> ParseResult<Vec<V>>
+where
+    R: Recognizable<'a, T, V>,
+    Sep: Recognizable<'a, T, S>,
+{
+    let mut elements = vec![];
+    scanner.rollback_on_err(|scanner| {
+        match element().recognize(scanner) {
+            Ok(Some(first)) => elements.push(first),
+            Ok(None) | Err(ParseError::UnexpectedEndOfInput) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+        loop {
+            let start = scanner.current_position();
+            match separator().recognize(scanner) {
+                Ok(Some(_)) => {}
+                Ok(None) | Err(ParseError::UnexpectedEndOfInput) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+            match element().recognize(scanner) {
+                Ok(Some(_)) if scanner.current_position() == start => {
+                    return Err(ParseError::NoProgress { offset: start });
+                }
+                Ok(Some(next)) => elements.push(next),
+                Ok(None) | Err(ParseError::UnexpectedEndOfInput) if trailing => return Ok(()),
+                Ok(None) if scanner.is_empty() => return Err(ParseError::UnexpectedEndOfInput),
+                Ok(None) => return Err(ParseError::UnexpectedToken),
+                Err(err) => return Err(err),
+            }
+        }
+    })?;
+    Ok(elements)
+}
+
+/// Recognize objects repeatedly, handing each of them to `each`.
+///
+/// An object recognized without consuming anything would be recognized
+/// forever, the repetition then fails with `ParseError::NoProgress`.
+fn repeat<'a, T, V, R: Recognizable<'a, T, V>>(
+    bounds: Range<usize>,
+    make: impl Fn() -> R,
+    scanner: &mut Scanner<'a, T>,
+    mut each: impl FnMut(V),
+) -> ParseResult<usize> {
+    let cursor = scanner.current_position();
+    let mut count = 0;
+
+    while count + 1 < bounds.end {
+        let start = scanner.current_position();
+        match make().recognize(scanner) {
+            Ok(Some(_)) if scanner.current_position() == start => {
+                scanner.rewind_to(cursor)?;
+                return Err(ParseError::NoProgress { offset: start });
+            }
+            Ok(Some(element)) => {
+                each(element);
+                count += 1;
+            }
+            Ok(None) | Err(ParseError::UnexpectedEndOfInput) => break,
+            Err(err) => {
+                scanner.rewind_to(cursor)?;
+                return Err(err);
+            }
+        }
+    }
+
+    if count < bounds.start {
+        let err = if scanner.is_empty() {
+            ParseError::UnexpectedEndOfInput
+        } else {
+            ParseError::UnexpectedToken
+        };
+        scanner.rewind_to(cursor)?;
+        return Err(err);
+    }
+
+    Ok(count)
+}
+
+/// Recognize a left associative chain of binary operations,
+/// `operand (op operand)*`.
+///
+/// # Type Parameters
+/// * `Op` - The type of the operators
+/// * `Operand` - The type of the operands, and of the resulting tree
+///
+/// # Arguments
+/// * `operand` - Parses an operand
+/// * `op` - Parses an operator, `Ok(None)` ending the chain
+/// * `combine` - Builds the tree of an operation from its operands
+/// * `scanner` - The scanner to recognize the chain for
+///
+/// # Returns
+/// * `Ok(Operand)` the tree, `1 - 2 - 3` giving `(1 - 2) - 3`,
+/// * `Err(ParseError)` if an operand fails, the scanner is then rewound to
+///   its position before the chain.
+pub fn recognize_left_assoc<'a, T, Op, Operand, F>(
+    operand: impl Fn(&mut Scanner<'a, T>) -> ParseResult<Operand>,
+    op: impl Fn(&mut Scanner<'a, T>) -> ParseResult<Option<Op>>,
+    combine: F,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<Operand>
+where
+    F: Fn(Operand, Op, Operand) -> Operand,
+{
+    scanner.rollback_on_err(|scanner| {
+        let mut lhs = operand(scanner)?;
+        while let Some(op) = op(scanner)? {
+            let rhs = operand(scanner)?;
+            lhs = combine(lhs, op, rhs);
+        }
+        Ok(lhs)
+    })
+}
+
 /// Recognize an object for the given scanner.
 /// Return a slice of the recognized object.
 impl<'a, T, M: Match<T> + MatchSize> RecognizeSelf<'a, T, M> for M {
     fn recognize_self(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<M>> {
+        let Some(size) = match_at(&self, scanner)? else {
+            return Ok(None);
+        };
+        scanner.bump_by(size);
+        Ok(Some(self))
+    }
+}
+
+/// Match the data at the cursor of a scanner.
+///
+/// # Arguments
+/// * `matcher` - The matcher to run
+/// * `scanner` - The scanner to match the data of
+///
+/// # Returns
+/// * `Ok(Some(size))` the size of the match,
+/// * `Ok(None)` if the data doesn't match,
+/// * `Err(ParseError::UnexpectedEndOfInput)` if the scanner is empty,
+/// * `Err(ParseError::Incomplete)` if the scanner is streaming and the
+///   matcher needs more data to decide, see [Match::matcher2],
+/// * `Err(ParseError::BudgetExhausted)` if the fuel of the scanner ran out.
+pub(crate) fn match_at<T, M: Match<T>>(
+    matcher: &M,
+    scanner: &mut Scanner<'_, T>,
+) -> ParseResult<Option<usize>> {
+    scanner.burn_fuel()?;
+    if !scanner.is_streaming() {
         if scanner.is_empty() {
             return Err(ParseError::UnexpectedEndOfInput);
         }
-
-        let data = scanner.remaining();
-
-        let (result, size) = self.matcher(data);
-        if !result {
-            return Ok(None);
-        }
-        if !scanner.is_empty() {
-            scanner.bump_by(size);
-        }
-        Ok(Some(self))
+        let (found, size) = matcher.matcher(scanner.remaining());
+        return Ok(found.then_some(size));
+    }
+    match matcher.matcher2(scanner.remaining()) {
+        MatchResult::Matched(size) => Ok(Some(size)),
+        MatchResult::NoMatch => Ok(None),
+        MatchResult::Incomplete(needed) => Err(ParseError::Incomplete { needed }),
     }
 }
 
@@ -192,9 +996,323 @@ impl<'a, 'b, T, R: RecognizeSelf<'a, T, R>> Recognizer<'a, 'b, T, R> {
 
 #[cfg(test)]
 mod tests {
+    use crate::bytes::primitives::identifier::TokenIdentifier;
+    use crate::bytes::primitives::number::{Number, TokenNumber};
+    use crate::bytes::primitives::whitespace::OptionalWhitespaces;
     use crate::bytes::token::Token;
+    use crate::errors::ParseError;
     use crate::errors::ParseResult;
-    use crate::recognizer::{RecognizeSelf, Recognizer};
+    use crate::matcher::{Match, MatchSize, Needed};
+    use crate::recognizer::{
+        Recognizable, RecognizeSelf, RecognizeString, Recognizer, TryRecognize, from_fn,
+        many0_count, many1_count, not, recognize, recognize_left_assoc, recognize_range,
+        recognize_separated, recognize_separated_trailing,
+    };
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_streaming() {
+        // a literal split across a chunk boundary
+        let mut scanner = Scanner::streaming(b"a\r");
+        scanner.bump_by(1);
+        assert!(matches!(
+            recognize(Token::CrLn, &mut scanner),
+            Err(ParseError::Incomplete {
+                needed: Needed::Size(1)
+            })
+        ));
+        assert_eq!(scanner.current_position(), 1);
+        let mut scanner = Scanner::new(b"a\r");
+        scanner.bump_by(1);
+        assert!(matches!(
+            recognize(Token::CrLn, &mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+
+        let mut scanner = Scanner::streaming(b"a\r\n");
+        scanner.bump_by(1);
+        assert_eq!(
+            recognize(Token::CrLn, &mut scanner).expect("failed to parse"),
+            b"\r\n"
+        );
+        assert!(matches!(
+            recognize(Token::Comma, &mut scanner),
+            Err(ParseError::Incomplete { .. })
+        ));
+
+        // a digit run ending exactly at the buffer edge
+        let mut scanner = Scanner::streaming(b"123");
+        assert!(matches!(
+            Number::<usize>::accept(&mut scanner),
+            Err(ParseError::Incomplete {
+                needed: Needed::Unknown
+            })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        let mut scanner = Scanner::new(b"123");
+        assert_eq!(
+            Number::<usize>::accept(&mut scanner)
+                .expect("failed to parse")
+                .0,
+            123
+        );
+        let mut scanner = Scanner::streaming(b"123 ");
+        assert_eq!(
+            Number::<usize>::accept(&mut scanner)
+                .expect("failed to parse")
+                .0,
+            123
+        );
+    }
+
+    #[test]
+    fn test_not() {
+        let mut scanner = Scanner::new(b"a)");
+        assert_eq!(
+            not(Token::CloseParen)
+                .recognize(&mut scanner)
+                .expect("failed to parse"),
+            Some(())
+        );
+        assert_eq!(scanner.current_position(), 0);
+
+        scanner.bump_by(1);
+        assert_eq!(
+            not(Token::CloseParen)
+                .recognize(&mut scanner)
+                .expect("failed to parse"),
+            None
+        );
+        assert_eq!(scanner.current_position(), 1);
+
+        // nothing follows the end of the data
+        scanner.bump_by(1);
+        recognize(not(Token::CloseParen), &mut scanner).expect("failed to parse");
+        assert!(matches!(
+            not(Token::CloseParen).recognize(&mut Scanner::streaming(b"")),
+            Err(ParseError::Incomplete { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recognize_all() {
+        let mut scanner = Scanner::new(b"42");
+        assert_eq!(
+            TokenNumber
+                .recognize_all(&mut scanner)
+                .expect("failed to parse"),
+            b"42"
+        );
+
+        let mut scanner = Scanner::new(b"42px");
+        assert!(matches!(
+            TokenNumber.recognize_all(&mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert_eq!(scanner.current_position(), 2);
+        assert!(matches!(
+            TokenNumber.recognize_all(&mut Scanner::new(b"")),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_recognize_req() {
+        /// `number+number`, the sum of the numbers.
+        fn sum(scanner: &mut Scanner<u8>) -> ParseResult<usize> {
+            let Number(lhs) = Number::<usize>::accept(scanner)?;
+            scanner.recognize_req(Token::Plus)?;
+            let Number(rhs) = Number::<usize>::accept(scanner)?;
+            Ok(lhs + rhs)
+        }
+
+        assert_eq!(sum(&mut Scanner::new(b"1+2")).expect("failed to parse"), 3);
+
+        let mut scanner = Scanner::new(b"1-2");
+        let err = sum(&mut scanner).expect_err("should fail");
+        assert_eq!(err.to_string(), "Expected \"+\" at offset 1");
+        assert_eq!(scanner.current_position(), 1);
+
+        let mut scanner = Scanner::new(b"12+");
+        assert_eq!(
+            scanner.recognize_req(TokenNumber).expect("failed to parse"),
+            b"12"
+        );
+        assert_eq!(
+            scanner.recognize_req(Token::Plus).expect("failed to parse"),
+            b"+"
+        );
+        assert!(matches!(
+            scanner.recognize_req(Token::Plus),
+            Err(ParseError::ExpectedStructured { offset: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_inspect() {
+        let seen = RefCell::new(vec![]);
+        let mut scanner = Scanner::new(b"42+");
+        let number = TokenNumber.inspect(|data: &&[u8]| seen.borrow_mut().push(data.to_vec()));
+        assert_eq!(
+            recognize(number, &mut scanner).expect("failed to parse"),
+            b"42"
+        );
+        assert_eq!(scanner.current_position(), 2);
+
+        // not called without a match
+        let number = TokenNumber.inspect(|data: &&[u8]| seen.borrow_mut().push(data.to_vec()));
+        assert!(
+            number
+                .recognize(&mut scanner)
+                .expect("failed to parse")
+                .is_none()
+        );
+        assert_eq!(scanner.current_position(), 2);
+        assert_eq!(*seen.borrow(), [b"42".to_vec()]);
+    }
+
+    #[test]
+    fn test_between() {
+        let mut scanner = Scanner::new(b"(42)");
+        let number = TokenNumber.between(Token::OpenParen, Token::CloseParen);
+        assert_eq!(
+            recognize(number, &mut scanner).expect("failed to parse"),
+            b"42"
+        );
+        assert!(scanner.is_empty());
+
+        // a missing delimiter rewinds the scanner
+        for data in [&b"(42]"[..], b"42)", b"(x)", b"(42"] {
+            let mut scanner = Scanner::new(data);
+            let number = TokenNumber.between(Token::OpenParen, Token::CloseParen);
+            assert!(!matches!(number.recognize(&mut scanner), Ok(Some(_))));
+            assert_eq!(scanner.current_position(), 0);
+        }
+
+        // unless the rewind exceeds the backtrack limit
+        let mut scanner = Scanner::new(b"(42]");
+        scanner.set_backtrack_limit(2);
+        let number = TokenNumber.between(Token::OpenParen, Token::CloseParen);
+        assert!(matches!(
+            number.recognize(&mut scanner),
+            Err(ParseError::BacktrackLimitExceeded { offset: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_fuse() {
+        let mut scanner = Scanner::new(b",,a,");
+        let comma = Token::Comma.fuse();
+        assert!(
+            comma
+                .recognize(&mut scanner)
+                .expect("failed to parse")
+                .is_some()
+        );
+        assert!(
+            comma
+                .recognize(&mut scanner)
+                .expect("failed to parse")
+                .is_some()
+        );
+        assert!(!comma.is_done());
+        assert!(
+            comma
+                .recognize(&mut scanner)
+                .expect("failed to parse")
+                .is_none()
+        );
+        assert!(comma.is_done());
+
+        // the comma following the identifier is no longer recognized
+        scanner.bump_by(1);
+        assert_eq!(scanner.current_position(), 3);
+        assert!(
+            comma
+                .recognize(&mut scanner)
+                .expect("failed to parse")
+                .is_none()
+        );
+        assert!(matches!(
+            recognize(&comma, &mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert_eq!(scanner.current_position(), 3);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        // an even number, odd numbers being rejected
+        let even = || {
+            from_fn(|scanner: &mut Scanner<u8>| {
+                let cursor = scanner.current_position();
+                let Ok(Number(value)) = Number::<u32>::accept(scanner) else {
+                    scanner.jump_to(cursor);
+                    return Ok(None);
+                };
+                if value % 2 == 1 {
+                    return Err(ParseError::Syntax {
+                        offset: cursor,
+                        reason: "odd number",
+                    });
+                }
+                Ok(Some(value))
+            })
+        };
+
+        let mut scanner = Scanner::new(b"42,7");
+        assert_eq!(
+            recognize(even(), &mut scanner).expect("failed to parse"),
+            42
+        );
+        assert!(matches!(
+            recognize(even(), &mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+        scanner.bump_by(1);
+        assert!(matches!(
+            recognize(even(), &mut scanner),
+            Err(ParseError::Syntax { offset: 3, .. })
+        ));
+
+        // composed with `or` and `map`
+        let mut scanner = Scanner::new(b",12");
+        let comma_or_even = even().or(Token::Comma.map(|_| 1));
+        assert_eq!(
+            recognize(comma_or_even, &mut scanner).expect("failed to parse"),
+            1
+        );
+        let doubled = even().map(|value| value * 2);
+        assert_eq!(
+            recognize(doubled, &mut scanner).expect("failed to parse"),
+            24
+        );
+    }
+
+    #[test]
+    fn test_or() {
+        let plus_or_end = || {
+            Token::Plus
+                .map(|_| "plus")
+                .or(not(Token::Plus).map(|_| "end"))
+        };
+        let mut scanner = Scanner::new(b"+");
+        assert_eq!(
+            recognize(plus_or_end(), &mut scanner).expect("failed to parse"),
+            "plus"
+        );
+        // the end of the data is left to the second alternative
+        assert_eq!(
+            recognize(plus_or_end(), &mut scanner).expect("failed to parse"),
+            "end"
+        );
+        assert!(matches!(
+            recognize(Token::Plus.or(Token::Dash), &mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
 
     #[test]
     fn test_recognizer() {
@@ -218,4 +1336,325 @@ mod tests {
         assert_eq!(result, Token::GreaterThan);
         Ok(())
     }
+
+    #[test]
+    fn test_repeat_range() {
+        let data = b"+";
+        let mut scanner = Scanner::new(data);
+        let result = Token::Plus.repeat_range(2, 3, &mut scanner);
+        assert!(matches!(result, Err(ParseError::UnexpectedEndOfInput)));
+        assert_eq!(scanner.current_position(), 0);
+
+        let data = b"+-";
+        let mut scanner = Scanner::new(data);
+        let result = Token::Plus.repeat_range(2, 3, &mut scanner);
+        assert!(matches!(result, Err(ParseError::UnexpectedToken)));
+        assert_eq!(scanner.current_position(), 0);
+
+        let data = b"+++";
+        let mut scanner = Scanner::new(data);
+        let result = Token::Plus
+            .repeat_range(2, 3, &mut scanner)
+            .expect("failed to parse");
+        assert_eq!(result.len(), 3);
+        assert!(scanner.is_empty());
+
+        let data = b"++++";
+        let mut scanner = Scanner::new(data);
+        let result = Token::Plus
+            .repeat_range(2, 3, &mut scanner)
+            .expect("failed to parse");
+        assert_eq!(result, vec![b"+", b"+", b"+"]);
+        assert_eq!(scanner.current_position(), 3);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_traced() {
+        /// A logger keeping the trace lines of the current thread.
+        struct Capture;
+
+        thread_local! {
+            static LINES: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+        }
+
+        impl log::Log for Capture {
+            fn enabled(&self, metadata: &log::Metadata) -> bool {
+                metadata.target() == "noa_parser"
+            }
+
+            fn log(&self, record: &log::Record) {
+                if self.enabled(record.metadata()) {
+                    LINES.with(|lines| lines.borrow_mut().push(record.args().to_string()));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: Capture = Capture;
+        // the logger may already be installed by another test
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let data = b"++-";
+        let mut scanner = Scanner::new(data);
+        let result = Token::Plus
+            .traced("plus")
+            .repeat_range(1, 3, &mut scanner)
+            .expect("failed to parse");
+        assert_eq!(result.len(), 2);
+
+        // the recognizers which aren't traced are silent
+        Token::Dash
+            .recognize(&mut scanner)
+            .expect("failed to parse");
+
+        assert_eq!(
+            LINES.with(|lines| lines.take()),
+            vec![
+                "plus: enter at 0",
+                "plus: exit, consumed 1",
+                "plus: enter at 1",
+                "plus: exit, consumed 1",
+                "plus: enter at 2",
+                "plus: exit, no match",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_debug_name() {
+        assert!(Token::Plus.debug_name().ends_with("Token"));
+        assert_eq!(Token::Plus.traced("plus").debug_name(), "plus");
+    }
+
+    #[test]
+    fn test_many_count() {
+        let data = b"### Title";
+        let mut scanner = Scanner::new(data);
+        assert_eq!(
+            many1_count(|| Token::Hash, &mut scanner).expect("failed to parse"),
+            3
+        );
+        assert_eq!(scanner.current_position(), 3);
+        assert_eq!(
+            many0_count(|| Token::Hash, &mut scanner).expect("failed to parse"),
+            0
+        );
+        assert!(matches!(
+            many1_count(|| Token::Hash, &mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert_eq!(scanner.current_position(), 3);
+
+        let mut scanner = Scanner::new(b"");
+        assert_eq!(
+            many0_count(|| Token::Hash, &mut scanner).expect("failed to parse"),
+            0
+        );
+        assert!(matches!(
+            many1_count(|| Token::Hash, &mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_to_string() {
+        let mut scanner = Scanner::new(b"abc,");
+        let text = TokenIdentifier
+            .to_string(&mut scanner)
+            .expect("failed to parse");
+        assert_eq!(text, "abc".to_string());
+        assert!(matches!(
+            TokenIdentifier.to_string(&mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+
+        // any two bytes
+        struct Pair;
+
+        impl Match<u8> for Pair {
+            fn matcher(&self, data: &[u8]) -> (bool, usize) {
+                (data.len() >= 2, 2)
+            }
+        }
+
+        impl MatchSize for Pair {
+            fn size(&self) -> usize {
+                2
+            }
+        }
+
+        let mut scanner = Scanner::new(b"\xc3(");
+        assert!(matches!(
+            Pair.to_string(&mut scanner),
+            Err(ParseError::Utf8Error(_))
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_zero_length_match() {
+        // a single recognition accepts an empty match
+        let mut scanner = Scanner::new(b"a");
+        assert_eq!(
+            recognize(None::<u8>, &mut scanner).expect("failed to parse"),
+            b""
+        );
+        assert_eq!(scanner.current_position(), 0);
+        assert!(matches!(
+            None::<u8>.repeat_range(0, 10, &mut scanner),
+            Err(ParseError::NoProgress { offset: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_recognize_separated() {
+        use crate::bytes::primitives::number::TokenNumber;
+
+        let number = || {
+            TokenNumber.map(|data: &[u8]| {
+                std::str::from_utf8(data)
+                    .expect("digits are UTF-8")
+                    .parse::<usize>()
+                    .expect("failed to parse")
+            })
+        };
+        let comma = || Token::Comma;
+
+        let mut scanner = Scanner::new(b"1,2,3,");
+        assert_eq!(
+            recognize_separated_trailing(number, comma, &mut scanner).expect("failed to parse"),
+            [1, 2, 3]
+        );
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"1,2,3");
+        assert_eq!(
+            recognize_separated_trailing(number, comma, &mut scanner).expect("failed to parse"),
+            [1, 2, 3]
+        );
+        let mut scanner = Scanner::new(b"1,2,3;");
+        assert_eq!(
+            recognize_separated(number, comma, &mut scanner).expect("failed to parse"),
+            [1, 2, 3]
+        );
+        assert_eq!(scanner.remaining(), b";");
+
+        // a trailing separator is an error, the scanner is rewound
+        let mut scanner = Scanner::new(b"1,2,3,");
+        assert!(matches!(
+            recognize_separated(number, comma, &mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        let mut scanner = Scanner::new(b"1,;");
+        assert!(matches!(
+            recognize_separated(number, comma, &mut scanner),
+            Err(ParseError::UnexpectedToken)
+        ));
+
+        let mut scanner = Scanner::new(b";");
+        assert!(
+            recognize_separated_trailing(number, comma, &mut scanner)
+                .expect("failed to parse")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_many_count_no_progress() {
+        // `None` matches without consuming anything
+        let mut scanner = Scanner::new(b"#a");
+        scanner.bump_by(1);
+        assert!(matches!(
+            many0_count(|| None::<u8>, &mut scanner),
+            Err(ParseError::NoProgress { offset: 1 })
+        ));
+        assert_eq!(scanner.current_position(), 1);
+        assert!(matches!(
+            recognize_range(0..3, || None::<u8>, &mut scanner),
+            Err(ParseError::NoProgress { offset: 1 })
+        ));
+
+        // the empty element may come after some progress
+        let mut scanner = Scanner::new(b"##a");
+        let hash_or_empty =
+            || from_fn(|scanner: &mut Scanner<u8>| Ok(Some(scanner.eat(Token::Hash))));
+        assert!(matches!(
+            many1_count(hash_or_empty, &mut scanner),
+            Err(ParseError::NoProgress { offset: 2 })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_recognize_range() {
+        let data = b"+++";
+        let mut scanner = Scanner::new(data);
+        let result = recognize_range(2..4, || Token::Plus, &mut scanner).expect("failed to parse");
+        assert_eq!(result.len(), 3);
+        assert!(scanner.is_empty());
+
+        let data = b"++++";
+        let mut scanner = Scanner::new(data);
+        let result = recognize_range(2..4, || Token::Plus, &mut scanner).expect("failed to parse");
+        assert_eq!(result.len(), 3);
+        assert_eq!(scanner.current_position(), 3);
+
+        let data = b"+-";
+        let mut scanner = Scanner::new(data);
+        let result = recognize_range(2..4, || Token::Plus, &mut scanner);
+        assert!(matches!(result, Err(ParseError::UnexpectedToken)));
+        assert_eq!(scanner.current_position(), 0);
+
+        let data = b"-";
+        let mut scanner = Scanner::new(data);
+        let result = recognize_range(0..4, || Token::Plus, &mut scanner).expect("failed to parse");
+        assert!(result.is_empty());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Expr {
+        Number(usize),
+        Sum(Box<Expr>, Box<Expr>),
+    }
+
+    fn sum(scanner: &mut Scanner<u8>) -> ParseResult<Expr> {
+        recognize_left_assoc(
+            |scanner| Ok(Expr::Number(Number::accept(scanner)?.0)),
+            |scanner| {
+                OptionalWhitespaces::accept(scanner)?;
+                if scanner.is_empty() {
+                    return Ok(None);
+                }
+                let plus = Token::Plus.recognize(scanner)?;
+                OptionalWhitespaces::accept(scanner)?;
+                Ok(plus)
+            },
+            |lhs, _, rhs| Expr::Sum(Box::new(lhs), Box::new(rhs)),
+            scanner,
+        )
+    }
+
+    #[test]
+    fn test_recognize_left_assoc() {
+        let number = |value| Box::new(Expr::Number(value));
+
+        let mut scanner = Scanner::new(b"1 + 2 + 3");
+        let result = sum(&mut scanner).expect("failed to parse");
+        assert_eq!(
+            result,
+            Expr::Sum(Box::new(Expr::Sum(number(1), number(2))), number(3))
+        );
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"1");
+        assert_eq!(sum(&mut scanner).expect("failed to parse"), Expr::Number(1));
+
+        let mut scanner = Scanner::new(b"1 + a");
+        assert!(sum(&mut scanner).is_err());
+        assert_eq!(scanner.current_position(), 0);
+    }
 }
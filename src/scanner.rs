@@ -1,7 +1,9 @@
 //! A scanner for a sequence of elements.
 
-use crate::errors::ParseResult;
-use crate::visitor::Visitor;
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::{Match, MatchSize};
+use crate::recognizer::match_at;
+use crate::visitor::{ParseContext, Visitor};
 use std::io::Cursor;
 use std::ops::Deref;
 
@@ -10,12 +12,44 @@ use std::ops::Deref;
 pub struct Scanner<'a, T> {
     /// The internal cursor.
     cursor: Cursor<&'a [T]>,
+    /// Whether more data may follow the data of the scanner.
+    streaming: bool,
+    /// The remaining recognition attempts, unlimited if unset.
+    fuel: Option<u64>,
+    /// The furthest a rewind may move the cursor back, unlimited if unset.
+    backtrack_limit: Option<usize>,
+    /// The furthest position the cursor reached.
+    furthest: usize,
 }
 
 impl<'a, T> Scanner<'a, T> {
     pub fn new(data: &'a [T]) -> Scanner<'a, T> {
         Scanner {
             cursor: Cursor::new(data),
+            streaming: false,
+            fuel: None,
+            backtrack_limit: None,
+            furthest: 0,
+        }
+    }
+
+    /// Create a scanner over a chunk of data which may be followed by more
+    /// data.
+    ///
+    /// Recognizing an object which could go on past the end of the chunk
+    /// then fails with `ParseError::Incomplete`, asking for more data instead
+    /// of returning a truncated object.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data available so far.
+    pub fn streaming(data: &'a [T]) -> Scanner<'a, T> {
+        Scanner {
+            cursor: Cursor::new(data),
+            streaming: true,
+            fuel: None,
+            backtrack_limit: None,
+            furthest: 0,
         }
     }
 }
@@ -32,6 +66,7 @@ impl<'a, T> Scanner<'a, T> {
     /// Panics if the internal cursor is moved past the end of the data.
     pub fn bump_by(&mut self, n: usize) {
         self.cursor.set_position(self.cursor.position() + n as u64);
+        self.furthest = self.furthest.max(self.current_position());
     }
 
     /// Move the internal cursor to the specified position.
@@ -45,6 +80,28 @@ impl<'a, T> Scanner<'a, T> {
     /// Panics if the internal cursor is moved past the end of the data.
     pub fn jump_to(&mut self, n: usize) {
         self.cursor.set_position(n as u64);
+        self.furthest = self.furthest.max(n);
+    }
+
+    /// Move the internal cursor back to a position it already reached,
+    /// checking the backtrack limit, see [Scanner::set_backtrack_limit].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The position to move the cursor back to.
+    ///
+    /// # Returns
+    ///
+    /// `ParseError::BacktrackLimitExceeded` if `n` is more than the limit
+    /// back from the furthest position reached, the cursor being moved anyway.
+    pub fn rewind_to(&mut self, n: usize) -> ParseResult<()> {
+        self.jump_to(n);
+        match self.backtrack_limit {
+            Some(max) if self.furthest - n > max => {
+                Err(ParseError::BacktrackLimitExceeded { offset: n })
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Move the internal cursor backward by `n` positions.
@@ -96,6 +153,66 @@ impl<'a, T> Scanner<'a, T> {
         &self.cursor.get_ref()[self.current_position()..]
     }
 
+    /// Limit the number of recognition attempts and backtracks, so that a
+    /// pathological input can't make a parse run forever.
+    ///
+    /// Once the fuel is exhausted, recognitions fail with
+    /// `ParseError::BudgetExhausted`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fuel` - The number of attempts allowed.
+    pub fn set_fuel(&mut self, fuel: u64) {
+        self.fuel = Some(fuel);
+    }
+
+    /// Limit how far the parse can backtrack, so that the data consumed
+    /// needn't be kept around for an arbitrary long time.
+    ///
+    /// Once set, [Scanner::rewind_to], thus [Scanner::rollback_on_err] and the
+    /// combinators backtracking on failure, fail with
+    /// `ParseError::BacktrackLimitExceeded` when rewinding the cursor more
+    /// than `max` elements back from the furthest position it reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The number of elements a rewind may move back.
+    pub fn set_backtrack_limit(&mut self, max: usize) {
+        self.backtrack_limit = Some(max);
+    }
+
+    /// Return the remaining fuel, `None` if the attempts are unlimited.
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    /// Burn a unit of fuel, if any has been set.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::BudgetExhausted` if no fuel remains.
+    pub(crate) fn burn_fuel(&mut self) -> ParseResult<()> {
+        if let Some(fuel) = &mut self.fuel {
+            if *fuel == 0 {
+                return Err(ParseError::BudgetExhausted {
+                    consumed_offset: self.cursor.position() as usize,
+                });
+            }
+            *fuel -= 1;
+        }
+        Ok(())
+    }
+
+    /// Return the furthest position the cursor reached.
+    pub(crate) fn furthest_position(&self) -> usize {
+        self.furthest
+    }
+
+    /// Return true if more data may follow the data of the scanner.
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
     /// Return true if there are no more elements to scan, false otherwise.
     ///
     /// # Returns
@@ -104,6 +221,183 @@ impl<'a, T> Scanner<'a, T> {
     pub fn is_empty(&self) -> bool {
         self.remaining().is_empty()
     }
+
+    /// Transform the remaining data, for a separate scanner to run over it,
+    /// a lowercased copy to parse case insensitively for example.
+    ///
+    /// The data of the scanner is left untouched, and a copy is allocated,
+    /// so this is meant for the grammars needing it only. The offsets in the
+    /// copy start at the cursor, adding `current_position()` to them gives
+    /// their offset in the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Transforms an element.
+    ///
+    /// # Returns
+    ///
+    /// The transformed remaining elements.
+    pub fn map_view<U>(&self, f: impl Fn(&T) -> U) -> Vec<U> {
+        self.remaining().iter().map(f).collect()
+    }
+
+    /// Return true if the remaining data ends with `suffix`, a terminator
+    /// already present for example.
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The elements to look for at the end of the data.
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.remaining().ends_with(suffix)
+    }
+
+    /// Return how many elements a matcher would consume at the cursor,
+    /// without consuming anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to run.
+    ///
+    /// # Returns
+    ///
+    /// The size of the match, `None` if the matcher doesn't match.
+    pub fn peek_len<M: Match<T>>(&self, matcher: &M) -> Option<usize> {
+        let (found, size) = matcher.matcher(self.remaining());
+        found.then_some(size)
+    }
+
+    /// Count the elements preceding the first match of a matcher, without
+    /// consuming anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to look for.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements before the first match, or `None` if the
+    /// matcher never matches the remaining data.
+    pub fn count_until<M: Match<T>>(&self, matcher: &M) -> Option<usize> {
+        let remaining = self.remaining();
+        (0..remaining.len()).find(|&pos| matcher.matcher(&remaining[pos..]).0)
+    }
+
+    /// Take a read-only snapshot of the scanner at its current position.
+    ///
+    /// The snapshot is not affected by further moves of the scanner, so it
+    /// can be handed to speculative branches. Adopting a snapshot back is
+    /// done with `jump_to(snapshot.position())`, and what has been consumed
+    /// since is measured with [ScannerSnapshot::consumed_since].
+    ///
+    /// # Returns
+    ///
+    /// A snapshot of the data and current position.
+    pub fn snapshot(&self) -> ScannerSnapshot<'a, T> {
+        ScannerSnapshot {
+            data: self.data(),
+            position: self.current_position(),
+        }
+    }
+
+    /// Record the position of the cursor, to measure later what has been
+    /// consumed since.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.current_position(),
+        }
+    }
+
+    /// Return the number of elements consumed since a checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `earlier` - A checkpoint of the scanner.
+    ///
+    /// # Panics
+    ///
+    /// If the checkpoint is ahead of the cursor, the scanner having been
+    /// moved back since.
+    pub fn consumed_since(&self, earlier: Checkpoint) -> usize {
+        let snapshot = ScannerSnapshot {
+            data: self.data(),
+            position: earlier.position,
+        };
+        snapshot.consumed_since(self)
+    }
+}
+
+/// A position of a scanner, see [Scanner::checkpoint].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checkpoint {
+    position: usize,
+}
+
+impl Checkpoint {
+    /// Return the position of the scanner when the checkpoint was taken.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// An immutable view of a scanner at a given position.
+#[derive(Debug, PartialEq)]
+pub struct ScannerSnapshot<'a, T> {
+    data: &'a [T],
+    position: usize,
+}
+
+impl<T> Clone for ScannerSnapshot<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ScannerSnapshot<'_, T> {}
+
+impl<'a, T> ScannerSnapshot<'a, T> {
+    /// Return the position of the scanner when the snapshot was taken.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the number of elements a scanner consumed since the snapshot
+    /// was taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner the snapshot was taken from.
+    ///
+    /// # Panics
+    ///
+    /// If the snapshot is ahead of the cursor, the scanner having been moved
+    /// back since.
+    pub fn consumed_since(&self, scanner: &Scanner<'a, T>) -> usize {
+        scanner
+            .current_position()
+            .checked_sub(self.position)
+            .expect("the scanner has been moved back since")
+    }
+
+    /// Return the element at the snapshot position, if any.
+    pub fn peek(&self) -> Option<&'a T> {
+        self.data.get(self.position)
+    }
+
+    /// Return the data remaining after the snapshot position.
+    pub fn remaining(&self) -> &'a [T] {
+        &self.data[self.position..]
+    }
+
+    /// Return true if the remaining data starts with `prefix`.
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.remaining().starts_with(prefix)
+    }
 }
 
 impl<'a, T> Deref for Scanner<'a, T> {
@@ -130,4 +424,387 @@ impl<'a, T> Scanner<'a, T> {
     pub fn visit<V: Visitor<'a, T>>(&mut self) -> ParseResult<V> {
         V::accept(self)
     }
+
+    /// Run a visitor on the scanner according to a parse context.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The type of the visitor to run.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The flags driving the grammar.
+    ///
+    /// # Returns
+    ///
+    /// The result of running the visitor on the scanner.
+    pub fn visit_with<V: Visitor<'a, T>>(&mut self, context: &ParseContext) -> ParseResult<V> {
+        V::accept_with(self, context)
+    }
+
+    /// Run a fallible closure on the scanner, rewinding the scanner to its
+    /// position before the call if the closure fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run on the scanner.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure, errors are propagated untouched but for
+    /// `ParseError::BacktrackLimitExceeded` replacing the error of a closure
+    /// rewinding further than the limit, see [Scanner::set_backtrack_limit].
+    pub fn rollback_on_err<V>(
+        &mut self,
+        f: impl FnOnce(&mut Scanner<'a, T>) -> ParseResult<V>,
+    ) -> ParseResult<V> {
+        let cursor = self.current_position();
+        let result = f(self);
+        if let Err(err) = &result {
+            let rewind = self.rewind_to(cursor);
+            // backtracking costs fuel too
+            self.burn_fuel()?;
+            if !matches!(err, ParseError::BacktrackLimitExceeded { .. }) {
+                rewind?;
+            }
+        }
+        result
+    }
+
+    /// Consume the data matched by a matcher standing at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to run.
+    ///
+    /// # Returns
+    ///
+    /// The consumed slice, `None` if the data doesn't match, the end of the
+    /// data included, the cursor is then left untouched.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::Incomplete` if the scanner is streaming and the matcher
+    /// needs more data to decide, `ParseError::BudgetExhausted` if the fuel
+    /// of the scanner ran out.
+    pub fn consume_if_matches<M: Match<T> + MatchSize>(
+        &mut self,
+        matcher: M,
+    ) -> ParseResult<Option<&'a [T]>> {
+        if !self.is_streaming() && matcher.size() > self.remaining().len() {
+            return Ok(None);
+        }
+        let size = match match_at(&matcher, self) {
+            Err(ParseError::UnexpectedEndOfInput) => return Ok(None),
+            result => result?,
+        };
+        let Some(size) = size else {
+            return Ok(None);
+        };
+        let start = self.current_position();
+        let end = (start + size).min(self.data().len());
+        self.jump_to(end);
+        Ok(Some(&self.data()[start..end]))
+    }
+
+    /// Run a fallible closure on the scanner and capture the data it
+    /// consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run on the scanner.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure along with the consumed slice, errors are
+    /// propagated untouched.
+    pub fn with_slice<V>(
+        &mut self,
+        f: impl FnOnce(&mut Scanner<'a, T>) -> ParseResult<V>,
+    ) -> ParseResult<(V, &'a [T])> {
+        let start = self.current_position();
+        let value = f(self)?;
+        let end = self.current_position().max(start);
+        Ok((value, &self.data()[start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::matchers::match_pattern;
+    use crate::bytes::primitives::number::{Number, TokenNumber};
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_peek_len() {
+        let mut scanner = Scanner::new(b"123x");
+        assert_eq!(scanner.peek_len(&TokenNumber), Some(3));
+        assert_eq!(scanner.current_position(), 0);
+        assert_eq!(scanner.peek_len(&Token::Comma), None);
+        scanner.bump_by(3);
+        assert_eq!(scanner.peek_len(&TokenNumber), None);
+    }
+
+    #[test]
+    fn test_count_until() {
+        let data = b"abc,d";
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.count_until(&Token::Comma), Some(3));
+        assert_eq!(scanner.current_position(), 0);
+        scanner.bump_by(3);
+        assert_eq!(scanner.count_until(&Token::Comma), Some(0));
+        assert_eq!(scanner.count_until(&Token::Semicolon), None);
+    }
+
+    #[test]
+    fn test_consume_if_matches() {
+        let mut scanner = Scanner::new(b"(a");
+        assert_eq!(
+            scanner
+                .consume_if_matches(Token::OpenParen)
+                .expect("failed to parse"),
+            Some(&b"("[..])
+        );
+        assert_eq!(scanner.current_position(), 1);
+        assert_eq!(
+            scanner
+                .consume_if_matches(Token::OpenParen)
+                .expect("failed to parse"),
+            None
+        );
+        assert_eq!(scanner.current_position(), 1);
+
+        // the end of the data doesn't match either
+        scanner.bump_by(1);
+        assert_eq!(
+            scanner
+                .consume_if_matches(Token::OpenParen)
+                .expect("failed to parse"),
+            None
+        );
+        assert_eq!(scanner.current_position(), 2);
+        let mut scanner = Scanner::new(b"\r");
+        assert_eq!(
+            scanner
+                .consume_if_matches(Token::CrLn)
+                .expect("failed to parse"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_backtrack_limit() {
+        /// Consume `n` bytes then fail.
+        fn fail_after(n: usize) -> impl FnOnce(&mut Scanner<u8>) -> ParseResult<()> {
+            move |scanner| {
+                scanner.bump_by(n);
+                Err(ParseError::UnexpectedToken)
+            }
+        }
+
+        let mut scanner = Scanner::new(b"abcdefgh");
+        scanner.set_backtrack_limit(3);
+        assert!(matches!(
+            scanner.rollback_on_err(fail_after(3)),
+            Err(ParseError::UnexpectedToken)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        let err = scanner
+            .rollback_on_err(fail_after(4))
+            .expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::BacktrackLimitExceeded { offset: 0 }
+        ));
+        assert_eq!(err.to_string(), "Backtrack limit exceeded at offset 0");
+
+        // the limit counts from the furthest position reached, the innermost
+        // error being kept
+        scanner.bump_by(2);
+        let err = scanner
+            .rollback_on_err(|scanner| scanner.rollback_on_err(fail_after(4)))
+            .expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::BacktrackLimitExceeded { offset: 2 }
+        ));
+        assert!(matches!(
+            scanner.rollback_on_err(fail_after(0)),
+            Err(ParseError::BacktrackLimitExceeded { offset: 2 })
+        ));
+
+        // unlimited by default
+        let mut scanner = Scanner::new(b"abcdefgh");
+        assert!(matches!(
+            scanner.rollback_on_err(fail_after(8)),
+            Err(ParseError::UnexpectedToken)
+        ));
+    }
+
+    #[test]
+    fn test_map_view() {
+        let mut scanner = Scanner::new(b"x=ABC");
+        scanner.bump_by(2);
+        let lowercase = scanner.map_view(u8::to_ascii_lowercase);
+        assert_eq!(lowercase, b"abc");
+        assert_eq!(scanner.remaining(), b"ABC");
+
+        let mut view = Scanner::new(&lowercase);
+        let (matched, size) = match_pattern(b"abc", view.remaining());
+        assert!(matched);
+        view.bump_by(size);
+        assert_eq!(scanner.current_position() + view.current_position(), 5);
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let mut scanner = Scanner::new(b"a;b;");
+        assert!(scanner.ends_with(b";"));
+        assert!(scanner.ends_with(b"b;"));
+        assert!(scanner.ends_with(b""));
+        assert!(!scanner.ends_with(b"b"));
+
+        // only the remaining data is considered
+        scanner.bump_by(3);
+        assert!(scanner.ends_with(b";"));
+        assert!(!scanner.ends_with(b"b;"));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let data = b"abc";
+        let mut scanner = Scanner::new(data);
+        scanner.bump_by(1);
+        let snapshot = scanner.snapshot();
+        scanner.bump_by(2);
+        assert!(scanner.is_empty());
+
+        assert_eq!(snapshot.position(), 1);
+        assert_eq!(snapshot.peek(), Some(&b'b'));
+        assert_eq!(snapshot.remaining(), b"bc");
+        assert!(snapshot.starts_with(b"bc"));
+        assert!(!snapshot.starts_with(b"c"));
+
+        scanner.jump_to(snapshot.position());
+        assert_eq!(scanner.remaining(), b"bc");
+    }
+
+    #[test]
+    fn test_with_slice() {
+        let data = b"42+";
+        let mut scanner = Scanner::new(data);
+        let (number, slice) = scanner
+            .with_slice(|scanner| scanner.visit::<Number<usize>>())
+            .expect("failed to parse");
+        assert_eq!(number, Number(42));
+        assert_eq!(slice, b"42");
+        assert_eq!(scanner.current_position(), 2);
+
+        let result = scanner.with_slice(|scanner| scanner.visit::<Number<usize>>());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rollback_on_err() {
+        let data = b"12+";
+        let mut scanner = Scanner::new(data);
+        let result = scanner.rollback_on_err(|scanner| {
+            scanner.visit::<Number<usize>>()?;
+            recognize(Token::Dash, scanner)
+        });
+        assert!(matches!(result, Err(ParseError::UnexpectedToken)));
+        assert_eq!(scanner.current_position(), 0);
+
+        let result = scanner.rollback_on_err(|scanner| {
+            let number = scanner.visit::<Number<usize>>()?;
+            recognize(Token::Plus, scanner)?;
+            Ok(number)
+        });
+        assert_eq!(result.expect("failed to parse"), Number(12));
+        assert_eq!(scanner.current_position(), 3);
+    }
+
+    #[test]
+    fn test_consumed_since() {
+        use crate::visitor::Visitor;
+
+        let mut scanner = Scanner::new(b"12+345");
+        let start = scanner.checkpoint();
+        assert_eq!(scanner.consumed_since(start), 0);
+        recognize(Token::Plus, &mut scanner).expect_err("should fail");
+        assert_eq!(scanner.consumed_since(start), 0);
+
+        scanner.bump_by(3);
+        let operand = scanner.checkpoint();
+        let snapshot = scanner.snapshot();
+        Number::<usize>::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(scanner.consumed_since(operand), 3);
+        assert_eq!(scanner.consumed_since(start), 6);
+        assert_eq!(operand.position(), 3);
+        assert_eq!(snapshot.consumed_since(&scanner), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "the scanner has been moved back since")]
+    fn test_consumed_since_ahead() {
+        let mut scanner = Scanner::new(b"12");
+        scanner.bump_by(2);
+        let end = scanner.checkpoint();
+        scanner.jump_to(0);
+        scanner.consumed_since(end);
+    }
+
+    /// `(` Nested `+` / `(` Nested `-` / nothing, exponential on `((((x`.
+    #[derive(Debug)]
+    struct Nested;
+
+    impl<'a> crate::visitor::Visitor<'a, u8> for Nested {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> crate::errors::ParseResult<Self> {
+            for last in [Token::Plus, Token::Dash] {
+                let result = scanner.rollback_on_err(|scanner| {
+                    recognize(Token::OpenParen, scanner)?;
+                    Nested::accept(scanner)?;
+                    recognize(last, scanner)
+                });
+                match result {
+                    Ok(_) => return Ok(Nested),
+                    Err(err @ ParseError::BudgetExhausted { .. }) => return Err(err),
+                    Err(_) => {}
+                }
+            }
+            Ok(Nested)
+        }
+    }
+
+    #[test]
+    fn test_fuel() {
+        use crate::visitor::Visitor;
+
+        let data = b"((((((((((((x";
+
+        // unlimited by default
+        let mut scanner = Scanner::new(data);
+        assert_eq!(scanner.fuel(), None);
+        assert!(Nested::accept(&mut scanner).is_ok());
+        assert_eq!(scanner.fuel(), None);
+
+        let mut scanner = Scanner::new(data);
+        scanner.set_fuel(1_000_000);
+        assert!(Nested::accept(&mut scanner).is_ok());
+        assert!(scanner.fuel().expect("fuel should be set") < 1_000_000);
+
+        let mut scanner = Scanner::new(data);
+        scanner.set_fuel(50);
+        let err = Nested::accept(&mut scanner).expect_err("should run out of fuel");
+        assert!(matches!(err, ParseError::BudgetExhausted { .. }));
+        assert_eq!(scanner.fuel(), Some(0));
+        assert_eq!(
+            err.to_string(),
+            format!("Parse budget exhausted at offset {}", err.offset().unwrap())
+        );
+    }
 }
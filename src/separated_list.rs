@@ -1,6 +1,6 @@
 use crate::errors::{ParseError, ParseResult};
 use crate::scanner::Scanner;
-use crate::visitor::Visitor;
+use crate::visitor::{ParseContext, Visitor};
 use std::marker::PhantomData;
 
 #[derive(Debug)]
@@ -20,6 +20,7 @@ impl<T, V, S> SeparatedList<T, V, S> {
     /// # Returns
     ///
     /// An iterator over the elements of the `SeparatedList`.
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> impl Iterator<Item = V> {
         self.data.into_iter()
     }
@@ -36,6 +37,7 @@ impl<T, V, S> SeparatedList<T, V, S> {
 /// # Arguments
 ///
 /// * `scanner` - The scanner to use.
+/// * `context` - The parse context, forwarded to the visitors.
 ///
 /// # Returns
 ///
@@ -44,16 +46,19 @@ impl<T, V, S> SeparatedList<T, V, S> {
 /// # Errors
 ///
 /// Any error the visitor for the element or the separator returns.
-fn yield_element<'a, T, V, S>(scanner: &mut Scanner<'a, T>) -> ParseResult<YieldResult<V>>
+fn yield_element<'a, T, V, S>(
+    scanner: &mut Scanner<'a, T>,
+    context: &ParseContext,
+) -> ParseResult<YieldResult<V>>
 where
     V: Visitor<'a, T>,
     S: Visitor<'a, T>,
 {
     let cursor = scanner.current_position();
-    let element = match scanner.visit::<V>() {
+    let element = match scanner.visit_with::<V>(context) {
         Ok(element) => element,
         Err(err) => {
-            scanner.jump_to(cursor);
+            scanner.rewind_to(cursor)?;
             return Err(err);
         }
     };
@@ -63,7 +68,7 @@ where
     }
 
     // consume the separator if not the end of the slice
-    scanner.visit::<S>()?;
+    scanner.visit_with::<S>(context)?;
 
     Ok(YieldResult::MaybeNext(element))
 }
@@ -88,8 +93,16 @@ where
     ///
     /// Any error the visitor for the element or the separator returns, or
     /// `ParseError::UnexpectedToken` if the scanner is empty when attempting
-    /// to parse the separator.
+    /// to parse the separator, or `ParseError::NoProgress` if an element and
+    /// its separator consume nothing.
     fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        Self::accept_with(scanner, &ParseContext::default())
+    }
+
+    /// Accept a list of elements separated by a separator, a separator
+    /// after the last element is accepted if the context allows trailing
+    /// commas.
+    fn accept_with(scanner: &mut Scanner<'a, T>, context: &ParseContext) -> ParseResult<Self> {
         let mut elements = vec![];
         let cursor = scanner.current_position();
 
@@ -102,7 +115,8 @@ where
         }
 
         loop {
-            if let Ok(result) = yield_element::<T, V, S>(scanner) {
+            let start = scanner.current_position();
+            if let Ok(result) = yield_element::<T, V, S>(scanner, context) {
                 let element: YieldResult<V> = result;
 
                 match element {
@@ -110,12 +124,20 @@ where
                         elements.push(element);
                         break;
                     }
+                    // neither the element nor the separator consumed anything
+                    YieldResult::MaybeNext(_) if scanner.current_position() == start => {
+                        scanner.rewind_to(cursor)?;
+                        return Err(ParseError::NoProgress { offset: start });
+                    }
                     YieldResult::MaybeNext(element) => {
                         elements.push(element);
+                        if context.allow_trailing_comma && scanner.remaining().is_empty() {
+                            break;
+                        }
                     }
                 }
             } else {
-                scanner.jump_to(cursor);
+                scanner.rewind_to(cursor)?;
                 return Err(ParseError::UnexpectedToken);
             }
         }
@@ -131,11 +153,11 @@ where
 mod tests {
     use crate::bytes::primitives::number::Number;
     use crate::bytes::token::Token;
-    use crate::errors::ParseResult;
+    use crate::errors::{ParseError, ParseResult};
     use crate::recognizer::recognize;
     use crate::scanner::Scanner;
     use crate::separated_list::SeparatedList;
-    use crate::visitor::Visitor;
+    use crate::visitor::{ParseContext, Visitor};
 
     struct SeparatorComma;
 
@@ -171,4 +193,52 @@ mod tests {
             .expect("failed to parse");
         assert_eq!(result.data, vec![]);
     }
+
+    #[test]
+    fn test_trailing_separator() {
+        type NumberList = SeparatedList<u8, Number<usize>, SeparatorComma>;
+
+        let data = b"12,4,";
+        let mut scanner = Scanner::new(data);
+        assert!(scanner.visit::<NumberList>().is_err());
+        assert_eq!(scanner.current_position(), 0);
+
+        let context = ParseContext {
+            allow_trailing_comma: true,
+            ..Default::default()
+        };
+        let result = scanner
+            .visit_with::<NumberList>(&context)
+            .expect("failed to parse");
+        assert_eq!(result.data, vec![Number(12), Number(4)]);
+        assert!(scanner.is_empty());
+
+        let data = b"12,4";
+        let mut scanner = Scanner::new(data);
+        let result = scanner
+            .visit_with::<NumberList>(&context)
+            .expect("failed to parse");
+        assert_eq!(result.data, vec![Number(12), Number(4)]);
+    }
+
+    #[test]
+    fn test_no_progress() {
+        /// An element and a separator matching nothing.
+        struct Empty;
+
+        impl<'a> Visitor<'a, u8> for Empty {
+            fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+                recognize(None::<u8>, scanner)?;
+                Ok(Empty)
+            }
+        }
+
+        let data = b"a,b";
+        let mut scanner = Scanner::new(data);
+        assert!(matches!(
+            scanner.visit::<SeparatedList<u8, Empty, Empty>>(),
+            Err(ParseError::NoProgress { offset: 0 })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
 }
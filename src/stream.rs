@@ -0,0 +1,329 @@
+//! A scanner over data pulled by chunks.
+//!
+//! A [StreamScanner] buffers the chunks handed by a refill callback, a file
+//! or a socket read for example, in a window sliding over the data. The
+//! consumed data is dropped from the window when more data is pulled, so
+//! that the memory used is bounded by the longest element recognized rather
+//! than by the length of the data.
+//!
+//! The matchers and visitors run over the window as over the data of a
+//! streaming [Scanner]: when they need more data to decide, the window is
+//! refilled and they are run again.
+//!
+//! ```
+//! use noa_parser::bytes::primitives::number::TokenNumber;
+//! use noa_parser::stream::StreamScanner;
+//!
+//! let mut chunks = [&b"12"[..], b"34 "].into_iter();
+//! let mut scanner = StreamScanner::new(4, |buffer: &mut Vec<u8>| {
+//!     let chunk = chunks.next().unwrap_or_default();
+//!     buffer.extend_from_slice(chunk);
+//!     Ok(chunk.len())
+//! });
+//! assert_eq!(scanner.recognize(&TokenNumber).unwrap(), Some(&b"1234"[..]));
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::{Match, MatchResult, Needed};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// A scanner over a window of data refilled on demand.
+///
+/// The refill callback appends the next chunk to the buffer it is given and
+/// returns its length, `0` standing for the end of the data.
+pub struct StreamScanner<F> {
+    /// The buffered data, starting at `offset` in the data.
+    buffer: Vec<u8>,
+    /// The position of the cursor in the buffer.
+    position: usize,
+    /// The offset of the first byte of the buffer.
+    offset: usize,
+    /// The number of bytes the window is refilled to.
+    lookahead: usize,
+    /// Whether the refill callback reported the end of the data.
+    eof: bool,
+    refill: F,
+}
+
+impl<F: FnMut(&mut Vec<u8>) -> ParseResult<usize>> StreamScanner<F> {
+    /// Create a scanner pulling its data from `refill`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookahead` - The minimum number of bytes to buffer ahead of the
+    ///   cursor each time the window is refilled.
+    /// * `refill` - Appends the next chunk to the buffer, returning its
+    ///   length.
+    pub fn new(lookahead: usize, refill: F) -> Self {
+        StreamScanner {
+            buffer: vec![],
+            position: 0,
+            offset: 0,
+            lookahead,
+            eof: false,
+            refill,
+        }
+    }
+
+    /// Return the offset of the cursor in the data.
+    pub fn current_position(&self) -> usize {
+        self.offset + self.position
+    }
+
+    /// Return the buffered data ahead of the cursor.
+    pub fn window(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+
+    /// Return true once the refill callback reported the end of the data.
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Return true if there are no more bytes to scan, the window being
+    /// refilled first if needed.
+    ///
+    /// # Errors
+    ///
+    /// The errors of the refill callback.
+    pub fn is_empty(&mut self) -> ParseResult<bool> {
+        self.fill(1)?;
+        Ok(self.window().is_empty())
+    }
+
+    /// Move the cursor forward by `n` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the cursor is moved past the end of the window.
+    pub fn bump_by(&mut self, n: usize) {
+        assert!(
+            n <= self.window().len(),
+            "bumped past the end of the window"
+        );
+        self.position += n;
+    }
+
+    /// Buffer at least `n` bytes ahead of the cursor, and the lookahead of
+    /// the scanner if the data allows.
+    ///
+    /// The slices returned by the scanner are only valid until the window is
+    /// refilled, which the borrow checker enforces.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if the data ends before `n` bytes,
+    /// the errors of the refill callback.
+    pub fn ensure(&mut self, n: usize) -> ParseResult<()> {
+        self.fill(n)?;
+        if self.window().len() < n {
+            return Err(ParseError::UnexpectedEndOfInput);
+        }
+        Ok(())
+    }
+
+    /// Pull chunks until `n` bytes, and the lookahead, are buffered or the
+    /// data ends.
+    fn fill(&mut self, n: usize) -> ParseResult<()> {
+        let wanted = n.max(self.lookahead);
+        if self.eof || self.window().len() >= wanted {
+            return Ok(());
+        }
+        // drop the consumed data before growing the buffer
+        self.buffer.drain(..self.position);
+        self.offset += self.position;
+        self.position = 0;
+        while !self.eof && self.buffer.len() < wanted {
+            self.eof = (self.refill)(&mut self.buffer)? == 0;
+        }
+        Ok(())
+    }
+
+    /// Match the data at the cursor, refilling the window as long as the
+    /// matcher needs more data to decide, see [Match::matcher2].
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to run.
+    ///
+    /// # Returns
+    ///
+    /// The matched slice, consumed, `None` if the data doesn't match.
+    ///
+    /// # Errors
+    ///
+    /// The errors of the refill callback.
+    pub fn recognize<M: Match<u8>>(&mut self, matcher: &M) -> ParseResult<Option<&[u8]>> {
+        self.fill(1)?;
+        let size = loop {
+            if self.eof {
+                let (found, size) = matcher.matcher(self.window());
+                break found.then_some(size);
+            }
+            match matcher.matcher2(self.window()) {
+                MatchResult::Matched(size) => break Some(size),
+                MatchResult::NoMatch => break None,
+                MatchResult::Incomplete(needed) => self.fill_more(needed)?,
+            }
+        };
+        let Some(size) = size else {
+            return Ok(None);
+        };
+        let start = self.position;
+        self.bump_by(size);
+        Ok(Some(&self.buffer[start..start + size]))
+    }
+
+    /// Accept a visitor at the cursor, refilling the window as long as the
+    /// visitor fails with `ParseError::Incomplete`.
+    ///
+    /// The visitor runs over a streaming [Scanner] of the window, or a
+    /// regular one once the data ended, so it can't borrow the data.
+    ///
+    /// # Errors
+    ///
+    /// The errors of the visitor, those of the refill callback.
+    pub fn visit<V: for<'a> Visitor<'a, u8>>(&mut self) -> ParseResult<V> {
+        self.fill(1)?;
+        loop {
+            let mut scanner = match self.eof {
+                true => Scanner::new(self.window()),
+                false => Scanner::streaming(self.window()),
+            };
+            match V::accept(&mut scanner) {
+                Err(ParseError::Incomplete { needed }) if !self.eof => self.fill_more(needed)?,
+                Err(err) => return Err(err),
+                Ok(value) => {
+                    let consumed = scanner.current_position();
+                    self.bump_by(consumed);
+                    return Ok(value);
+                }
+            }
+        }
+    }
+
+    /// Grow the window for a matcher or a visitor needing more data.
+    fn fill_more(&mut self, needed: Needed) -> ParseResult<()> {
+        let more = match needed {
+            Needed::Size(size) => size.max(1),
+            Needed::Unknown => 1,
+        };
+        self.fill(self.window().len() + more)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::{Number, TokenNumber};
+    use crate::bytes::primitives::string::TokenQuotedString;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::stream::StreamScanner;
+
+    /// Hand the data by chunks of `size` bytes.
+    fn chunked(data: &[u8], size: usize) -> impl FnMut(&mut Vec<u8>) -> ParseResult<usize> {
+        let mut chunks = data.chunks(size);
+        move |buffer| {
+            let chunk = chunks.next().unwrap_or_default();
+            buffer.extend_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_stream_scanner() {
+        let mut scanner = StreamScanner::new(2, chunked(b"12345,6", 2));
+        assert_eq!(
+            scanner.recognize(&TokenNumber).expect("failed to parse"),
+            Some(&b"12345"[..])
+        );
+        assert_eq!(scanner.current_position(), 5);
+        assert_eq!(
+            scanner
+                .recognize(&Token::Semicolon)
+                .expect("failed to parse"),
+            None
+        );
+        assert_eq!(
+            scanner.recognize(&Token::Comma).expect("failed to parse"),
+            Some(&b","[..])
+        );
+        // the last number ends with the data
+        let Number(value) = scanner.visit::<Number<usize>>().expect("failed to parse");
+        assert_eq!(value, 6);
+        assert!(scanner.is_empty().expect("failed to refill"));
+        assert!(scanner.is_eof());
+        assert_eq!(scanner.current_position(), 7);
+    }
+
+    #[test]
+    fn test_stream_scanner_split() {
+        crate::tokens! {
+            Keyword:
+                Arrow => "->",
+                Select => keyword "select",
+                From => keyword "from",
+        }
+
+        // every element is split across two chunks
+        let mut scanner = StreamScanner::new(1, chunked(br#"->select from"a b""#, 3));
+        for matcher in [Keyword::Arrow, Keyword::Select] {
+            assert!(
+                scanner
+                    .recognize(&matcher)
+                    .expect("failed to parse")
+                    .is_some()
+            );
+        }
+        assert_eq!(
+            scanner
+                .recognize(&Token::Whitespace)
+                .expect("failed to parse"),
+            Some(&b" "[..])
+        );
+        assert_eq!(
+            scanner.recognize(&Keyword::From).expect("failed to parse"),
+            Some(&b"from"[..])
+        );
+        assert_eq!(
+            scanner
+                .recognize(&TokenQuotedString)
+                .expect("failed to parse"),
+            Some(&br#""a b""#[..])
+        );
+
+        // the keyword is only decided by the byte following it
+        let mut scanner = StreamScanner::new(1, chunked(b"selection", 6));
+        assert_eq!(
+            scanner
+                .recognize(&Keyword::Select)
+                .expect("failed to parse"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stream_scanner_window() {
+        let mut scanner = StreamScanner::new(3, chunked(b"abcdefgh", 2));
+        scanner.ensure(1).expect("failed to refill");
+        assert_eq!(scanner.window(), b"abcd");
+        scanner.bump_by(3);
+
+        // the consumed data is dropped on refill
+        scanner.ensure(4).expect("failed to refill");
+        assert_eq!(scanner.window(), b"defgh");
+        assert_eq!(scanner.current_position(), 3);
+        assert!(matches!(
+            scanner.ensure(6),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+
+        // the errors of the callback are propagated
+        let mut scanner = StreamScanner::new(1, |_: &mut Vec<u8>| Err(ParseError::UnexpectedToken));
+        assert!(matches!(
+            scanner.recognize(&Token::Comma),
+            Err(ParseError::UnexpectedToken)
+        ));
+    }
+}
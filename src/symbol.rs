@@ -0,0 +1,185 @@
+//! Classification of the elements a scanner works on.
+//!
+//! The byte and char matchers are both implemented on top of the generic
+//! matchers of this module, so that they share the same semantics.
+
+/// An element of the data to scan which can be classified.
+pub trait Classify: Copy + PartialEq {
+    /// Convert an ASCII byte to the symbol.
+    fn from_ascii(byte: u8) -> Self;
+
+    /// Return true if the symbol is an ASCII decimal digit.
+    fn is_digit(&self) -> bool;
+
+    /// Return true if the symbol is an ASCII whitespace.
+    fn is_whitespace(&self) -> bool;
+
+    /// Return true if the symbol can start an identifier.
+    fn is_identifier_start(&self) -> bool;
+
+    /// Return true if the symbol can continue an identifier.
+    fn is_identifier_continue(&self) -> bool;
+
+    /// Return true if both symbols are equal, ignoring ASCII case.
+    fn eq_ignore_case(&self, other: &Self) -> bool;
+}
+
+impl Classify for u8 {
+    fn from_ascii(byte: u8) -> Self {
+        byte
+    }
+
+    fn is_digit(&self) -> bool {
+        self.is_ascii_digit()
+    }
+
+    fn is_whitespace(&self) -> bool {
+        self.is_ascii_whitespace()
+    }
+
+    fn is_identifier_start(&self) -> bool {
+        self.is_ascii_alphabetic() || *self == b'_'
+    }
+
+    fn is_identifier_continue(&self) -> bool {
+        self.is_ascii_alphanumeric() || *self == b'_'
+    }
+
+    fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.eq_ignore_ascii_case(other)
+    }
+}
+
+impl Classify for char {
+    fn from_ascii(byte: u8) -> Self {
+        byte as char
+    }
+
+    fn is_digit(&self) -> bool {
+        self.is_ascii_digit()
+    }
+
+    fn is_whitespace(&self) -> bool {
+        self.is_ascii_whitespace()
+    }
+
+    fn is_identifier_start(&self) -> bool {
+        self.is_ascii_alphabetic() || *self == '_'
+    }
+
+    fn is_identifier_continue(&self) -> bool {
+        self.is_ascii_alphanumeric() || *self == '_'
+    }
+
+    fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.eq_ignore_ascii_case(other)
+    }
+}
+
+/// Match a single symbol, one symbol is always reported as consumed.
+pub(crate) fn match_symbol<T: Classify>(pattern: T, data: &[T]) -> (bool, usize) {
+    (data.first() == Some(&pattern), 1)
+}
+
+/// Match a sequence of symbols, ignoring ASCII case.
+pub(crate) fn match_sequence<T: Classify>(
+    pattern: impl ExactSizeIterator<Item = T>,
+    data: &[T],
+) -> (bool, usize) {
+    let size = pattern.len();
+    if size == 0 || size > data.len() {
+        return (false, 0);
+    }
+
+    if pattern
+        .zip(data)
+        .all(|(expected, found)| expected.eq_ignore_case(found))
+    {
+        return (true, size);
+    }
+
+    (false, 0)
+}
+
+/// Match a run of symbols satisfying a predicate, at least one is required.
+pub(crate) fn match_while<T: Classify>(
+    data: &[T],
+    predicate: impl Fn(&T) -> bool,
+) -> (bool, usize) {
+    let pos = data
+        .iter()
+        .position(|symbol| !predicate(symbol))
+        .unwrap_or(data.len());
+    (pos > 0, pos)
+}
+
+/// Match a run of ASCII decimal digits.
+pub(crate) fn match_digits<T: Classify>(data: &[T]) -> (bool, usize) {
+    match_while(data, T::is_digit)
+}
+
+/// Match a run of ASCII whitespaces.
+pub(crate) fn match_whitespaces<T: Classify>(data: &[T]) -> (bool, usize) {
+    match_while(data, T::is_whitespace)
+}
+
+/// Match a run of Unicode whitespaces, see [char::is_whitespace].
+pub(crate) fn match_unicode_whitespaces(data: &[char]) -> (bool, usize) {
+    match_while(data, |symbol| char::is_whitespace(*symbol))
+}
+
+/// Match an ASCII letter or `_` followed by ASCII alphanumerics or `_`.
+pub(crate) fn match_identifier<T: Classify>(data: &[T]) -> (bool, usize) {
+    match data.first() {
+        Some(symbol) if symbol.is_identifier_start() => {}
+        _ => return (false, 0),
+    }
+    match_while(data, T::is_identifier_continue)
+}
+
+/// Return true if the char can start a Unicode identifier, either `_` or
+/// an XID_Start code point.
+#[cfg(feature = "unicode")]
+pub(crate) fn is_unicode_identifier_start(symbol: char) -> bool {
+    // ASCII fast path, avoiding the table lookup
+    if symbol.is_ascii() {
+        return symbol.is_identifier_start();
+    }
+    unicode_ident::is_xid_start(symbol)
+}
+
+/// Return true if the char can continue a Unicode identifier, an
+/// XID_Continue code point.
+#[cfg(feature = "unicode")]
+pub(crate) fn is_unicode_identifier_continue(symbol: char) -> bool {
+    if symbol.is_ascii() {
+        return symbol.is_identifier_continue();
+    }
+    unicode_ident::is_xid_continue(symbol)
+}
+
+/// Match a string enclosed in double quotes, `\` escaping the next symbol.
+pub(crate) fn match_quoted_string<T: Classify>(data: &[T]) -> (bool, usize) {
+    match_quoted(T::from_ascii(b'"'), T::from_ascii(b'\\'), data)
+}
+
+/// Match a string enclosed in `quote`, `escape` escaping the next symbol.
+pub(crate) fn match_quoted<T: Classify>(quote: T, escape: T, data: &[T]) -> (bool, usize) {
+    if data.first() != Some(&quote) {
+        return (false, 0);
+    }
+
+    let mut pos = 1;
+    while pos < data.len() {
+        if data[pos] == escape {
+            pos += 2;
+        } else if data[pos] == quote {
+            return (true, pos + 1);
+        } else {
+            pos += 1;
+        }
+    }
+
+    // unterminated string
+    (false, 0)
+}
@@ -0,0 +1,18 @@
+//! Trace output of the parsing.
+//!
+//! Traced recognizers, see [crate::recognizer::Recognizable::traced], report
+//! their entry and exit at the `trace` level of the `log` crate, under the
+//! `noa_parser` target. Nothing is emitted without the `log` feature.
+
+use std::fmt;
+
+/// Send a trace line to the logger, the line is only formatted if the trace
+/// level is enabled.
+#[cfg(feature = "log")]
+pub(crate) fn trace(line: fmt::Arguments) {
+    log::trace!(target: "noa_parser", "{line}");
+}
+
+/// Discard the trace line, the `log` feature being disabled.
+#[cfg(not(feature = "log"))]
+pub(crate) fn trace(_line: fmt::Arguments) {}
@@ -1,7 +1,11 @@
 //! A visitor is a trait that allows to define how to visit a `Scanner`.
 
-use crate::errors::ParseResult;
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::Match;
 use crate::scanner::Scanner;
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::Range;
 
 /// A `Visitor` is a trait that allows to define how to visit a `Scanner`.
 ///
@@ -15,6 +19,13 @@ use crate::scanner::Scanner;
 /// # Associated Functions
 ///
 /// * `accept` - Try to accept the `Scanner` and return the result of the visit.
+/// * `accept_with` - Same as `accept`, but with a [ParseContext] for
+///   mode-dependent grammars.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a visitor",
+    label = "doesn't implement `Visitor`",
+    note = "in `seq!` and `alt!`, a step naming a type accepts a visitor, a step after `=` recognizes an object"
+)]
 pub trait Visitor<'a, T>: Sized {
     /// Try to accept the `Scanner` and return the result of the visit.
     ///
@@ -26,4 +37,400 @@ pub trait Visitor<'a, T>: Sized {
     ///
     /// The result of the visit.
     fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self>;
+
+    /// Try to accept the `Scanner` according to a parse context.
+    ///
+    /// The default implementation ignores the context and calls `accept`.
+    /// Visitors depending on the context override this method and implement
+    /// `accept` with the default context.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to accept.
+    /// * `context` - The flags driving the grammar.
+    ///
+    /// # Returns
+    ///
+    /// The result of the visit.
+    fn accept_with(scanner: &mut Scanner<'a, T>, context: &ParseContext) -> ParseResult<Self> {
+        let _ = context;
+        Self::accept(scanner)
+    }
+}
+
+/// Flags altering the behavior of context aware visitors.
+///
+/// The default context is the strict one, used by `Visitor::accept`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParseContext {
+    /// Accept a separator after the last element of a list.
+    pub allow_trailing_comma: bool,
+    /// Skip every Unicode `White_Space` code point instead of ASCII spaces
+    /// only.
+    pub unicode_whitespace: bool,
+}
+
+/// A visitor accepting `V` and discarding it, for an element of a sequence
+/// only mattering for the data it consumes.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Skip<V>(pub PhantomData<V>);
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Skip<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        V::accept(scanner)?;
+        Ok(Skip(PhantomData))
+    }
+
+    fn accept_with(scanner: &mut Scanner<'a, T>, context: &ParseContext) -> ParseResult<Self> {
+        V::accept_with(scanner, context)?;
+        Ok(Skip(PhantomData))
+    }
+}
+
+/// A value along with the span of the data it has been accepted from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<V> {
+    /// The accepted value.
+    pub value: V,
+    /// The positions of the data accepted, end excluded.
+    pub span: Range<usize>,
+}
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Spanned<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let value = V::accept(scanner)?;
+        Ok(Spanned {
+            value,
+            span: start..scanner.current_position(),
+        })
+    }
+
+    fn accept_with(scanner: &mut Scanner<'a, T>, context: &ParseContext) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let value = V::accept_with(scanner, context)?;
+        Ok(Spanned {
+            value,
+            span: start..scanner.current_position(),
+        })
+    }
+}
+
+/// The elements accepted so far by [fill], dropped if a later one fails.
+struct Filled<V, const N: usize> {
+    elements: MaybeUninit<[V; N]>,
+    /// The number of elements initialized, from the start of the array.
+    len: usize,
+}
+
+impl<V, const N: usize> Filled<V, N> {
+    fn push(&mut self, element: V) {
+        debug_assert!(self.len < N, "the array is full");
+        // SAFETY: the slot is in bounds, and not initialized yet
+        unsafe {
+            let slot = self.elements.as_mut_ptr().cast::<V>().add(self.len);
+            slot.write(element);
+        }
+        self.len += 1;
+    }
+
+    fn into_array(self) -> [V; N] {
+        debug_assert_eq!(self.len, N, "the array is not full");
+        let filled = ManuallyDrop::new(self);
+        // SAFETY: every element is initialized, and won't be dropped by the
+        // guard anymore
+        unsafe { filled.elements.assume_init_read() }
+    }
+}
+
+impl<V, const N: usize> Drop for Filled<V, N> {
+    fn drop(&mut self) {
+        let elements = self.elements.as_mut_ptr().cast::<V>();
+        // SAFETY: the first `len` elements are initialized
+        unsafe { std::ptr::slice_from_raw_parts_mut(elements, self.len).drop_in_place() }
+    }
+}
+
+/// Accept exactly `N` visitors in a row into an array.
+///
+/// No allocation is made on success.
+///
+/// # Type Parameters
+///
+/// * `V` - The type of the visitor to accept.
+/// * `N` - The number of visitors to accept.
+///
+/// # Arguments
+///
+/// * `scanner` - The scanner to accept.
+///
+/// # Returns
+///
+/// * `Ok([V; N])` the accepted visitors,
+/// * `Err(ParseError::Element)` holding the index of the failing visitor,
+///   the scanner is then rewound to its position before the first one.
+pub fn fill<'a, T, V: Visitor<'a, T>, const N: usize>(
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<[V; N]> {
+    let cursor = scanner.current_position();
+    let mut filled = Filled::<V, N> {
+        elements: MaybeUninit::uninit(),
+        len: 0,
+    };
+    while filled.len < N {
+        let offset = scanner.current_position();
+        match V::accept(scanner) {
+            Ok(element) => filled.push(element),
+            Err(source) => {
+                let index = filled.len;
+                // the accepted elements are dropped before rewinding
+                drop(filled);
+                scanner.rewind_to(cursor)?;
+                return Err(ParseError::Element {
+                    index,
+                    offset,
+                    source: Box::new(source),
+                });
+            }
+        }
+    }
+    Ok(filled.into_array())
+}
+
+/// Accept a visitor, skipping to a synchronization point if it fails.
+///
+/// On failure, the data up to the next match of `sync` is skipped and
+/// handed to `make_error`, so that the parse goes on after a malformed
+/// element: an error node stands for the element in the result. The
+/// synchronization point itself is not consumed.
+///
+/// # Arguments
+///
+/// * `scanner` - The scanner to accept.
+/// * `sync` - The matcher of the synchronization point, the end of the data
+///   being used if it doesn't match.
+/// * `make_error` - Builds the error node from the span of the skipped data.
+///
+/// # Returns
+///
+/// * `Ok(V)` the accepted visitor or the error node,
+/// * `Err(ParseError::Incomplete)`, `Err(ParseError::BudgetExhausted)` or
+///   `Err(ParseError::BacktrackLimitExceeded)` which can't be recovered
+///   from.
+pub fn recover_with<'a, T, V: Visitor<'a, T>, M: Match<T>>(
+    scanner: &mut Scanner<'a, T>,
+    sync: M,
+    make_error: impl FnOnce(Range<usize>) -> V,
+) -> ParseResult<V> {
+    let start = scanner.current_position();
+    match scanner.rollback_on_err(V::accept) {
+        Ok(value) => Ok(value),
+        Err(err) if err.is_unrecoverable() => Err(err),
+        Err(_) => {
+            let skipped = scanner
+                .count_until(&sync)
+                .unwrap_or(scanner.remaining().len());
+            scanner.bump_by(skipped);
+            Ok(make_error(start..start + skipped))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{ParseError, ParseResult};
+    use crate::scanner::Scanner;
+    use crate::visitor::{Skip, Spanned, Visitor, fill, recover_with};
+
+    /// A little endian `u16`.
+    #[derive(Debug, PartialEq)]
+    struct U16Le(u16);
+
+    impl Visitor<'_, u8> for U16Le {
+        fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+            match scanner.remaining() {
+                [low, high, ..] => {
+                    let value = u16::from_le_bytes([*low, *high]);
+                    scanner.bump_by(2);
+                    Ok(U16Le(value))
+                }
+                _ => Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        let data = [1, 0, 2, 0, 0xff, 0xff, 4, 1, 9];
+        let mut scanner = Scanner::new(&data);
+        let values = fill::<_, U16Le, 4>(&mut scanner).expect("failed to parse");
+        assert_eq!(values.map(|U16Le(value)| value), [1, 2, 0xffff, 0x104]);
+        assert_eq!(scanner.current_position(), 8);
+
+        let values = fill::<_, U16Le, 0>(&mut scanner).expect("failed to parse");
+        assert_eq!(values, []);
+        assert_eq!(scanner.current_position(), 8);
+    }
+
+    #[test]
+    fn test_fill_failure() {
+        // the values own heap data, dropping the accepted ones must be sound
+        #[derive(Debug)]
+        struct Owned(#[allow(dead_code)] Vec<u8>);
+
+        impl Visitor<'_, u8> for Owned {
+            fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+                let U16Le(value) = U16Le::accept(scanner)?;
+                Ok(Owned(value.to_le_bytes().to_vec()))
+            }
+        }
+
+        let data = [1, 0, 2, 0, 3];
+        let mut scanner = Scanner::new(&data);
+        let err = fill::<_, Owned, 3>(&mut scanner).expect_err("should fail");
+        assert!(matches!(
+            err,
+            ParseError::Element { index: 2, offset: 4, ref source }
+                if matches!(**source, ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        assert_eq!(
+            err.to_string(),
+            "Element 2 at offset 4: Unexpected end of input"
+        );
+
+        // the accepted elements are dropped once, on failure and on success
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug)]
+        struct Counted;
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        impl Visitor<'_, u8> for Counted {
+            fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+                U16Le::accept(scanner)?;
+                Ok(Counted)
+            }
+        }
+
+        let mut scanner = Scanner::new(&data);
+        fill::<_, Counted, 3>(&mut scanner).expect_err("should fail");
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 2);
+        let mut scanner = Scanner::new(&data);
+        drop(fill::<_, Counted, 2>(&mut scanner).expect("failed to parse"));
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_skip() {
+        use crate::bytes::primitives::number::Number;
+
+        let mut scanner = Scanner::new(b"42,");
+        assert!(Skip::<Number<usize>>::accept(&mut scanner).is_ok());
+        assert_eq!(scanner.current_position(), 2);
+        assert!(Skip::<Number<usize>>::accept(&mut scanner).is_err());
+        assert_eq!(scanner.current_position(), 2);
+    }
+
+    #[test]
+    fn test_spanned() {
+        use crate::bytes::primitives::number::Number;
+
+        let mut scanner = Scanner::new(b"1,22");
+        scanner.bump_by(2);
+        let number = Spanned::<Number<usize>>::accept(&mut scanner).expect("failed to parse");
+        assert_eq!(number.value.0, 22);
+        assert_eq!(number.span, 2..4);
+    }
+
+    /// `name=number`, or the error node of a malformed statement.
+    #[derive(Debug, PartialEq)]
+    enum Statement<'a> {
+        Assign(&'a [u8], usize),
+        Error(std::ops::Range<usize>),
+    }
+
+    impl<'a> Visitor<'a, u8> for Statement<'a> {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            use crate::bytes::primitives::identifier::TokenIdentifier;
+            use crate::bytes::primitives::number::Number;
+            use crate::bytes::token::Token;
+            use crate::recognizer::recognize;
+
+            let name = recognize(TokenIdentifier, scanner)?;
+            recognize(Token::Equal, scanner)?;
+            let Number(value) = Number::accept(scanner)?;
+            Ok(Statement::Assign(name, value))
+        }
+    }
+
+    #[test]
+    fn test_recover_with() {
+        use crate::bytes::token::Token;
+
+        let mut scanner = Scanner::new(b"x=1;y=+2;z=3");
+        let mut statements = vec![];
+        loop {
+            statements.push(
+                recover_with(&mut scanner, Token::Semicolon, Statement::Error)
+                    .expect("failed to recover"),
+            );
+            if !scanner.eat(Token::Semicolon) {
+                break;
+            }
+        }
+        assert_eq!(
+            statements,
+            [
+                Statement::Assign(b"x", 1),
+                Statement::Error(4..8),
+                Statement::Assign(b"z", 3)
+            ]
+        );
+
+        // without synchronization point, the rest of the data is skipped
+        let mut scanner = Scanner::new(b"x=;y");
+        let statement =
+            recover_with(&mut scanner, Token::Comma, Statement::Error).expect("failed to recover");
+        assert_eq!(statement, Statement::Error(0..4));
+        assert!(scanner.is_empty());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        use crate::bytes::primitives::number::Number;
+        use crate::bytes::token::Token;
+
+        #[derive(crate::Visitor)]
+        #[separator(Token::Whitespace)]
+        struct Pair {
+            first: Number<usize>,
+            second: Number<usize>,
+        }
+
+        #[derive(crate::Visitor)]
+        #[separator(Token::Semicolon)]
+        struct Statements<'a>(Statement<'a>, Statement<'a>);
+
+        let pair = Pair::accept(&mut Scanner::new(b"1 2")).expect("failed to parse");
+        assert_eq!((pair.first.0, pair.second.0), (1, 2));
+
+        // a field failing rewinds the scanner
+        let mut scanner = Scanner::new(b"1 x");
+        assert!(Pair::accept(&mut scanner).is_err());
+        assert_eq!(scanner.current_position(), 0);
+        assert!(Pair::accept(&mut Scanner::new(b"12")).is_err());
+
+        let statements =
+            Statements::accept(&mut Scanner::new(b"x=1;y=2")).expect("failed to parse");
+        assert_eq!(statements.0, Statement::Assign(b"x", 1));
+        assert_eq!(statements.1, Statement::Assign(b"y", 2));
+    }
 }
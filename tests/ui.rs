@@ -0,0 +1,9 @@
+//! Diagnostics of the macros misused, see the `.stderr` files of `tests/ui`.
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/seq_*.rs");
+    #[cfg(feature = "derive")]
+    cases.compile_fail("tests/ui/derive_match_*.rs");
+}
@@ -0,0 +1,8 @@
+use noa_parser::Match;
+
+#[derive(Match)]
+#[pattern("a")]
+#[pattern("b")]
+struct Duplicate;
+
+fn main() {}
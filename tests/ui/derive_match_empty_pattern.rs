@@ -0,0 +1,7 @@
+use noa_parser::Match;
+
+#[derive(Match)]
+#[pattern("")]
+struct Empty;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use noa_parser::Match;
+
+#[derive(Match)]
+#[pattern(42)]
+struct Invalid;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use noa_parser::Match;
+
+#[derive(Match)]
+struct Missing;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use noa_parser::Match;
+
+#[derive(Match)]
+#[pattern("é")]
+#[chars]
+struct Accent;
+
+fn main() {}
@@ -0,0 +1,11 @@
+use noa_parser::bytes::token::Token;
+use noa_parser::errors::ParseResult;
+use noa_parser::scanner::Scanner;
+use noa_parser::seq;
+
+fn plus(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    seq!(scanner => _: Token);
+    Ok(())
+}
+
+fn main() {}
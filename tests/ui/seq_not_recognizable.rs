@@ -0,0 +1,10 @@
+use noa_parser::errors::ParseResult;
+use noa_parser::scanner::Scanner;
+use noa_parser::seq;
+
+fn number(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    seq!(scanner => _ = "42");
+    Ok(())
+}
+
+fn main() {}